@@ -4,9 +4,11 @@ use std::io::{self, Read, Write};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
 enum From {
-    /// Whichever succeeds first of bytes, hex then diag
+    /// Bytes if starting with a magic number (as a sequence if it marks one), otherwise whichever
+    /// succeeds first of bytes, hex then diag
     Auto,
-    /// Hex-encoded bytes, ignores whitespace and comments from `#` to the end of line
+    /// Hex-encoded bytes, ignores whitespace and comments from `#` to the end of line, or the
+    /// output of `xxd`, `hexdump -C` and `od -t x1`, or bytes with `0x` prefixes as in a C array
     Hex,
     /// Raw encoded bytes
     Bytes,
@@ -35,6 +37,63 @@ enum To {
     Debug,
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+enum Profile {
+    /// Label the entries of COSE_Key and COSE_KeySet structures
+    Cose,
+}
+
+impl Profile {
+    fn to_annotation_profile(self) -> cbor_diag::AnnotationProfile {
+        match self {
+            Profile::Cose => cbor_diag::AnnotationProfile::Cose,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+enum TypedArrays {
+    /// As a plain byte string
+    Bytes,
+    /// As a plain byte string followed by a comment containing the elements
+    Commented,
+    /// As a byte string literal of the elements, e.g. `f32le'1.5, 2.0'`
+    Literal,
+}
+
+impl TypedArrays {
+    fn to_typed_array_style(self) -> cbor_diag::TypedArrayStyle {
+        match self {
+            TypedArrays::Bytes => cbor_diag::TypedArrayStyle::Bytes,
+            TypedArrays::Commented => cbor_diag::TypedArrayStyle::Commented,
+            TypedArrays::Literal => cbor_diag::TypedArrayStyle::Literal,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
+enum ByteStrings {
+    /// As a string with any non-printable or non-ASCII bytes escaped
+    Ascii,
+    /// As text if printable, otherwise as base16 with hints about well-known binary values
+    Detect,
+    /// As base64url
+    Base64,
+    /// As base32
+    Base32,
+}
+
+impl ByteStrings {
+    fn to_byte_string_style(self) -> cbor_diag::ByteStringStyle {
+        match self {
+            ByteStrings::Ascii => cbor_diag::ByteStringStyle::Ascii,
+            ByteStrings::Detect => cbor_diag::ByteStringStyle::Detect,
+            ByteStrings::Base64 => cbor_diag::ByteStringStyle::Base64,
+            ByteStrings::Base32 => cbor_diag::ByteStringStyle::Base32,
+        }
+    }
+}
+
 #[derive(Debug, Parser)]
 #[command(version)]
 /// A utility for converting between binary, diagnostic, hex and annotated hex
@@ -48,10 +107,58 @@ struct Args {
     #[arg(long, default_value_t = To::Diag, value_enum)]
     to: To,
 
-    /// Parse a series of undelimited CBOR data items in binary format (a.k.a. the `cbor-seq` data
-    /// type).
-    #[arg(long, conflicts_with("from"))]
+    /// Parse and output a sequence of CBOR data items (a.k.a. the `cbor-seq` data type), one
+    /// directly after another in bytes and hex or separated by commas in diagnostic notation. Binary
+    /// input, including when not specified, is read and output an item at a time; hex and diag
+    /// sequences must be asked for with `--from`.
+    #[arg(long)]
     seq: bool,
+
+    /// An application-specific annotation profile to apply when outputting annotated hex
+    #[arg(long, value_enum)]
+    profile: Option<Profile>,
+
+    /// Follow multi-dimensional arrays in diagnostic notation output with a comment showing their
+    /// elements as nested rows
+    #[arg(long)]
+    expand_arrays: bool,
+
+    /// How to output the contents of typed arrays in diagnostic notation
+    #[arg(long, default_value_t = TypedArrays::Bytes, value_enum)]
+    typed_arrays: TypedArrays,
+
+    /// How to annotate the contents of byte strings without an encoding hint tag in annotated hex
+    #[arg(long, default_value_t = ByteStrings::Ascii, value_enum)]
+    byte_strings: ByteStrings,
+
+    /// Decode untagged byte strings that contain well-formed CBOR, as `<<...>>` in diagnostic
+    /// notation or a nested annotation
+    #[arg(long)]
+    embedded_cbor: bool,
+
+    /// Output bignums in diagnostic notation as plain integer literals, where they parse back to
+    /// the same encoding
+    #[arg(long)]
+    bignum_literals: bool,
+
+    /// Escape all non-ASCII characters of text strings in diagnostic notation
+    #[arg(long)]
+    escape_non_ascii: bool,
+
+    /// Output datetimes, IP addresses and UUIDs in diagnostic notation as application-extension
+    /// literals such as `dt'...'`
+    #[arg(long)]
+    app_literals: bool,
+
+    /// Truncate arrays and maps to this many items, and strings to this many bytes, in diagnostic
+    /// notation, marking the rest as elided with `...`
+    #[arg(long)]
+    truncate: Option<usize>,
+
+    /// Output an encoding indicator for every known width in diagnostic notation, including `_i`
+    /// for immediate values and the lengths of strings, arrays and maps
+    #[arg(long)]
+    explicit_encoding: bool,
 }
 
 trait ReadExt: Read {
@@ -66,10 +173,36 @@ trait ReadExt: Read {
 
 impl<R: Read> ReadExt for R {}
 
-fn output_item(value: cbor_diag::DataItem, to: To, mut output: impl Write) -> anyhow::Result<()> {
-    match to {
+fn hex_options(args: &Args) -> cbor_diag::HexOptions {
+    cbor_diag::HexOptions {
+        profile: args.profile.map(Profile::to_annotation_profile),
+        embedded_cbor: args.embedded_cbor.then(cbor_diag::EmbeddedCbor::default),
+        byte_strings: args.byte_strings.to_byte_string_style(),
+    }
+}
+
+fn diag_options(args: &Args) -> cbor_diag::DiagOptions {
+    cbor_diag::DiagOptions {
+        pretty: args.to == To::Diag,
+        expand_arrays: args.expand_arrays,
+        typed_arrays: args.typed_arrays.to_typed_array_style(),
+        embedded_cbor: args.embedded_cbor.then(cbor_diag::EmbeddedCbor::default),
+        bignum_literals: args.bignum_literals,
+        escape_non_ascii: args.escape_non_ascii,
+        app_literals: args.app_literals,
+        truncate: args.truncate,
+        explicit_encoding: args.explicit_encoding,
+    }
+}
+
+fn output_item(
+    value: cbor_diag::DataItem,
+    args: &Args,
+    mut output: impl Write,
+) -> anyhow::Result<()> {
+    match args.to {
         To::Annotated => {
-            output.write_all(value.to_hex().as_bytes())?;
+            output.write_all(value.to_hex_with(&hex_options(args)).as_bytes())?;
         }
         To::Hex => {
             output.write_all(hex::encode(value.to_bytes()).as_bytes())?;
@@ -77,12 +210,8 @@ fn output_item(value: cbor_diag::DataItem, to: To, mut output: impl Write) -> an
         To::Bytes => {
             output.write_all(&value.to_bytes())?;
         }
-        To::Diag => {
-            output.write_all(value.to_diag_pretty().as_bytes())?;
-            output.write_all(b"\n")?;
-        }
-        To::Compact => {
-            output.write_all(value.to_diag().as_bytes())?;
+        To::Diag | To::Compact => {
+            output.write_all(value.to_diag_with(&diag_options(args)).as_bytes())?;
             output.write_all(b"\n")?;
         }
         To::Debug => {
@@ -94,6 +223,96 @@ fn output_item(value: cbor_diag::DataItem, to: To, mut output: impl Write) -> an
     Ok(())
 }
 
+/// Outputs the items of a sequence as they are parsed, separated as the output format requires.
+struct SeqOutput<'a, W> {
+    args: &'a Args,
+    output: W,
+    started: bool,
+}
+
+impl<'a, W: Write> SeqOutput<'a, W> {
+    fn new(args: &'a Args, output: W) -> Self {
+        SeqOutput {
+            args,
+            output,
+            started: false,
+        }
+    }
+
+    fn item(&mut self, value: cbor_diag::DataItem) -> anyhow::Result<()> {
+        let started = std::mem::replace(&mut self.started, true);
+        match self.args.to {
+            // Separators go between items, so are written before each but the first
+            To::Diag | To::Compact => {
+                if started {
+                    let separator = if self.args.to == To::Diag { ",\n" } else { "," };
+                    self.output.write_all(separator.as_bytes())?;
+                }
+                let diag = value.to_diag_with(&diag_options(self.args));
+                self.output.write_all(diag.as_bytes())?;
+            }
+            To::Annotated | To::Debug => {
+                if started {
+                    self.output.write_all(b"\n")?;
+                }
+                output_item(value, self.args, &mut self.output)?;
+            }
+            To::Hex => {
+                output_item(value, self.args, &mut self.output)?;
+                self.output.write_all(b"\n")?;
+            }
+            To::Bytes => output_item(value, self.args, &mut self.output)?,
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> anyhow::Result<()> {
+        if self.started && matches!(self.args.to, To::Diag | To::Compact) {
+            self.output.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// Outputs each item of a binary sequence starting with the already read `data` and continuing
+/// with the rest of `input`.
+fn output_seq(
+    mut data: Vec<u8>,
+    mut input: impl Read,
+    args: &Args,
+    output: impl Write,
+) -> anyhow::Result<()> {
+    let mut output = SeqOutput::new(args, output);
+    loop {
+        while let Some((value, len)) = cbor_diag::parse_bytes_partial(&data)? {
+            output.item(value)?;
+            data.drain(..len);
+        }
+        if !input.read_to_vec(&mut data)? {
+            break;
+        }
+    }
+
+    if !data.is_empty() {
+        return Err(anyhow!("{} bytes remaining after last item", data.len()));
+    }
+
+    output.finish()
+}
+
+/// Outputs each item of an already parsed sequence.
+fn output_items(
+    items: Vec<cbor_diag::DataItem>,
+    args: &Args,
+    output: impl Write,
+) -> anyhow::Result<()> {
+    let mut output = SeqOutput::new(args, output);
+    for value in items {
+        output.item(value)?;
+    }
+    output.finish()
+}
+
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
 
@@ -104,20 +323,20 @@ fn main() -> anyhow::Result<()> {
     let mut output = output.lock();
 
     if args.seq {
-        let mut data = Default::default();
-
-        while input.read_to_vec(&mut data)? {
-            while let Some((value, len)) = cbor_diag::parse_bytes_partial(&data)? {
-                output_item(value, args.to, &mut output)?;
-                if args.to != To::Bytes && args.to != To::Compact {
-                    output.write_all(b"\n")?;
-                }
-                data.drain(..len);
+        match args.from {
+            // Most text is also a valid sequence of small integers, so sequences are only read as
+            // text when asked to
+            From::Auto | From::Bytes => output_seq(Vec::new(), input, &args, output)?,
+            From::Hex => {
+                let mut data = String::new();
+                input.read_to_string(&mut data)?;
+                output_items(cbor_diag::parse_hex_seq(data)?, &args, output)?;
+            }
+            From::Diag => {
+                let mut data = String::new();
+                input.read_to_string(&mut data)?;
+                output_items(cbor_diag::parse_diag_seq(data)?, &args, output)?;
             }
-        }
-
-        if !data.is_empty() {
-            return Err(anyhow!("{} bytes remaining after last item", data.len()));
         }
     } else {
         let data = {
@@ -127,16 +346,26 @@ fn main() -> anyhow::Result<()> {
         };
 
         let value = match args.from {
-            From::Auto => cbor_diag::parse_bytes(&data)
-                .ok()
-                .or_else(|| {
-                    String::from_utf8(data).ok().and_then(|data| {
-                        cbor_diag::parse_hex(&data)
-                            .ok()
-                            .or_else(|| cbor_diag::parse_diag(&data).ok())
+            From::Auto => match cbor_diag::Magic::detect(&data) {
+                Some((
+                    cbor_diag::Magic::Sequence | cbor_diag::Magic::ContentFormatSequence(_),
+                    _,
+                )) => return output_seq(data, io::empty(), &args, output),
+                Some((cbor_diag::Magic::LabeledNonCbor, _)) => {
+                    return Err(anyhow!("Input is labeled as not being CBOR"));
+                }
+                Some(_) => cbor_diag::parse_bytes(&data)?,
+                None => cbor_diag::parse_bytes(&data)
+                    .ok()
+                    .or_else(|| {
+                        String::from_utf8(data).ok().and_then(|data| {
+                            cbor_diag::parse_hex(&data)
+                                .ok()
+                                .or_else(|| cbor_diag::parse_diag(&data).ok())
+                        })
                     })
-                })
-                .ok_or_else(|| anyhow!("Failed all parsers"))?,
+                    .ok_or_else(|| anyhow!("Failed all parsers"))?,
+            },
             From::Hex => {
                 let data = String::from_utf8(data)?;
                 cbor_diag::parse_hex(data)?
@@ -148,7 +377,7 @@ fn main() -> anyhow::Result<()> {
             }
         };
 
-        output_item(value, args.to, &mut output)?;
+        output_item(value, &args, &mut output)?;
     }
 
     Ok(())
@@ -0,0 +1,332 @@
+//! The application-extension literals of the [CBOR EDN draft][EDN], a prefix
+//! followed by a single-quoted string, e.g. `dt'2023-01-01T00:00:00Z'`, used
+//! to convert the tags they represent to and from diagnostic notation. The
+//! `float'...'` extension instead represents the exact bits of a float, for
+//! NaNs which `NaN` cannot represent.
+//!
+//! Each extension is an entry in [`APP_LITERALS`], supporting another only
+//! needs a new entry with a function in each direction. Extensions which
+//! compute their value, such as `hash'...'`, are not supported.
+//!
+//! [EDN]: https://datatracker.ietf.org/doc/draft-ietf-cbor-edn-literals/
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use chrono::{DateTime, NaiveDateTime};
+use uuid::Uuid;
+
+use crate::{ByteString, DataItem, Float, FloatWidth, IntegerWidth, Tag, TextString};
+
+pub(crate) struct AppLiteral {
+    prefix: &'static str,
+    /// What the text of a literal must be, to describe why it is invalid.
+    expected: &'static str,
+    /// Parses the text of a literal to the data item it represents.
+    parse: fn(&str) -> Option<DataItem>,
+    /// Formats the text of a literal representing a data item, if the data
+    /// item is one this extension represents.
+    format: fn(&DataItem) -> Option<String>,
+}
+
+const APP_LITERALS: &[AppLiteral] = &[
+    AppLiteral {
+        prefix: "dt",
+        expected: "an RFC 3339 date-time in dt''",
+        parse: parse_epoch_datetime,
+        format: format_epoch_datetime,
+    },
+    AppLiteral {
+        prefix: "DT",
+        expected: "an RFC 3339 date-time in DT''",
+        parse: parse_datetime,
+        format: format_datetime,
+    },
+    AppLiteral {
+        prefix: "ip",
+        expected: "an IP address or prefix in ip''",
+        parse: parse_ip,
+        format: format_ip,
+    },
+    AppLiteral {
+        prefix: "uuid",
+        expected: "a UUID in uuid''",
+        parse: parse_uuid,
+        format: format_uuid,
+    },
+    // Recognised so that its use gets a clearer error than an unknown prefix
+    AppLiteral {
+        prefix: "hash",
+        expected: "a supported application literal, hash'' is not supported",
+        parse: |_| None,
+        format: |_| None,
+    },
+    AppLiteral {
+        prefix: "float",
+        expected: "4, 8 or 16 hex digits in float''",
+        parse: parse_float,
+        format: format_float,
+    },
+];
+
+/// The width the diagnostic notation parser gives integers without an
+/// encoding indicator, so that literals parse the same as the equivalent
+/// plain diagnostic notation.
+fn width(value: u64) -> IntegerWidth {
+    if value <= 23 {
+        IntegerWidth::Zero
+    } else {
+        IntegerWidth::Unknown
+    }
+}
+
+fn tagged(tag: Tag, value: DataItem) -> DataItem {
+    DataItem::Tag {
+        tag,
+        bitwidth: width(tag.0),
+        value: Box::new(value),
+    }
+}
+
+fn bytes(data: impl Into<Vec<u8>>) -> DataItem {
+    DataItem::ByteString(ByteString {
+        data: data.into(),
+        bitwidth: IntegerWidth::Unknown,
+    })
+}
+
+fn integer(value: i64) -> DataItem {
+    match u64::try_from(value) {
+        Ok(value) => DataItem::Integer {
+            value,
+            bitwidth: width(value),
+        },
+        Err(_) => {
+            let value = (-1 - value) as u64;
+            DataItem::Negative {
+                value,
+                bitwidth: width(value),
+            }
+        }
+    }
+}
+
+/// `dt'...'`, an RFC 3339 datetime as a [`Tag::EPOCH_DATETIME`], with
+/// fractional seconds as a float.
+fn parse_epoch_datetime(text: &str) -> Option<DataItem> {
+    let date = DateTime::parse_from_rfc3339(text).ok()?;
+    let (seconds, nanos) = (date.timestamp(), date.timestamp_subsec_nanos());
+    let value = if nanos == 0 {
+        integer(seconds)
+    } else {
+        DataItem::Float(Float::new(
+            seconds as f64 + f64::from(nanos) / 1e9,
+            FloatWidth::Unknown,
+        ))
+    };
+    Some(tagged(Tag::EPOCH_DATETIME, value))
+}
+
+fn format_epoch_datetime(value: &DataItem) -> Option<String> {
+    let (seconds, nanos) = match value {
+        DataItem::Tag {
+            tag: Tag::EPOCH_DATETIME,
+            value,
+            ..
+        } => match **value {
+            DataItem::Integer { value, .. } => (i64::try_from(value).ok()?, 0),
+            DataItem::Negative { value, .. } => (-1 - i64::try_from(value).ok()?, 0),
+            DataItem::Float(float) if float.as_f64().is_finite() => {
+                let value = float.as_f64();
+                let seconds = value.floor();
+                (seconds as i64, ((value - seconds) * 1e9).round() as u32)
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let date = NaiveDateTime::from_timestamp_opt(seconds, nanos)?;
+    Some(date.format("%FT%T%.fZ").to_string())
+}
+
+/// `DT'...'`, an RFC 3339 datetime as a [`Tag::DATETIME`].
+fn parse_datetime(text: &str) -> Option<DataItem> {
+    DateTime::parse_from_rfc3339(text).ok()?;
+    Some(tagged(
+        Tag::DATETIME,
+        DataItem::TextString(TextString {
+            data: text.to_owned(),
+            bitwidth: IntegerWidth::Unknown,
+        }),
+    ))
+}
+
+fn format_datetime(value: &DataItem) -> Option<String> {
+    match value {
+        DataItem::Tag {
+            tag: Tag::DATETIME,
+            value,
+            ..
+        } => match &**value {
+            DataItem::TextString(TextString { data, .. }) => Some(data.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `ip'...'`, an address as a [`Tag::IPV4`] or [`Tag::IPV6`], with a `/length`
+/// suffix as a prefix if all bits after the length are zero, otherwise as an
+/// address and prefix length, see [RFC 9164][].
+///
+/// [RFC 9164]: https://www.rfc-editor.org/rfc/rfc9164
+fn parse_ip(text: &str) -> Option<DataItem> {
+    let (address, length) = match text.split_once('/') {
+        Some((address, length)) => (address, Some(length.parse::<u8>().ok()?)),
+        None => (text, None),
+    };
+    let (tag, octets) = match address.parse().ok()? {
+        IpAddr::V4(address) => (Tag::IPV4, address.octets().to_vec()),
+        IpAddr::V6(address) => (Tag::IPV6, address.octets().to_vec()),
+    };
+
+    let Some(length) = length else {
+        return Some(tagged(tag, bytes(octets)));
+    };
+    if usize::from(length) > octets.len() * 8 {
+        return None;
+    }
+
+    let bits = octets
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |bit| byte >> bit & 1));
+    let value = if bits.skip(length.into()).all(|bit| bit == 0) {
+        let len = octets
+            .iter()
+            .rposition(|&byte| byte != 0)
+            .map_or(0, |i| i + 1);
+        vec![integer(length.into()), bytes(&octets[..len])]
+    } else {
+        vec![bytes(octets), integer(length.into())]
+    };
+    Some(tagged(
+        tag,
+        DataItem::Array {
+            data: value,
+            bitwidth: Some(IntegerWidth::Unknown),
+        },
+    ))
+}
+
+fn format_ip(value: &DataItem) -> Option<String> {
+    let (tag, value) = match value {
+        DataItem::Tag { tag, value, .. } if matches!(*tag, Tag::IPV4 | Tag::IPV6) => (*tag, value),
+        _ => return None,
+    };
+
+    let address = |data: &[u8]| -> Option<IpAddr> {
+        Some(if tag == Tag::IPV4 {
+            let mut octets = [0; 4];
+            octets.get_mut(..data.len())?.copy_from_slice(data);
+            Ipv4Addr::from(octets).into()
+        } else {
+            let mut octets = [0; 16];
+            octets.get_mut(..data.len())?.copy_from_slice(data);
+            Ipv6Addr::from(octets).into()
+        })
+    };
+
+    match &**value {
+        DataItem::ByteString(ByteString { data, .. }) => Some(address(data)?.to_string()),
+        DataItem::Array { data, .. } => match data.as_slice() {
+            [DataItem::Integer { value: length, .. }, DataItem::ByteString(ByteString { data, .. })]
+            | [DataItem::ByteString(ByteString { data, .. }), DataItem::Integer { value: length, .. }] => {
+                Some(format!("{}/{length}", address(data)?))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `uuid'...'`, a [`Tag::UUID`].
+fn parse_uuid(text: &str) -> Option<DataItem> {
+    let uuid = Uuid::try_parse(text).ok()?;
+    Some(tagged(Tag::UUID, bytes(uuid.as_bytes().as_slice())))
+}
+
+fn format_uuid(value: &DataItem) -> Option<String> {
+    match value {
+        DataItem::Tag {
+            tag: Tag::UUID,
+            value,
+            ..
+        } => match &**value {
+            DataItem::ByteString(ByteString { data, .. }) => {
+                Some(Uuid::from_slice(data).ok()?.hyphenated().to_string())
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `float'...'`, the bits of a float in hex, with the width given by the
+/// number of digits, e.g. `float'7e01'` for a half precision NaN with a
+/// payload.
+fn parse_float(text: &str) -> Option<DataItem> {
+    if !text.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let bitwidth = match text.len() {
+        4 => FloatWidth::Sixteen,
+        8 => FloatWidth::ThirtyTwo,
+        16 => FloatWidth::SixtyFour,
+        _ => return None,
+    };
+    Some(DataItem::Float(Float {
+        bits: u64::from_str_radix(text, 16).ok()?,
+        bitwidth,
+    }))
+}
+
+/// Only NaNs other than the quiet NaN `NaN` parses to are represented.
+fn format_float(value: &DataItem) -> Option<String> {
+    let DataItem::Float(float) = *value else {
+        return None;
+    };
+    if !float.as_f64().is_nan() || float == Float::new(f64::NAN, float.bitwidth) {
+        return None;
+    }
+    let bits = float.encoded_bits();
+    Some(match float.bitwidth {
+        FloatWidth::Sixteen => format!("{bits:04x}"),
+        FloatWidth::ThirtyTwo => format!("{bits:08x}"),
+        FloatWidth::Unknown | FloatWidth::SixtyFour => format!("{bits:016x}"),
+    })
+}
+
+impl AppLiteral {
+    pub(crate) fn from_prefix(prefix: &str) -> Option<&'static AppLiteral> {
+        APP_LITERALS.iter().find(|app| app.prefix == prefix)
+    }
+
+    /// What the text of a literal with this prefix must be.
+    pub(crate) fn expected(&self) -> &'static str {
+        self.expected
+    }
+
+    /// Parses the text of a literal with this prefix.
+    pub(crate) fn parse(&self, text: &str) -> Option<DataItem> {
+        (self.parse)(text)
+    }
+
+    /// The literal representing `value`, if there is one which parses back to
+    /// the same encoding.
+    pub(crate) fn format(value: &DataItem) -> Option<String> {
+        APP_LITERALS.iter().find_map(|app| {
+            let text = (app.format)(value)?;
+            let parsed = app.parse(&text)?;
+            (parsed.to_bytes() == value.to_bytes()).then(|| format!("{}'{text}'", app.prefix))
+        })
+    }
+}
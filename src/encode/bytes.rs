@@ -1,6 +1,4 @@
-use half::f16;
-
-use crate::{ByteString, DataItem, FloatWidth, IntegerWidth, Simple, Tag, TextString};
+use crate::{ByteString, DataItem, Float, FloatWidth, IntegerWidth, Simple, Tag, TextString};
 
 fn item_to_bytes(bytes: &mut Vec<u8>, value: &DataItem) {
     match *value {
@@ -21,16 +19,16 @@ fn item_to_bytes(bytes: &mut Vec<u8>, value: &DataItem) {
             bitwidth,
             ref value,
         } => tagged_to_bytes(bytes, tag, bitwidth, value),
-        DataItem::Float { value, bitwidth } => float_to_bytes(bytes, value, bitwidth),
+        DataItem::Float(float) => float_to_bytes(bytes, float),
         DataItem::Simple(simple) => simple_to_bytes(bytes, simple),
     }
 }
 
 fn integer_to_bytes(bytes: &mut Vec<u8>, value: u64, bitwidth: IntegerWidth, major: u8) {
-    const U8_MAX: u64 = u8::max_value() as u64;
-    const U16_MAX: u64 = u16::max_value() as u64;
-    const U32_MAX: u64 = u32::max_value() as u64;
-    const U64_MAX: u64 = u64::max_value();
+    const U8_MAX: u64 = u8::MAX as u64;
+    const U16_MAX: u64 = u16::MAX as u64;
+    const U32_MAX: u64 = u32::MAX as u64;
+    const U64_MAX: u64 = u64::MAX;
 
     match bitwidth {
         IntegerWidth::Unknown => {
@@ -141,24 +139,20 @@ fn tagged_to_bytes(bytes: &mut Vec<u8>, tag: Tag, bitwidth: IntegerWidth, value:
     item_to_bytes(bytes, value);
 }
 
-fn float_to_bytes(bytes: &mut Vec<u8>, value: f64, mut bitwidth: FloatWidth) {
-    if bitwidth == FloatWidth::Unknown {
-        bitwidth = FloatWidth::SixtyFour;
-    }
-
-    match bitwidth {
-        FloatWidth::Unknown => unreachable!(),
+fn float_to_bytes(bytes: &mut Vec<u8>, float: Float) {
+    let bits = float.encoded_bits();
+    match float.bitwidth {
         FloatWidth::Sixteen => {
             bytes.push(0xf9);
-            bytes.extend_from_slice(&f16::from_f64(value).to_bits().to_be_bytes());
+            bytes.extend_from_slice(&(bits as u16).to_be_bytes());
         }
         FloatWidth::ThirtyTwo => {
             bytes.push(0xfa);
-            bytes.extend_from_slice(&(value as f32).to_bits().to_be_bytes());
+            bytes.extend_from_slice(&(bits as u32).to_be_bytes());
         }
-        FloatWidth::SixtyFour => {
+        FloatWidth::SixtyFour | FloatWidth::Unknown => {
             bytes.push(0xfb);
-            bytes.extend_from_slice(&value.to_bits().to_be_bytes());
+            bytes.extend_from_slice(&bits.to_be_bytes());
         }
     }
 }
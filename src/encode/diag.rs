@@ -1,9 +1,19 @@
 use half::f16;
+use num_bigint::BigInt;
 
 use std::fmt::Write;
 
-use super::Encoding;
-use crate::{ByteString, DataItem, FloatWidth, IntegerWidth, Simple, Tag, TextString};
+use super::{
+    hex::extract_integer,
+    json::{parse_json, Json},
+    language::LanguageTaggedString,
+    multi_dimensional::MultiDimensionalArray,
+    EmbeddedCbor, Encoding,
+};
+use crate::{
+    app_literal::AppLiteral, typed_array::TypedArray, ByteString, DataItem, Float, FloatWidth,
+    IntegerWidth, Simple, Tag, TextString,
+};
 
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub(crate) enum Layout {
@@ -11,11 +21,87 @@ pub(crate) enum Layout {
     Compact,
 }
 
+/// How the contents of typed arrays ([`Tag::TYPED_ARRAY_U8`] to
+/// [`Tag::TYPED_ARRAY_F128_LITTLE_ENDIAN`]) are output.
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub enum TypedArrayStyle {
+    /// As a plain byte string, e.g. `85(h'0000c03f')`.
+    #[default]
+    Bytes,
+
+    /// As a plain byte string followed by a comment containing the elements,
+    /// e.g. `85(h'0000c03f') / [1.5] /`.
+    Commented,
+
+    /// As a byte string literal of the elements, e.g. `85(f32le'1.5')`, which
+    /// parses back to the same bytes. Falls back to a plain byte string when
+    /// the elements cannot be represented exactly.
+    Literal,
+}
+
+/// Options controlling the output of [`DataItem::to_diag_with`].
+#[derive(Debug, Clone, Default)]
+pub struct DiagOptions {
+    /// Whether to output with nice whitespace, as [`DataItem::to_diag_pretty`]
+    /// does.
+    pub pretty: bool,
+
+    /// Whether to follow each multi-dimensional array ([`Tag::MULTI_DIMENSIONAL_ARRAY`] and
+    /// [`Tag::MULTI_DIMENSIONAL_ARRAY_COLUMN_MAJOR`]) with a comment containing its elements as
+    /// nested row-major arrays.
+    pub expand_arrays: bool,
+
+    /// How the contents of typed arrays are output.
+    pub typed_arrays: TypedArrayStyle,
+
+    /// Whether to output untagged byte strings which contain a well-formed
+    /// CBOR data item or sequence as `<<...>>`.
+    pub embedded_cbor: Option<EmbeddedCbor>,
+
+    /// Whether to output bignums ([`Tag::POSITIVE_BIGNUM`] and
+    /// [`Tag::NEGATIVE_BIGNUM`]) as integer literals, e.g.
+    /// `18446744073709551616` rather than `2(h'010000000000000000')`. Only
+    /// bignums which parse back to the same encoding are output this way.
+    pub bignum_literals: bool,
+
+    /// Whether to escape all non-ASCII characters in text strings as `\uXXXX`
+    /// (using surrogate pairs outside the basic multilingual plane), rather
+    /// than only control characters.
+    pub escape_non_ascii: bool,
+
+    /// Whether to output datetimes ([`Tag::DATETIME`] and
+    /// [`Tag::EPOCH_DATETIME`]), IP addresses ([`Tag::IPV4`] and [`Tag::IPV6`])
+    /// and UUIDs ([`Tag::UUID`]) as application-extension literals, e.g.
+    /// `dt'2023-01-01T00:00:00Z'` rather than `1(1672531200)`. Only values
+    /// which parse back to the same encoding are output this way.
+    pub app_literals: bool,
+
+    /// The maximum number of items of arrays and maps, and bytes of strings,
+    /// to output, with the rest marked as elided with `...`. Elisions parse
+    /// back to [`Tag::ELISION`], so truncated output does not parse back to
+    /// the same data.
+    pub truncate: Option<usize>,
+
+    /// Whether to output an encoding indicator for every known argument
+    /// width, including `_i` for immediate values and the lengths of strings,
+    /// arrays and maps, e.g. `[_0 1_i, "a"_i]`. Data items parsed from CBOR
+    /// then parse back to exactly the same encoding.
+    pub explicit_encoding: bool,
+}
+
 pub(crate) struct Context<'a> {
     output: &'a mut String,
     layout: Layout,
     encoding: Encoding,
     indent: usize,
+    expand_arrays: bool,
+    typed_arrays: TypedArrayStyle,
+    embedded_cbor: Option<EmbeddedCbor>,
+    bignum_literals: bool,
+    escape_non_ascii: bool,
+    app_literals: bool,
+    truncate: Option<usize>,
+    explicit_encoding: bool,
 }
 
 trait LengthEstimate {
@@ -33,7 +119,7 @@ impl LengthEstimate for DataItem {
         match self {
             DataItem::Integer { value, .. } => value.to_string().len() + 2,
             DataItem::Negative { value, .. } => value.to_string().len() + 3,
-            DataItem::Float { value, .. } => value.to_string().len() + 3,
+            DataItem::Float(value) => value.as_f64().to_string().len() + 3,
             DataItem::Simple(value) => value.estimate(max),
             DataItem::ByteString(value) => value.estimate(max),
             DataItem::TextString(value) => value.estimate(max),
@@ -128,6 +214,91 @@ impl LengthEstimate for Simple {
     }
 }
 
+/// The elements of a typed array, if they can be represented exactly as
+/// numbers.
+fn typed_array_elements(tag: Tag, value: &DataItem) -> Option<(TypedArray, Vec<String>)> {
+    let array = TypedArray::from_tag(tag)?;
+    let data = match value {
+        DataItem::ByteString(ByteString { data, .. }) => data,
+        _ => return None,
+    };
+    let elements = array.format(data).ok()?;
+    // Some values, such as NaNs with a payload, are not preserved by formatting
+    if array.encode(elements.iter().map(String::as_str)).ok()? != *data {
+        return None;
+    }
+    Some((array, elements))
+}
+
+/// The JSON text embedded in a [`Tag::EMBEDDED_JSON`] item, or the reason it
+/// is invalid, or in an [`Tag::ENCODED_CBOR`] item which does not contain
+/// CBOR.
+fn embedded_json(tag: Tag, value: &DataItem) -> Option<Result<Json, String>> {
+    let data = match value {
+        DataItem::ByteString(ByteString { data, .. }) => data,
+        _ => return None,
+    };
+    match tag {
+        Tag::EMBEDDED_JSON => {
+            Some(parse_json(data).map_err(|err| format!("invalid embedded json, {err}")))
+        }
+        Tag::ENCODED_CBOR if !data.is_empty() && crate::parse_bytes(data).is_err() => {
+            parse_json(data).ok().map(Ok)
+        }
+        _ => None,
+    }
+}
+
+fn elided() -> DataItem {
+    DataItem::Tag {
+        tag: Tag::ELISION,
+        bitwidth: IntegerWidth::Unknown,
+        value: Box::new(DataItem::Simple(Simple::NULL)),
+    }
+}
+
+fn is_elided(value: &DataItem) -> bool {
+    matches!(elision_parts(value).as_deref(), Some([None]))
+}
+
+/// The parts of a [`Tag::ELISION`] that can be output as `...` elisions, with
+/// `None` for each elided part. The strings of an elided string must be of the
+/// same type and separated by elisions, otherwise they would be parsed back
+/// as a different item.
+fn elision_parts(value: &DataItem) -> Option<Vec<Option<&DataItem>>> {
+    let DataItem::Tag {
+        tag: Tag::ELISION,
+        value,
+        ..
+    } = value
+    else {
+        return None;
+    };
+    let data = match &**value {
+        DataItem::Simple(Simple::NULL) => return Some(vec![None]),
+        DataItem::Array { data, .. } => data,
+        _ => return None,
+    };
+
+    let parts = data
+        .iter()
+        .map(|item| match item {
+            DataItem::Simple(Simple::NULL) => Some(None),
+            DataItem::ByteString(_) | DataItem::TextString(_) => Some(Some(item)),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let mut strings = parts.iter().flatten();
+    let first = strings.next()?;
+    let same_type =
+        strings.all(|string| std::mem::discriminant(*string) == std::mem::discriminant(*first));
+    let separated = parts
+        .windows(2)
+        .all(|pair| pair[0].is_none() || pair[1].is_none());
+    let elided = parts.iter().any(Option::is_none);
+    (same_type && separated && elided).then_some(parts)
+}
+
 impl<'a> Context<'a> {
     pub(crate) fn new(output: &'a mut String, layout: Layout) -> Self {
         Self {
@@ -135,6 +306,14 @@ impl<'a> Context<'a> {
             layout,
             encoding: Encoding::Base16,
             indent: 0,
+            expand_arrays: false,
+            typed_arrays: TypedArrayStyle::Bytes,
+            embedded_cbor: None,
+            bignum_literals: false,
+            escape_non_ascii: false,
+            app_literals: false,
+            truncate: None,
+            explicit_encoding: false,
         }
     }
 
@@ -144,6 +323,14 @@ impl<'a> Context<'a> {
             layout: self.layout,
             encoding,
             indent: self.indent,
+            expand_arrays: self.expand_arrays,
+            typed_arrays: self.typed_arrays,
+            embedded_cbor: self.embedded_cbor,
+            bignum_literals: self.bignum_literals,
+            escape_non_ascii: self.escape_non_ascii,
+            app_literals: self.app_literals,
+            truncate: self.truncate,
+            explicit_encoding: self.explicit_encoding,
         }
     }
 
@@ -161,35 +348,49 @@ impl<'a> Context<'a> {
         self.output.push('\n');
     }
 
+    /// Outputs a comment following the current item, with every `/` in it
+    /// escaped as `\u002f` so that it cannot end the comment early.
+    fn comment(&mut self, write: impl FnOnce(&mut Self)) {
+        self.output
+            .push_str(if self.pretty() { " / " } else { "/" });
+        let start = self.output.len();
+        write(self);
+        let escaped = self.output[start..].replace('/', "\\u002f");
+        self.output.replace_range(start.., &escaped);
+        self.output.push_str(if self.pretty() { " /" } else { "/" });
+    }
+
+    /// Outputs the encoding indicator for an argument width, where the
+    /// immediate `_i` is only output if all encodings are explicit. Returns
+    /// whether an indicator was output.
+    fn encoding_indicator(&mut self, bitwidth: IntegerWidth) -> bool {
+        let indicator = match bitwidth {
+            IntegerWidth::Unknown => return false,
+            IntegerWidth::Zero if !self.explicit_encoding => return false,
+            IntegerWidth::Zero => "_i",
+            IntegerWidth::Eight => "_0",
+            IntegerWidth::Sixteen => "_1",
+            IntegerWidth::ThirtyTwo => "_2",
+            IntegerWidth::SixtyFour => "_3",
+        };
+        self.output.push_str(indicator);
+        true
+    }
+
+    /// Outputs the encoding indicator for the width of a length, only output
+    /// if all encodings are explicit.
+    fn length_indicator(&mut self, bitwidth: IntegerWidth) -> bool {
+        self.explicit_encoding && self.encoding_indicator(bitwidth)
+    }
+
     fn integer_to_diag(&mut self, value: u64, bitwidth: IntegerWidth) {
-        if bitwidth == IntegerWidth::Unknown || bitwidth == IntegerWidth::Zero {
-            self.output.push_str(&value.to_string());
-        } else {
-            let encoding = match bitwidth {
-                IntegerWidth::Eight => 0,
-                IntegerWidth::Sixteen => 1,
-                IntegerWidth::ThirtyTwo => 2,
-                IntegerWidth::SixtyFour => 3,
-                _ => unreachable!(),
-            };
-            write!(self.output, "{value}_{encoding}").unwrap();
-        }
+        write!(self.output, "{value}").unwrap();
+        self.encoding_indicator(bitwidth);
     }
 
     fn negative_to_diag(&mut self, value: u64, bitwidth: IntegerWidth) {
-        let value = -1i128 - i128::from(value);
-        if bitwidth == IntegerWidth::Unknown || bitwidth == IntegerWidth::Zero {
-            self.output.push_str(&value.to_string());
-        } else {
-            let encoding = match bitwidth {
-                IntegerWidth::Eight => 0,
-                IntegerWidth::Sixteen => 1,
-                IntegerWidth::ThirtyTwo => 2,
-                IntegerWidth::SixtyFour => 3,
-                _ => unreachable!(),
-            };
-            write!(self.output, "{value}_{encoding}").unwrap();
-        }
+        write!(self.output, "{}", -1i128 - i128::from(value)).unwrap();
+        self.encoding_indicator(bitwidth);
     }
 
     fn definite_bytestring_to_diag(&mut self, bytestring: &ByteString) {
@@ -210,20 +411,65 @@ impl<'a> Context<'a> {
                 self.output.push('\'');
             }
         }
+        self.length_indicator(bytestring.bitwidth);
+    }
+
+    /// The value of a bignum to output as an integer literal, if enabled and
+    /// it parses back to the same encoding (so is not small enough to be a
+    /// plain integer and has no leading zeros or non-preferred widths).
+    fn bignum_literal(&self, value: &DataItem) -> Option<BigInt> {
+        if !self.bignum_literals
+            || !matches!(
+                value,
+                DataItem::Tag {
+                    tag: Tag::POSITIVE_BIGNUM | Tag::NEGATIVE_BIGNUM,
+                    ..
+                }
+            )
+        {
+            return None;
+        }
+        extract_integer(value).filter(|integer| {
+            DataItem::from_integer(integer.clone()).to_bytes() == value.to_bytes()
+        })
+    }
+
+    /// Outputs the items of an encoded CBOR sequence as `<<...>>`.
+    fn sequence_to_diag(&mut self, items: &[DataItem]) {
+        self.container_to_diag(
+            "<<",
+            items,
+            ">>",
+            Some(IntegerWidth::Unknown),
+            is_trivial(&DataItem::Array {
+                data: items.to_vec(),
+                bitwidth: None,
+            }),
+            Self::item_to_diag,
+        );
     }
 
     fn definite_textstring_to_diag(&mut self, textstring: &TextString) {
         self.output.push('"');
         for c in textstring.data.chars() {
-            if c == '\"' || c == '\\' {
-                for c in c.escape_default() {
-                    self.output.push(c);
+            match c {
+                '"' => self.output.push_str("\\\""),
+                '\\' => self.output.push_str("\\\\"),
+                '\u{8}' => self.output.push_str("\\b"),
+                '\u{c}' => self.output.push_str("\\f"),
+                '\n' => self.output.push_str("\\n"),
+                '\r' => self.output.push_str("\\r"),
+                '\t' => self.output.push_str("\\t"),
+                c if c.is_control() || (self.escape_non_ascii && !c.is_ascii()) => {
+                    for unit in c.encode_utf16(&mut [0; 2]) {
+                        write!(self.output, "\\u{unit:04x}").unwrap();
+                    }
                 }
-            } else {
-                self.output.push(c);
+                c => self.output.push(c),
             }
         }
         self.output.push('"');
+        self.length_indicator(textstring.bitwidth);
     }
 
     fn container_to_diag<T>(
@@ -231,15 +477,24 @@ impl<'a> Context<'a> {
         begin: impl Into<String>,
         items: impl IntoIterator<Item = T>,
         end: impl Into<String>,
-        definite: bool,
+        bitwidth: Option<IntegerWidth>,
         trivial: bool,
         item_to_diag: fn(&mut Self, T),
     ) {
         self.output.push_str(&begin.into());
-        if !definite {
-            self.output.push('_');
-            if trivial && self.pretty() {
-                self.output.push(' ');
+        match bitwidth {
+            None => {
+                self.output.push('_');
+                if trivial && self.pretty() {
+                    self.output.push(' ');
+                }
+            }
+            // The indicator must be followed by whitespace, unless it is
+            // followed by the first item on a new line
+            Some(bitwidth) => {
+                if self.length_indicator(bitwidth) && (trivial || !self.pretty()) {
+                    self.output.push(' ');
+                }
             }
         }
         if !trivial {
@@ -282,40 +537,69 @@ impl<'a> Context<'a> {
         trivial: bool,
         definite_string_to_diag: fn(&mut Self, &T),
     ) {
-        self.container_to_diag('(', strings, ')', false, trivial, definite_string_to_diag);
+        self.container_to_diag('(', strings, ')', None, trivial, definite_string_to_diag);
     }
 
-    fn array_to_diag(&mut self, array: &[DataItem], definite: bool, trivial: bool) {
-        self.container_to_diag('[', array, ']', definite, trivial, Self::item_to_diag);
+    fn array_to_diag(&mut self, array: &[DataItem], bitwidth: Option<IntegerWidth>, trivial: bool) {
+        let elided = elided();
+        let (array, elided) = match self.truncate {
+            Some(max) if array.len() > max => (&array[..max], Some(&elided)),
+            _ => (array, None),
+        };
+        self.container_to_diag(
+            '[',
+            array.iter().chain(elided),
+            ']',
+            bitwidth,
+            trivial,
+            Self::item_to_diag,
+        );
     }
 
-    fn map_to_diag(&mut self, values: &[(DataItem, DataItem)], definite: bool, trivial: bool) {
-        self.container_to_diag('{', values, '}', definite, trivial, |this, (key, value)| {
-            this.item_to_diag(key);
-            this.output.push(':');
-            if this.pretty() {
-                this.output.push(' ');
-            }
-            this.item_to_diag(value);
-        });
+    fn map_to_diag(
+        &mut self,
+        values: &[(DataItem, DataItem)],
+        bitwidth: Option<IntegerWidth>,
+        trivial: bool,
+    ) {
+        let (values, elided) = match self.truncate {
+            Some(max) if values.len() > max => (&values[..max], Some(None)),
+            _ => (values, None),
+        };
+        self.container_to_diag(
+            '{',
+            values.iter().map(Some).chain(elided),
+            '}',
+            bitwidth,
+            trivial,
+            |this, entry| match entry {
+                Some((key, value)) if !(is_elided(key) && is_elided(value)) => {
+                    this.item_to_diag(key);
+                    this.output.push(':');
+                    if this.pretty() {
+                        this.output.push(' ');
+                    }
+                    this.item_to_diag(value);
+                }
+                _ => this.output.push_str("..."),
+            },
+        );
     }
 
     pub fn tagged_to_diag(&mut self, tag: Tag, bitwidth: IntegerWidth, value: &DataItem) {
-        let tag_value = tag.0;
-        if bitwidth == IntegerWidth::Unknown || bitwidth == IntegerWidth::Zero {
-            write!(self.output, "{tag_value}").unwrap();
-        } else {
-            let encoding = match bitwidth {
-                IntegerWidth::Eight => 0,
-                IntegerWidth::Sixteen => 1,
-                IntegerWidth::ThirtyTwo => 2,
-                IntegerWidth::SixtyFour => 3,
-                _ => unreachable!(),
-            };
-            write!(self.output, "{tag_value}_{encoding}").unwrap();
-        }
+        write!(self.output, "{}", tag.0).unwrap();
+        self.encoding_indicator(bitwidth);
         self.output.push('(');
 
+        // A tagged byte string already has a meaning, so is not checked for
+        // embedded CBOR, unless it is explicitly encoded CBOR
+        let embedded_cbor = self.embedded_cbor;
+        if let DataItem::ByteString(_) = value {
+            if !matches!(tag, Tag::ENCODED_CBOR | Tag::ENCODED_CBOR_SEQ) {
+                self.embedded_cbor = None;
+            }
+        }
+
         match tag {
             Tag::ENCODED_BASE64URL => {
                 self.with_encoding(Encoding::Base64Url).item_to_diag(value);
@@ -350,17 +634,7 @@ impl<'a> Context<'a> {
                         data = rest;
                         items.push(item);
                     }
-                    self.container_to_diag(
-                        "<<",
-                        &items,
-                        ">>",
-                        true,
-                        is_trivial(&DataItem::Array {
-                            data: items.clone(),
-                            bitwidth: None,
-                        }),
-                        Self::item_to_diag,
-                    );
+                    self.sequence_to_diag(&items);
                     if !data.is_empty() {
                         if self.pretty() {
                             self.output.push(' ');
@@ -374,16 +648,74 @@ impl<'a> Context<'a> {
                     self.item_to_diag(value);
                 }
             }
-            _ => {
-                self.item_to_diag(value);
-            }
+            _ => match typed_array_elements(tag, value) {
+                Some((array, elements)) if self.typed_arrays == TypedArrayStyle::Literal => {
+                    let separator = if self.pretty() { ", " } else { "," };
+                    write!(
+                        self.output,
+                        "{}'{}'",
+                        array.prefix(),
+                        elements.join(separator)
+                    )
+                    .unwrap();
+                }
+                _ => {
+                    self.item_to_diag(value);
+                }
+            },
         }
 
         self.output.push(')');
+        self.embedded_cbor = embedded_cbor;
+
+        if tag == Tag::LANGUAGE_TAGGED_STRING {
+            if let Ok(string) = LanguageTaggedString::extract(value) {
+                self.comment(|this| this.output.push_str(&string.describe()));
+            }
+        }
+
+        if let Some(json) = embedded_json(tag, value) {
+            let pretty = self.pretty();
+            let text = match json {
+                Ok(json) => json
+                    .to_text(pretty)
+                    .replace('\n', &format!("\n{}", " ".repeat(self.indent))),
+                Err(err) => err,
+            };
+            self.comment(|this| this.output.push_str(&text));
+        }
+
+        if self.typed_arrays == TypedArrayStyle::Commented {
+            if let Some((_, elements)) = typed_array_elements(tag, value) {
+                let separator = if self.pretty() { ", " } else { "," };
+                self.comment(|this| {
+                    write!(this.output, "[{}]", elements.join(separator)).unwrap();
+                });
+            }
+        }
+
+        if self.expand_arrays {
+            let column_major = match tag {
+                Tag::MULTI_DIMENSIONAL_ARRAY => Some(false),
+                Tag::MULTI_DIMENSIONAL_ARRAY_COLUMN_MAJOR => Some(true),
+                _ => None,
+            };
+            if let Some(array) = column_major
+                .and_then(|column_major| MultiDimensionalArray::extract(value, column_major).ok())
+            {
+                self.comment(|this| this.item_to_diag(&array.nested()));
+            }
+        }
     }
 
-    fn float_to_diag(&mut self, value: f64, bitwidth: FloatWidth) {
+    fn float_to_diag(&mut self, float: Float) {
+        let (value, bitwidth) = (float.as_f64(), float.bitwidth);
         if value.is_nan() {
+            // NaNs with a sign or payload are output as their bits
+            if let Some(literal) = AppLiteral::format(&DataItem::Float(float)) {
+                self.output.push_str(&literal);
+                return;
+            }
             self.output.push_str("NaN");
         } else if value.is_infinite() {
             if value.is_sign_negative() {
@@ -393,8 +725,8 @@ impl<'a> Context<'a> {
         } else {
             let value = match bitwidth {
                 FloatWidth::Unknown | FloatWidth::SixtyFour => value.to_string(),
-                FloatWidth::Sixteen => f16::from_f64(value).to_string(),
-                FloatWidth::ThirtyTwo => (value as f32).to_string(),
+                FloatWidth::Sixteen => f16::from_bits(float.encoded_bits() as u16).to_string(),
+                FloatWidth::ThirtyTwo => f32::from_bits(float.encoded_bits() as u32).to_string(),
             };
             self.output.push_str(&value);
             if !value.contains('.') && !value.contains('e') {
@@ -419,7 +751,70 @@ impl<'a> Context<'a> {
         }
     }
 
+    /// Outputs the parts of an elision separated by spaces, with `None` for
+    /// each elided part.
+    fn elision_to_diag<'b>(&mut self, parts: impl IntoIterator<Item = Option<&'b DataItem>>) {
+        for (i, part) in parts.into_iter().enumerate() {
+            if i > 0 {
+                self.output.push(' ');
+            }
+            match part {
+                Some(part) => self.item_to_diag(part),
+                None => self.output.push_str("..."),
+            }
+        }
+    }
+
+    /// Outputs the start of a string longer than the truncation length
+    /// followed by an elision, returns whether the string was truncated.
+    fn truncated_string_to_diag(&mut self, value: &DataItem) -> bool {
+        let Some(max) = self.truncate else {
+            return false;
+        };
+        let truncated = match value {
+            DataItem::ByteString(ByteString { data, .. }) if data.len() > max => {
+                DataItem::ByteString(ByteString {
+                    data: data[..max].to_vec(),
+                    bitwidth: IntegerWidth::Unknown,
+                })
+            }
+            DataItem::TextString(TextString { data, .. }) if data.len() > max => {
+                let end = (0..=max).rev().find(|&i| data.is_char_boundary(i)).unwrap();
+                DataItem::TextString(TextString {
+                    data: data[..end].to_owned(),
+                    bitwidth: IntegerWidth::Unknown,
+                })
+            }
+            _ => return false,
+        };
+        self.elision_to_diag([Some(&truncated), None]);
+        true
+    }
+
     fn item_to_diag(&mut self, value: &DataItem) {
+        if let Some(parts) = elision_parts(value) {
+            self.elision_to_diag(parts);
+            return;
+        }
+
+        if self.truncated_string_to_diag(value) {
+            return;
+        }
+
+        if let Some(integer) = self.bignum_literal(value) {
+            write!(self.output, "{integer}").unwrap();
+            return;
+        }
+
+        if let Some(literal) = self
+            .app_literals
+            .then(|| AppLiteral::format(value))
+            .flatten()
+        {
+            self.output.push_str(&literal);
+            return;
+        }
+
         match *value {
             DataItem::Integer { value, bitwidth } => {
                 self.integer_to_diag(value, bitwidth);
@@ -428,7 +823,19 @@ impl<'a> Context<'a> {
                 self.negative_to_diag(value, bitwidth);
             }
             DataItem::ByteString(ref bytestring) => {
-                self.definite_bytestring_to_diag(bytestring);
+                match self
+                    .embedded_cbor
+                    .and_then(|options| options.decode(&bytestring.data))
+                {
+                    Some(items) => {
+                        let embedded_cbor = self.embedded_cbor;
+                        self.embedded_cbor = embedded_cbor.map(EmbeddedCbor::nested);
+                        self.sequence_to_diag(&items);
+                        self.embedded_cbor = embedded_cbor;
+                        self.length_indicator(bytestring.bitwidth);
+                    }
+                    None => self.definite_bytestring_to_diag(bytestring),
+                }
             }
             DataItem::IndefiniteByteString(ref bytestrings) => {
                 self.indefinite_string_to_diag(
@@ -440,6 +847,12 @@ impl<'a> Context<'a> {
             DataItem::TextString(ref textstring) => {
                 self.definite_textstring_to_diag(textstring);
             }
+            // `(_)` parses back as a byte string
+            DataItem::IndefiniteTextString(ref textstrings)
+                if textstrings.is_empty() && self.explicit_encoding =>
+            {
+                self.output.push_str("\"\"_");
+            }
             DataItem::IndefiniteTextString(ref textstrings) => {
                 self.indefinite_string_to_diag(
                     textstrings,
@@ -447,17 +860,11 @@ impl<'a> Context<'a> {
                     Self::definite_textstring_to_diag,
                 );
             }
-            DataItem::Array {
-                ref data,
-                ref bitwidth,
-            } => {
-                self.array_to_diag(data, bitwidth.is_some(), is_trivial(value));
+            DataItem::Array { ref data, bitwidth } => {
+                self.array_to_diag(data, bitwidth, is_trivial(value));
             }
-            DataItem::Map {
-                ref data,
-                ref bitwidth,
-            } => {
-                self.map_to_diag(data, bitwidth.is_some(), is_trivial(value));
+            DataItem::Map { ref data, bitwidth } => {
+                self.map_to_diag(data, bitwidth, is_trivial(value));
             }
             DataItem::Tag {
                 tag,
@@ -466,8 +873,8 @@ impl<'a> Context<'a> {
             } => {
                 self.tagged_to_diag(tag, bitwidth, value);
             }
-            DataItem::Float { value, bitwidth } => {
-                self.float_to_diag(value, bitwidth);
+            DataItem::Float(float) => {
+                self.float_to_diag(float);
             }
             DataItem::Simple(simple) => {
                 self.simple_to_diag(simple);
@@ -488,4 +895,51 @@ impl DataItem {
         Context::new(&mut s, Layout::Pretty).item_to_diag(self);
         s
     }
+
+    /// Like [`DataItem::to_diag`], but with additional control over the
+    /// output.
+    pub fn to_diag_with(&self, options: &DiagOptions) -> String {
+        let layout = if options.pretty {
+            Layout::Pretty
+        } else {
+            Layout::Compact
+        };
+        let mut s = String::with_capacity(128);
+        let mut context = Context::new(&mut s, layout);
+        context.expand_arrays = options.expand_arrays;
+        context.typed_arrays = options.typed_arrays;
+        context.embedded_cbor = options.embedded_cbor;
+        context.bignum_literals = options.bignum_literals;
+        context.escape_non_ascii = options.escape_non_ascii;
+        context.app_literals = options.app_literals;
+        context.truncate = options.truncate;
+        context.explicit_encoding = options.explicit_encoding;
+        context.item_to_diag(self);
+        s
+    }
+}
+
+/// Encodes a sequence of data items (RFC 8742) in diagnostic notation,
+/// separated by commas as [`parse_diag_seq`](crate::parse_diag_seq) expects,
+/// with each item on its own line when pretty printing.
+///
+/// # Examples
+///
+/// ```rust
+/// use cbor_diag::{DataItem, DiagOptions, IntegerWidth};
+///
+/// let items = [
+///     DataItem::Integer { value: 1, bitwidth: IntegerWidth::Zero },
+///     DataItem::Array { data: vec![], bitwidth: Some(IntegerWidth::Zero) },
+/// ];
+///
+/// assert_eq!(cbor_diag::to_diag_seq(&items, &DiagOptions::default()), "1,[]");
+/// ```
+pub fn to_diag_seq(items: &[DataItem], options: &DiagOptions) -> String {
+    let separator = if options.pretty { ",\n" } else { "," };
+    items
+        .iter()
+        .map(|item| item.to_diag_with(options))
+        .collect::<Vec<_>>()
+        .join(separator)
 }
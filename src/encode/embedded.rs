@@ -0,0 +1,57 @@
+//! Heuristic detection of CBOR embedded in byte strings that are not tagged
+//! with [`crate::Tag::ENCODED_CBOR`], as many protocols do.
+
+use crate::{parse_bytes_partial, DataItem};
+
+/// Options controlling the detection of CBOR embedded in untagged byte
+/// strings, see [`DiagOptions::embedded_cbor`](crate::DiagOptions::embedded_cbor)
+/// and [`HexOptions::embedded_cbor`](crate::HexOptions::embedded_cbor).
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub struct EmbeddedCbor {
+    /// The minimum length of byte strings to check, as short byte strings are
+    /// often a valid CBOR sequence by chance.
+    pub min_length: usize,
+
+    /// How many levels of embedded CBOR to decode within each other.
+    pub max_depth: usize,
+}
+
+impl Default for EmbeddedCbor {
+    fn default() -> Self {
+        Self {
+            min_length: 4,
+            max_depth: 4,
+        }
+    }
+}
+
+impl EmbeddedCbor {
+    /// The options to use within embedded CBOR, one level deeper.
+    pub(crate) fn nested(self) -> Self {
+        Self {
+            max_depth: self.max_depth.saturating_sub(1),
+            ..self
+        }
+    }
+
+    /// The items of the well-formed CBOR sequence making up all of `data`,
+    /// if it is long enough to check and any are within the depth limit.
+    pub(crate) fn decode(&self, data: &[u8]) -> Option<Vec<DataItem>> {
+        if self.max_depth == 0 || data.len() < self.min_length {
+            return None;
+        }
+
+        let mut items = Vec::new();
+        let mut rest = data;
+        while !rest.is_empty() {
+            let (item, len) = parse_bytes_partial(rest).ok()??;
+            items.push(item);
+            rest = &rest[len..];
+        }
+
+        // Only decode data that encodes back to the same bytes, so that the
+        // output represents the original data
+        let encoded: Vec<u8> = items.iter().flat_map(DataItem::to_bytes).collect();
+        (encoded == data).then_some(items)
+    }
+}
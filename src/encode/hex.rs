@@ -1,25 +1,97 @@
 use std::{
-    ascii, cmp,
+    ascii,
+    borrow::Cow,
+    cmp,
     convert::TryFrom,
-    i64, iter,
+    iter,
     net::{Ipv4Addr, Ipv6Addr},
 };
 
-use super::Encoding;
+use super::{
+    json::{parse_json, Json},
+    language::LanguageTaggedString,
+    multi_dimensional::{is_homogeneous, MultiDimensionalArray},
+    EmbeddedCbor, Encoding,
+};
 use chrono::{DateTime, NaiveDate, NaiveDateTime};
 use half::f16;
 use num_bigint::{BigInt, BigUint, Sign};
 use num_rational::{BigRational, Ratio};
-use num_traits::pow::pow;
+use num_traits::{pow::pow, ToPrimitive, Zero};
 use separator::Separatable;
 use url::Url;
 use uuid::Uuid;
 
-use crate::{parse_bytes, ByteString, DataItem, FloatWidth, IntegerWidth, Simple, Tag, TextString};
+use crate::{
+    f128::F128, magic::describe_content_format, resolve::References, ByteString, DataItem, Float,
+    FloatWidth, IntegerWidth, Simple, Tag, TextString,
+};
+
+mod byte_string;
+mod cid;
+mod cose;
+mod geo;
+mod lenient;
+mod time;
+
+/// An application-specific set of annotations applied by
+/// [`DataItem::to_hex_with`] in addition to the generic annotations derived
+/// from the structure and semantic tags of the data item.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum AnnotationProfile {
+    /// Labels the entries of any [COSE_Key][] map (and arrays of them, a
+    /// COSE_KeySet) found within the data item, along with the registered key
+    /// types, curves, algorithms and key operations. Private key parameters
+    /// are flagged with a warning.
+    ///
+    /// [COSE_Key]: https://www.rfc-editor.org/rfc/rfc9052#section-7
+    Cose,
+}
+
+/// How the contents of byte strings are annotated by
+/// [`DataItem::to_hex_with`], when they are not tagged with an encoding hint
+/// ([`Tag::ENCODED_BASE64URL`], [`Tag::ENCODED_BASE64`] or
+/// [`Tag::ENCODED_BASE16`]).
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub enum ByteStringStyle {
+    /// As a string with any non-printable or non-ASCII bytes escaped, e.g.
+    /// `"\x01abc"`.
+    #[default]
+    Ascii,
+
+    /// As text if it is printable UTF-8, e.g. `'abc'`, otherwise as base16,
+    /// e.g. `h'01616263'`, with a hint if its length is that of a well-known
+    /// binary value, such as a hash or address.
+    Detect,
+
+    /// As base64url, e.g. `b64'AWFiYw'`.
+    Base64,
+
+    /// As base32, e.g. `b32'AFQWEYY='`.
+    Base32,
+}
+
+/// Options controlling the output of [`DataItem::to_hex_with`].
+#[derive(Debug, Clone, Default)]
+pub struct HexOptions {
+    /// An optional application-specific annotation profile to apply.
+    pub profile: Option<AnnotationProfile>,
+
+    /// Whether to annotate the contents of untagged byte strings which
+    /// contain a well-formed CBOR data item or sequence.
+    pub embedded_cbor: Option<EmbeddedCbor>,
+
+    /// How the contents of byte strings without an encoding hint are
+    /// annotated.
+    pub byte_strings: ByteStringStyle,
+}
 
 struct Context {
     encoding: Option<Encoding>,
-    reference_count: u64,
+    byte_strings: ByteStringStyle,
+    references: References,
+    profile: Option<AnnotationProfile>,
+    embedded_cbor: Option<EmbeddedCbor>,
 }
 
 impl Context {
@@ -33,6 +105,24 @@ impl Context {
         self.encoding = encoding;
         value
     }
+
+    fn with_references<T>(&mut self, references: References, f: impl FnOnce(&mut Self) -> T) -> T {
+        let references = std::mem::replace(&mut self.references, references);
+        let value = f(self);
+        self.references = references;
+        value
+    }
+
+    fn with_embedded_cbor<T>(
+        &mut self,
+        embedded_cbor: Option<EmbeddedCbor>,
+        f: impl FnOnce(&mut Self) -> T,
+    ) -> T {
+        let embedded_cbor = std::mem::replace(&mut self.embedded_cbor, embedded_cbor);
+        let value = f(self);
+        self.embedded_cbor = embedded_cbor;
+        value
+    }
 }
 
 struct Line {
@@ -55,14 +145,22 @@ impl Line {
             DataItem::Integer { value, bitwidth } => integer_to_hex(value, bitwidth),
             DataItem::Negative { value, bitwidth } => negative_to_hex(value, bitwidth),
             DataItem::ByteString(ref bytestring) => {
-                definite_bytestring_to_hex(context.encoding, bytestring)
+                context.references.record_string(value);
+                let mut line =
+                    definite_bytestring_to_hex(context.encoding, context.byte_strings, bytestring);
+                line.sublines
+                    .extend(embedded_cbor(context, &bytestring.data));
+                line
             }
             DataItem::IndefiniteByteString(ref bytestrings) => {
                 indefinite_string_to_hex(0x02, "bytes", bytestrings, |bytestring| {
-                    definite_bytestring_to_hex(context.encoding, bytestring)
+                    definite_bytestring_to_hex(context.encoding, context.byte_strings, bytestring)
                 })
             }
-            DataItem::TextString(ref textstring) => definite_textstring_to_hex(textstring),
+            DataItem::TextString(ref textstring) => {
+                context.references.record_string(value);
+                definite_textstring_to_hex(textstring)
+            }
             DataItem::IndefiniteTextString(ref textstrings) => {
                 indefinite_string_to_hex(0x03, "text", textstrings, definite_textstring_to_hex)
             }
@@ -73,11 +171,26 @@ impl Line {
                 bitwidth,
                 ref value,
             } => tagged_to_hex(context, tag, bitwidth, value),
-            DataItem::Float { value, bitwidth } => float_to_hex(value, bitwidth),
+            DataItem::Float(float) => float_to_hex(float),
             DataItem::Simple(simple) => simple_to_hex(simple),
         }
     }
 
+    /// Merges a list of sibling lines, aligning their comments, for
+    /// embedding them within the comments of another line.
+    fn merge_nested(lines: Vec<Line>) -> impl Iterator<Item = Line> {
+        let hex_width = lines.iter().map(Line::hex_width).max().unwrap_or(0);
+        let mut output = String::with_capacity(128);
+        for line in lines {
+            line.do_merge(hex_width as isize, 0, &mut output);
+        }
+        output
+            .lines()
+            .map(|line| Line::new("", line))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
     fn merge(self) -> String {
         let hex_width = self.hex_width();
         let mut output = String::with_capacity(128);
@@ -134,11 +247,11 @@ fn integer_to_hex(value: u64, mut bitwidth: IntegerWidth) -> Line {
     if bitwidth == IntegerWidth::Unknown {
         bitwidth = if value < 24 {
             IntegerWidth::Zero
-        } else if value <= u64::from(u8::max_value()) {
+        } else if value <= u64::from(u8::MAX) {
             IntegerWidth::Eight
-        } else if value <= u64::from(u16::max_value()) {
+        } else if value <= u64::from(u16::MAX) {
             IntegerWidth::Sixteen
-        } else if value <= u64::from(u32::max_value()) {
+        } else if value <= u64::from(u32::MAX) {
             IntegerWidth::ThirtyTwo
         } else {
             IntegerWidth::SixtyFour
@@ -163,11 +276,11 @@ fn negative_to_hex(value: u64, mut bitwidth: IntegerWidth) -> Line {
     if bitwidth == IntegerWidth::Unknown {
         bitwidth = if value < 24 {
             IntegerWidth::Zero
-        } else if value <= u64::from(u8::max_value()) {
+        } else if value <= u64::from(u8::MAX) {
             IntegerWidth::Eight
-        } else if value <= u64::from(u16::max_value()) {
+        } else if value <= u64::from(u16::MAX) {
             IntegerWidth::Sixteen
-        } else if value <= u64::from(u32::max_value()) {
+        } else if value <= u64::from(u32::MAX) {
             IntegerWidth::ThirtyTwo
         } else {
             IntegerWidth::SixtyFour
@@ -199,11 +312,11 @@ fn length_to_hex(
     if bitwidth == Some(IntegerWidth::Unknown) {
         bitwidth = if length.unwrap() < 24 {
             Some(IntegerWidth::Zero)
-        } else if length.unwrap() < usize::from(u8::max_value()) {
+        } else if length.unwrap() < usize::from(u8::MAX) {
             Some(IntegerWidth::Eight)
-        } else if length.unwrap() < usize::from(u16::max_value()) {
+        } else if length.unwrap() < usize::from(u16::MAX) {
             Some(IntegerWidth::Sixteen)
-        } else if length.unwrap() < u32::max_value() as usize {
+        } else if length.unwrap() < u32::MAX as usize {
             Some(IntegerWidth::ThirtyTwo)
         } else {
             Some(IntegerWidth::SixtyFour)
@@ -270,12 +383,18 @@ fn bytes_to_hex(encoding: Option<Encoding>, data: &[u8]) -> impl Iterator<Item =
     })
 }
 
-fn definite_bytestring_to_hex(encoding: Option<Encoding>, bytestring: &ByteString) -> Line {
+fn definite_bytestring_to_hex(
+    encoding: Option<Encoding>,
+    style: ByteStringStyle,
+    bytestring: &ByteString,
+) -> Line {
     let ByteString { ref data, bitwidth } = *bytestring;
 
     let mut line = length_to_hex(Some(data.len()), Some(bitwidth), 2, "bytes");
 
-    if data.is_empty() {
+    if encoding.is_none() && style != ByteStringStyle::Ascii {
+        line.sublines.extend(byte_string::contents(style, data));
+    } else if data.is_empty() {
         line.sublines.push(Line::new("", "\"\""));
     } else {
         line.sublines.extend(bytes_to_hex(encoding, data))
@@ -353,6 +472,10 @@ fn array_to_hex(context: &mut Context, array: &[DataItem], bitwidth: Option<Inte
         line.sublines.push(Line::new("ff", "break"));
     }
 
+    if context.profile == Some(AnnotationProfile::Cose) {
+        cose::annotate_key_set(array, &mut line);
+    }
+
     line
 }
 
@@ -374,6 +497,10 @@ fn map_to_hex(
         line.sublines.push(Line::new("ff", "break"));
     }
 
+    if context.profile == Some(AnnotationProfile::Cose) {
+        cose::annotate_key(values, &mut line);
+    }
+
     line
 }
 
@@ -387,11 +514,11 @@ fn tagged_to_hex(
     if bitwidth == IntegerWidth::Unknown {
         bitwidth = if tag_value < 24 {
             IntegerWidth::Zero
-        } else if tag_value < u64::from(u8::max_value()) {
+        } else if tag_value < u64::from(u8::MAX) {
             IntegerWidth::Eight
-        } else if tag_value < u64::from(u16::max_value()) {
+        } else if tag_value < u64::from(u16::MAX) {
             IntegerWidth::Sixteen
-        } else if tag_value < u64::from(u32::max_value()) {
+        } else if tag_value < u64::from(u32::MAX) {
             IntegerWidth::ThirtyTwo
         } else {
             IntegerWidth::SixtyFour
@@ -414,6 +541,7 @@ fn tagged_to_hex(
         Tag::NEGATIVE_BIGNUM => Some("negative bignum"),
         Tag::DECIMAL_FRACTION => Some("decimal fraction"),
         Tag::BIGFLOAT => Some("bigfloat"),
+        Tag::RATIONAL => Some("rational number"),
         Tag::ENCODED_BASE64URL => Some("suggested base64url encoding"),
         Tag::ENCODED_BASE64 => Some("suggested base64 encoding"),
         Tag::ENCODED_BASE16 => Some("suggested base16 encoding"),
@@ -425,12 +553,32 @@ fn tagged_to_hex(
         Tag::REGEX => Some("regex"),
         Tag::MIME => Some("mime message"),
         Tag::UUID => Some("uuid"),
+        Tag::LANGUAGE_TAGGED_STRING => Some("language-tagged string"),
+        Tag::MULTI_DIMENSIONAL_ARRAY => Some("multi-dimensional array, row-major"),
+        Tag::MULTI_DIMENSIONAL_ARRAY_COLUMN_MAJOR => Some("multi-dimensional array, column-major"),
+        Tag::HOMOGENEOUS_ARRAY => Some("homogeneous array"),
+        Tag::CID => Some("ipld content identifier"),
         Tag::NETWORK_ADDRESS => Some("network address"),
+        Tag::EMBEDDED_JSON => Some("embedded json"),
+        Tag::ELISION => Some("elided data"),
         Tag::SELF_DESCRIBE_CBOR => Some("self describe cbor"),
+        Tag::SELF_DESCRIBE_CBOR_SEQUENCE => Some("self describe cbor sequence"),
+        Tag::CBOR_LABELED_NON_CBOR_DATA => Some("cbor-labeled non-cbor data"),
         Tag::EPOCH_DATE => Some("epoch date value"),
+        Tag::GEOGRAPHIC_COORDINATES => Some("geographic coordinates"),
+        Tag::GEOGRAPHIC_COORDINATE_REFERENCE_SYSTEM => {
+            Some("geographic coordinates with reference system")
+        }
         Tag::DATE => Some("standard date string"),
+        Tag::EXTENDED_TIME => Some("extended time"),
+        Tag::DURATION => Some("duration"),
+        Tag::PERIOD => Some("period"),
         Tag::SHAREABLE => Some("shareable value"),
         Tag::SHARED_REF => Some("reference to shared value"),
+        Tag::STRINGREF_NAMESPACE => Some("stringref namespace"),
+        Tag::STRINGREF => Some("reference to shared string"),
+        Tag::SET => Some("mathematical finite set"),
+        Tag::MAP_WITH_ANY_KEYS => Some("map with any keys"),
         Tag::IPV4 => Some("ipv4 address and/or prefix"),
         Tag::IPV6 => Some("ipv6 address and/or prefix"),
         Tag::TYPED_ARRAY_U8 => Some("typed array of u8"),
@@ -462,9 +610,22 @@ fn tagged_to_hex(
         Tag::TYPED_ARRAY_F32_BIG_ENDIAN => Some("typed array of f32, big endian"),
         Tag::TYPED_ARRAY_F64_BIG_ENDIAN => Some("typed array of f64, big endian"),
         Tag::TYPED_ARRAY_F128_BIG_ENDIAN => Some("typed array of f128, big endian"),
+        _ if tag.content_format().is_some() => Some("content-format labeled cbor"),
         _ => None,
     };
 
+    // The shared value is resolved ahead of traversing it, without keeping
+    // what that records, so that the strings and values within it are only
+    // recorded once. References to other shared values within it are kept,
+    // and only expanded when describing a reference to it
+    let shared = if tag == Tag::SHAREABLE {
+        let index = context.references.share();
+        let resolved = context.references.resolve_ahead(value);
+        Some((index, resolved.unwrap_or_else(|_| value.clone())))
+    } else {
+        None
+    };
+
     let extra_lines = match tag {
         Tag::DATETIME => vec![datetime_epoch(value)],
         Tag::EPOCH_DATETIME => vec![epoch_datetime(value)],
@@ -472,37 +633,85 @@ fn tagged_to_hex(
         Tag::NEGATIVE_BIGNUM => vec![negative_bignum(value)],
         Tag::DECIMAL_FRACTION => vec![decimal_fraction(value)],
         Tag::BIGFLOAT => vec![bigfloat(value)],
+        Tag::RATIONAL => rational(value),
         Tag::URI => vec![uri(value)],
         Tag::BASE64URL => vec![base64url(value)],
         Tag::BASE64 => vec![base64(value)],
-        Tag::ENCODED_CBOR => vec![encoded_cbor(value)],
-        Tag::ENCODED_CBOR_SEQ => encoded_cbor_seq(value),
         Tag::NETWORK_ADDRESS => vec![network_address(value)],
+        Tag::EMBEDDED_JSON => vec![embedded_json(value)],
         Tag::UUID => vec![uuid(value)],
+        Tag::LANGUAGE_TAGGED_STRING => vec![language_tagged_string(value)],
+        Tag::MULTI_DIMENSIONAL_ARRAY => vec![multi_dimensional_array(value, false)],
+        Tag::MULTI_DIMENSIONAL_ARRAY_COLUMN_MAJOR => vec![multi_dimensional_array(value, true)],
+        Tag::HOMOGENEOUS_ARRAY => homogeneous_array(value),
+        Tag::CID => vec![cid::cid(value)],
         Tag::EPOCH_DATE => vec![epoch_date(value)],
+        Tag::GEOGRAPHIC_COORDINATES => vec![geo::geographic_coordinates(value)],
+        Tag::GEOGRAPHIC_COORDINATE_REFERENCE_SYSTEM => geo::coordinate_reference_system(value),
         Tag::DATE => vec![date_epoch(value)],
-        Tag::SHAREABLE => {
-            let line = format!("reference({})", context.reference_count.separated_string());
-            context.reference_count += 1;
-            vec![Line::new("", line)]
-        }
-        Tag::SHARED_REF => vec![shared_ref(value, context.reference_count)],
+        Tag::EXTENDED_TIME => time::extended_time(value),
+        Tag::DURATION => time::duration(value),
+        Tag::PERIOD => time::period(value),
+        Tag::SHAREABLE => shared
+            .iter()
+            .map(|(index, _)| Line::new("", format!("reference({})", index.separated_string())))
+            .collect(),
+        Tag::SHARED_REF => vec![shared_ref(value, &context.references)],
+        Tag::STRINGREF => vec![stringref(value, &context.references)],
+        Tag::SET => set(value),
+        Tag::MAP_WITH_ANY_KEYS => map_with_any_keys(value),
         Tag::IPV4 => vec![ipv4_address_or_prefix(value)],
         Tag::IPV6 => vec![ipv6_address_or_prefix(value)],
-        _ => vec![],
+        Tag::SELF_DESCRIBE_CBOR_SEQUENCE | Tag::CBOR_LABELED_NON_CBOR_DATA => magic_bor(value),
+        _ => match tag.content_format() {
+            Some(content_format) => vec![Line::new("", describe_content_format(content_format))],
+            None => vec![],
+        },
     };
 
+    // A tagged byte string already has a meaning, so is not checked for
+    // embedded CBOR, unlike the contents of explicitly encoded CBOR
+    let embedded_cbor = context.embedded_cbor;
+    if let DataItem::ByteString(_) = value {
+        context.embedded_cbor = None;
+    }
+
     let sublines = match tag {
+        Tag::STRINGREF_NAMESPACE => {
+            context.references.enter_namespace();
+            let line = Line::from_value(context, value);
+            context.references.exit_namespace();
+            vec![line]
+        }
+        // The contents are annotated after the byte string containing them,
+        // so that any strings and shared values within them are recorded in
+        // order
+        Tag::ENCODED_CBOR => {
+            let line = Line::from_value(context, value);
+            let contents =
+                context.with_embedded_cbor(embedded_cbor, |context| encoded_cbor(context, value));
+            vec![line, contents]
+        }
+        Tag::ENCODED_CBOR_SEQ => {
+            let line = Line::from_value(context, value);
+            let contents = context
+                .with_embedded_cbor(embedded_cbor, |context| encoded_cbor_seq(context, value));
+            iter::once(line).chain(contents).collect()
+        }
         Tag::ENCODED_BASE64URL => context.with_encoding(Some(Encoding::Base64Url), |context| {
             vec![Line::from_value(context, value)]
         }),
         Tag::ENCODED_BASE64 => context.with_encoding(Some(Encoding::Base64), |context| {
             vec![Line::from_value(context, value)]
         }),
-        Tag::ENCODED_BASE16 | Tag::NETWORK_ADDRESS | Tag::UUID | Tag::IPV4 | Tag::IPV6 => context
-            .with_encoding(Some(Encoding::Base16), |context| {
-                vec![Line::from_value(context, value)]
-            }),
+        Tag::ENCODED_BASE16
+        | Tag::NETWORK_ADDRESS
+        | Tag::UUID
+        | Tag::CID
+        | Tag::IPV4
+        | Tag::IPV6 => context.with_encoding(Some(Encoding::Base16), |context| {
+            vec![Line::from_value(context, value)]
+        }),
         Tag::TYPED_ARRAY_U8 | Tag::TYPED_ARRAY_U8_CLAMPED => {
             typed_array::<1>(context, value, "unsigned", |[byte]| byte.to_string())
         }
@@ -560,9 +769,9 @@ fn tagged_to_hex(
         Tag::TYPED_ARRAY_F64_BIG_ENDIAN => typed_array::<8>(context, value, "float", |bytes| {
             f64::from_be_bytes(bytes).separated_string()
         }),
-        Tag::TYPED_ARRAY_F128_BIG_ENDIAN => {
-            typed_array::<16>(context, value, "float", |_| "TODO: f128 unsupported".into())
-        }
+        Tag::TYPED_ARRAY_F128_BIG_ENDIAN => typed_array::<16>(context, value, "float", |bytes| {
+            F128::from_be_bytes(bytes).describe()
+        }),
         Tag::TYPED_ARRAY_F16_LITTLE_ENDIAN => typed_array::<2>(context, value, "float", |bytes| {
             f16::from_le_bytes(bytes).to_f64().separated_string()
         }),
@@ -573,7 +782,9 @@ fn tagged_to_hex(
             f64::from_le_bytes(bytes).separated_string()
         }),
         Tag::TYPED_ARRAY_F128_LITTLE_ENDIAN => {
-            typed_array::<16>(context, value, "float", |_| "TODO: f128 unsupported".into())
+            typed_array::<16>(context, value, "float", |bytes| {
+                F128::from_le_bytes(bytes).describe()
+            })
         }
         _ => {
             vec![Line::from_value(context, value)]
@@ -583,6 +794,12 @@ fn tagged_to_hex(
     .chain(extra_lines)
     .collect();
 
+    context.embedded_cbor = embedded_cbor;
+
+    if let Some((index, resolved)) = shared {
+        context.references.set_shared(index, resolved);
+    }
+
     let comment = if let Some(extra) = extra {
         format!("{extra}, tag({tag_value})")
     } else {
@@ -614,7 +831,7 @@ fn datetime_epoch(value: &DataItem) -> Line {
 fn epoch_datetime(value: &DataItem) -> Line {
     let date = match *value {
         DataItem::Integer { value, .. } => {
-            if value >= (i64::max_value() as u64) {
+            if value >= (i64::MAX as u64) {
                 None
             } else {
                 NaiveDateTime::from_timestamp_opt(value as i64, 0)
@@ -622,7 +839,7 @@ fn epoch_datetime(value: &DataItem) -> Line {
         }
 
         DataItem::Negative { value, .. } => {
-            if value >= (i64::max_value() as u64) {
+            if value >= (i64::MAX as u64) {
                 None
             } else if let Some(value) = (-1i64).checked_sub(value as i64) {
                 NaiveDateTime::from_timestamp_opt(value, 0)
@@ -631,8 +848,9 @@ fn epoch_datetime(value: &DataItem) -> Line {
             }
         }
 
-        DataItem::Float { value, .. } => {
-            if value - 1.0 <= (i64::min_value() as f64) || value >= (i64::max_value() as f64) {
+        DataItem::Float(float) => {
+            let value = float.as_f64();
+            if value - 1.0 <= (i64::MIN as f64) || value >= (i64::MAX as f64) {
                 None
             } else {
                 let (value, fract) = if value < 0.0 {
@@ -716,25 +934,150 @@ fn epoch_date(value: &DataItem) -> Line {
     }
 }
 
-fn shared_ref(value: &DataItem, known_references: u64) -> Line {
+/// The most items of a shared value shown in the comment on a reference to
+/// it, as references within it can expand to far more than is encoded.
+const SHOWN_SHARED_ITEMS: usize = 64;
+
+fn shared_ref(value: &DataItem, references: &References) -> Line {
     match *value {
         DataItem::Integer { value, .. } => {
-            if value < known_references {
-                Line::new("", format!("reference-to({})", value.separated_string()))
-            } else {
-                Line::new(
-                    "",
-                    format!(
-                        "reference-to({}), not previously shared",
-                        value.separated_string()
-                    ),
-                )
+            let index = value.separated_string();
+            match references.describe_shared(value, SHOWN_SHARED_ITEMS) {
+                Some(Some(shared)) => {
+                    Line::new("", format!("reference-to({index}): {}", shared.to_diag()))
+                }
+                Some(None) => Line::new("", format!("reference-to({index}), cyclic reference")),
+                None => Line::new("", format!("reference-to({index}), not previously shared")),
             }
         }
         _ => Line::new("", "invalid type for shared ref"),
     }
 }
 
+fn stringref(value: &DataItem, references: &References) -> Line {
+    match *value {
+        DataItem::Integer { value, .. } => {
+            let index = value.separated_string();
+            match references.lookup_string(value) {
+                Some(Some(string)) => {
+                    Line::new("", format!("stringref({index}): {}", string.to_diag()))
+                }
+                Some(None) => Line::new("", format!("stringref({index}), not previously defined")),
+                None => Line::new(
+                    "",
+                    format!("stringref({index}), outside of stringref namespace"),
+                ),
+            }
+        }
+        _ => Line::new("", "invalid type for stringref"),
+    }
+}
+
+/// Compares two data items by their value, ignoring differences in how they
+/// are encoded.
+fn same_value(left: &DataItem, right: &DataItem) -> bool {
+    fn concat<'a>(value: &'a DataItem) -> Option<Cow<'a, [u8]>> {
+        match value {
+            DataItem::ByteString(ByteString { data, .. }) => Some(Cow::Borrowed(data)),
+            DataItem::IndefiniteByteString(chunks) => Some(Cow::Owned(
+                chunks
+                    .iter()
+                    .flat_map(|chunk| chunk.data.iter().copied())
+                    .collect(),
+            )),
+            _ => None,
+        }
+    }
+
+    fn concat_text<'a>(value: &'a DataItem) -> Option<Cow<'a, str>> {
+        match value {
+            DataItem::TextString(TextString { data, .. }) => Some(Cow::Borrowed(data)),
+            DataItem::IndefiniteTextString(chunks) => Some(Cow::Owned(
+                chunks.iter().map(|chunk| chunk.data.as_str()).collect(),
+            )),
+            _ => None,
+        }
+    }
+
+    match (left, right) {
+        (DataItem::Integer { value: l, .. }, DataItem::Integer { value: r, .. })
+        | (DataItem::Negative { value: l, .. }, DataItem::Negative { value: r, .. }) => l == r,
+        (
+            DataItem::ByteString(_) | DataItem::IndefiniteByteString(_),
+            DataItem::ByteString(_) | DataItem::IndefiniteByteString(_),
+        ) => concat(left) == concat(right),
+        (
+            DataItem::TextString(_) | DataItem::IndefiniteTextString(_),
+            DataItem::TextString(_) | DataItem::IndefiniteTextString(_),
+        ) => concat_text(left) == concat_text(right),
+        (DataItem::Array { data: l, .. }, DataItem::Array { data: r, .. }) => {
+            l.len() == r.len() && l.iter().zip(r).all(|(l, r)| same_value(l, r))
+        }
+        // Maps are unordered, so each entry only needs a match somewhere in
+        // the other map
+        (DataItem::Map { data: l, .. }, DataItem::Map { data: r, .. }) => {
+            let contains = |entries: &[(DataItem, DataItem)],
+                            (key, value): &(DataItem, DataItem)| {
+                entries
+                    .iter()
+                    .any(|(k, v)| same_value(key, k) && same_value(value, v))
+            };
+            l.len() == r.len()
+                && l.iter().all(|entry| contains(r, entry))
+                && r.iter().all(|entry| contains(l, entry))
+        }
+        (
+            DataItem::Tag {
+                tag: l, value: lv, ..
+            },
+            DataItem::Tag {
+                tag: r, value: rv, ..
+            },
+        ) => l == r && same_value(lv, rv),
+        (DataItem::Float(l), DataItem::Float(r)) => l.as_f64().to_bits() == r.as_f64().to_bits(),
+        (DataItem::Simple(l), DataItem::Simple(r)) => l == r,
+        _ => false,
+    }
+}
+
+/// Warnings for each of `items` that is the same value as an earlier one.
+fn duplicates(items: &[&DataItem], what: &str) -> Vec<Line> {
+    items
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| {
+            let first = items[..index]
+                .iter()
+                .position(|other| same_value(item, other))?;
+            Some(Line::new(
+                "",
+                format!(
+                    "warning: duplicate {what} at index {}, same as index {}",
+                    index.separated_string(),
+                    first.separated_string(),
+                ),
+            ))
+        })
+        .collect()
+}
+
+fn set(value: &DataItem) -> Vec<Line> {
+    match value {
+        DataItem::Array { data, .. } => duplicates(&data.iter().collect::<Vec<_>>(), "set element"),
+        _ => vec![Line::new("", "invalid type for set")],
+    }
+}
+
+fn map_with_any_keys(value: &DataItem) -> Vec<Line> {
+    match value {
+        DataItem::Map { data, .. } => {
+            let keys = data.iter().map(|(key, _)| key).collect::<Vec<_>>();
+            duplicates(&keys, "map key")
+        }
+        _ => vec![Line::new("", "invalid type for map with any keys")],
+    }
+}
+
 fn extract_positive_bignum(value: &DataItem) -> Option<BigUint> {
     if let DataItem::ByteString(ByteString { data, .. }) = value {
         Some(BigUint::from_bytes_be(data))
@@ -763,6 +1106,25 @@ fn negative_bignum(value: &DataItem) -> Line {
         .unwrap_or_else(|| Line::new("", "invalid type for bignum"))
 }
 
+/// Extracts an integer that may be encoded as either a plain integer or a bignum.
+pub(super) fn extract_integer(value: &DataItem) -> Option<BigInt> {
+    match *value {
+        DataItem::Integer { value, .. } => Some(BigInt::from(value)),
+        DataItem::Negative { value, .. } => Some(BigInt::from(-1) - BigInt::from(value)),
+        DataItem::Tag {
+            tag: Tag::POSITIVE_BIGNUM,
+            ref value,
+            ..
+        } => extract_positive_bignum(value).map(|value| BigInt::from_biguint(Sign::Plus, value)),
+        DataItem::Tag {
+            tag: Tag::NEGATIVE_BIGNUM,
+            ref value,
+            ..
+        } => extract_negative_bignum(value),
+        _ => None,
+    }
+}
+
 fn extract_fraction(value: &DataItem, base: usize) -> Result<BigRational, &'static str> {
     Ok(match value {
         DataItem::Array { data, .. } => {
@@ -771,14 +1133,14 @@ fn extract_fraction(value: &DataItem, base: usize) -> Result<BigRational, &'stat
             }
             let (exponent, positive_exponent) = match data[0] {
                 DataItem::Integer { value, .. } => {
-                    if value <= usize::max_value() as u64 {
+                    if value <= usize::MAX as u64 {
                         (value as usize, true)
                     } else {
                         return Err("exponent is too large");
                     }
                 }
                 DataItem::Negative { value, .. } => {
-                    if value < usize::max_value() as u64 {
+                    if value < usize::MAX as u64 {
                         (value as usize + 1, false)
                     } else {
                         return Err("exponent is too large");
@@ -786,27 +1148,7 @@ fn extract_fraction(value: &DataItem, base: usize) -> Result<BigRational, &'stat
                 }
                 _ => return Err("invalid type"),
             };
-            let mantissa = match data[1] {
-                DataItem::Integer { value, .. } => BigInt::from(value),
-                DataItem::Negative { value, .. } => BigInt::from(-1) - BigInt::from(value),
-                DataItem::Tag {
-                    tag: Tag::POSITIVE_BIGNUM,
-                    ref value,
-                    ..
-                } => match extract_positive_bignum(value) {
-                    Some(value) => BigInt::from_biguint(Sign::Plus, value),
-                    _ => return Err("invalid type"),
-                },
-                DataItem::Tag {
-                    tag: Tag::NEGATIVE_BIGNUM,
-                    ref value,
-                    ..
-                } => match extract_negative_bignum(value) {
-                    Some(value) => value,
-                    _ => return Err("invalid type"),
-                },
-                _ => return Err("invalid type"),
-            };
+            let mantissa = extract_integer(&data[1]).ok_or("invalid type")?;
             let multiplier = if positive_exponent {
                 Ratio::from_integer(pow(BigInt::from(base), exponent))
             } else {
@@ -832,6 +1174,73 @@ fn bigfloat(value: &DataItem) -> Line {
         .unwrap_or_else(|err| Line::new("", format!("{err} for bigfloat")))
 }
 
+/// Formats `value` as a decimal number, truncated to at most `digits`
+/// significant digits after the decimal point, along with whether the result
+/// is exact.
+fn decimal_expansion(value: &BigRational, digits: usize) -> (String, bool) {
+    let (numer, denom) = (value.numer().magnitude(), value.denom().magnitude());
+    let mut output = String::new();
+    if value.numer().sign() == Sign::Minus {
+        output.push('-');
+    }
+    let integer = numer / denom;
+    let mut significant = !integer.is_zero();
+    output.push_str(&integer.to_string());
+    let mut remainder = numer % denom;
+    if !remainder.is_zero() {
+        output.push('.');
+        let mut remaining = digits;
+        while remaining > 0 && !remainder.is_zero() {
+            remainder *= 10u8;
+            let digit = (&remainder / denom).to_u8().unwrap();
+            output.push(char::from(b'0' + digit));
+            remainder %= denom;
+            significant |= digit != 0;
+            if significant {
+                remaining -= 1;
+            }
+        }
+    }
+    (output, remainder.is_zero())
+}
+
+fn rational(value: &DataItem) -> Vec<Line> {
+    let (numerator, denominator) = match value {
+        DataItem::Array { data, .. } if data.len() == 2 => {
+            match (extract_integer(&data[0]), extract_integer(&data[1])) {
+                (Some(numerator), Some(denominator)) => (numerator, denominator),
+                _ => return vec![Line::new("", "invalid type for rational number")],
+            }
+        }
+        _ => return vec![Line::new("", "invalid type for rational number")],
+    };
+
+    match denominator.sign() {
+        Sign::Plus => {}
+        Sign::NoSign => return vec![Line::new("", "invalid rational number, zero denominator")],
+        Sign::Minus => {
+            return vec![Line::new(
+                "",
+                "invalid rational number, negative denominator",
+            )]
+        }
+    }
+
+    let rational = BigRational::new(numerator, denominator);
+    let (decimal, exact) = decimal_expansion(&rational, 20);
+    vec![
+        Line::new("", format!("rational({rational})")),
+        Line::new(
+            "",
+            if exact {
+                format!("decimal({decimal})")
+            } else {
+                format!("decimal(≈{decimal}…)")
+            },
+        ),
+    ]
+}
+
 fn uri(value: &DataItem) -> Line {
     if let DataItem::TextString(TextString { data, .. }) = value {
         Line::new(
@@ -847,6 +1256,13 @@ fn uri(value: &DataItem) -> Line {
     }
 }
 
+fn language_tagged_string(value: &DataItem) -> Line {
+    match LanguageTaggedString::extract(value) {
+        Ok(string) => Line::new("", string.describe()),
+        Err(err) => Line::new("", err),
+    }
+}
+
 fn base64_base(
     value: &DataItem,
     encoding: data_encoding::Encoding,
@@ -889,18 +1305,24 @@ fn base64(value: &DataItem) -> Line {
         .unwrap_or_else(|err| Line::new("", format!("{err} for base64")))
 }
 
-fn encoded_cbor(value: &DataItem) -> Line {
+fn encoded_cbor(context: &mut Context, value: &DataItem) -> Line {
     if let DataItem::ByteString(ByteString { data, .. }) = value {
-        match parse_bytes(data) {
-            Ok(value) => {
+        match lenient::decode(context, data, 0, false) {
+            Ok(lines) => {
                 let mut line = Line::new("", "encoded cbor data item");
-                line.sublines
-                    .extend(value.to_hex().lines().map(|line| Line::new("", line)));
+                line.sublines.extend(Line::merge_nested(lines));
                 line
             }
-            Err(err) => {
-                let mut line = Line::new("", "failed to parse encoded cbor data item");
-                line.sublines.push(Line::new("", format!("{err:?}")));
+            Err(decoded) => {
+                let mut line = Line::new(
+                    "",
+                    format!("failed to parse encoded cbor data item, {}", decoded.error),
+                );
+                line.sublines.extend(Line::merge_nested(decoded.lines));
+                if let Ok(json) = parse_json(data) {
+                    line.sublines
+                        .push(json_line("hint: content is json", &json));
+                }
                 line
             }
         }
@@ -909,22 +1331,69 @@ fn encoded_cbor(value: &DataItem) -> Line {
     }
 }
 
-fn encoded_cbor_seq(value: &DataItem) -> Vec<Line> {
+fn embedded_cbor(context: &mut Context, data: &[u8]) -> Option<Line> {
+    let options = context.embedded_cbor?;
+    let items = options.decode(data)?;
+    let description = if items.len() == 1 {
+        "embedded cbor data item"
+    } else {
+        "embedded cbor sequence"
+    };
+    // Each item is only guessed to be CBOR, so does not take part in the
+    // surrounding stringref namespace and shared values
+    let lines = context.with_embedded_cbor(Some(options.nested()), |context| {
+        items
+            .iter()
+            .map(|item| {
+                context.with_references(References::default(), |context| {
+                    Line::from_value(context, item)
+                })
+            })
+            .collect()
+    });
+    let mut line = Line::new("", description);
+    line.sublines.extend(Line::merge_nested(lines));
+    Some(line)
+}
+
+fn json_line(description: &str, json: &Json) -> Line {
+    let mut line = Line::new("", description);
+    line.sublines
+        .extend(json.to_text(true).lines().map(|line| Line::new("", line)));
+    line
+}
+
+fn embedded_json(value: &DataItem) -> Line {
     if let DataItem::ByteString(ByteString { data, .. }) = value {
-        let mut data = data.as_slice();
+        match parse_json(data) {
+            Ok(json) => json_line("json", &json),
+            Err(err) => Line::new("", format!("invalid embedded json, {err}")),
+        }
+    } else {
+        Line::new("", "invalid type for embedded json")
+    }
+}
+
+fn encoded_cbor_seq(context: &mut Context, value: &DataItem) -> Vec<Line> {
+    if let DataItem::ByteString(ByteString { data, .. }) = value {
+        let mut offset = 0;
         let mut lines = Vec::new();
-        while let Ok(Some((item, len))) = crate::parse_bytes_partial(data) {
-            let (_, rest) = data.split_at(len);
-            data = rest;
+        while let Ok(Some((item, len))) = crate::parse_bytes_partial(&data[offset..]) {
+            offset += len;
             let mut line = Line::new("", "encoded cbor data item");
             line.sublines
-                .extend(item.to_hex().lines().map(|line| Line::new("", line)));
+                .extend(Line::merge_nested(vec![Line::from_value(context, &item)]));
             lines.push(line);
         }
-        if !data.is_empty() {
-            let err = parse_bytes(data).unwrap_err();
-            let mut line = Line::new("", "failed to parse remaining encoded cbor sequence");
-            line.sublines.push(Line::new("", format!("{err:?}")));
+        if let Err(decoded) = lenient::decode(context, data, offset, true) {
+            let mut line = Line::new(
+                "",
+                format!(
+                    "failed to parse remaining encoded cbor sequence, {}",
+                    decoded.error
+                ),
+            );
+            line.sublines.extend(Line::merge_nested(decoded.lines));
             lines.push(line);
         }
         lines
@@ -992,6 +1461,13 @@ fn network_address(value: &DataItem) -> Line {
     }
 }
 
+fn magic_bor(value: &DataItem) -> Vec<Line> {
+    match value {
+        DataItem::ByteString(ByteString { data, .. }) if data == b"BOR" => vec![],
+        _ => vec![Line::new("", "invalid magic number, expected 'BOR'")],
+    }
+}
+
 fn ipv4_address_or_prefix(value: &DataItem) -> Line {
     match value {
         DataItem::ByteString(ByteString { data, .. }) => {
@@ -1002,7 +1478,7 @@ fn ipv4_address_or_prefix(value: &DataItem) -> Line {
             }
         }
         DataItem::Array { data, .. } => {
-            match data.get(0) {
+            match data.first() {
                 Some(DataItem::Integer { value: length, .. }) => {
                     if let Some(DataItem::ByteString(ByteString { data: prefix, .. })) = data.get(1)
                     {
@@ -1072,7 +1548,7 @@ fn ipv6_address_or_prefix(value: &DataItem) -> Line {
             }
         }
         DataItem::Array { data, .. } => {
-            match data.get(0) {
+            match data.first() {
                 Some(DataItem::Integer { value: length, .. }) => {
                     if let Some(DataItem::ByteString(ByteString { data: prefix, .. })) = data.get(1)
                     {
@@ -1139,6 +1615,7 @@ fn typed_array<const LEN: usize>(
 ) -> Vec<Line> {
     if let DataItem::ByteString(ByteString { data, bitwidth }) = value {
         if data.len() % LEN == 0 {
+            context.references.record_string(value);
             let mut line = length_to_hex(Some(data.len()), Some(*bitwidth), 2, "bytes");
             // TODO: Use slice::array_chunks when stable
             line.sublines.extend(
@@ -1165,18 +1642,52 @@ fn typed_array<const LEN: usize>(
     }
 }
 
-fn float_to_hex(value: f64, mut bitwidth: FloatWidth) -> Line {
-    if bitwidth == FloatWidth::Unknown {
-        bitwidth = FloatWidth::SixtyFour;
+fn multi_dimensional_array(value: &DataItem, column_major: bool) -> Line {
+    let array = match MultiDimensionalArray::extract(value, column_major) {
+        Ok(array) => array,
+        Err(err) => return Line::new("", err),
+    };
+
+    let dimensions = array
+        .dimensions
+        .iter()
+        .map(|dimension| dimension.separated_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut line = Line::new("", format!("dimensions({dimensions})"));
+
+    // Show one line per row of the outermost dimension
+    line.sublines = match array.nested() {
+        DataItem::Array { data, .. } if array.dimensions.len() > 1 => data
+            .iter()
+            .map(|row| Line::new("", row.to_diag()))
+            .collect(),
+        nested => vec![Line::new("", nested.to_diag())],
+    };
+
+    line
+}
+
+fn homogeneous_array(value: &DataItem) -> Vec<Line> {
+    match value {
+        DataItem::Array { data, .. } if is_homogeneous(data) => vec![],
+        DataItem::Array { .. } => vec![Line::new(
+            "",
+            "warning: elements are not all of the same type",
+        )],
+        _ => vec![Line::new("", "invalid type for homogeneous array")],
     }
+}
 
-    let hex = match bitwidth {
-        FloatWidth::Unknown => unreachable!(),
-        FloatWidth::Sixteen => format!("f9 {:04x}", f16::from_f64(value).to_bits()),
-        FloatWidth::ThirtyTwo => format!("fa {:08x}", (value as f32).to_bits()),
-        FloatWidth::SixtyFour => format!("fb {:016x}", value.to_bits()),
+fn float_to_hex(float: Float) -> Line {
+    let bits = float.encoded_bits();
+    let hex = match float.bitwidth {
+        FloatWidth::Sixteen => format!("f9 {bits:04x}"),
+        FloatWidth::ThirtyTwo => format!("fa {bits:08x}"),
+        FloatWidth::SixtyFour | FloatWidth::Unknown => format!("fb {bits:016x}"),
     };
 
+    let value = float.as_f64();
     let comment = format!(
         "float({})",
         if value.is_nan() {
@@ -1220,10 +1731,29 @@ fn simple_to_hex(simple: Simple) -> Line {
 
 impl DataItem {
     pub fn to_hex(&self) -> String {
+        self.to_hex_with(&HexOptions::default())
+    }
+
+    /// Like [`DataItem::to_hex`], but with additional control over the
+    /// annotations that are output.
+    pub fn to_hex_with(&self, options: &HexOptions) -> String {
         let mut context = Context {
             encoding: None,
-            reference_count: 0,
+            byte_strings: options.byte_strings,
+            references: References::default(),
+            profile: options.profile,
+            embedded_cbor: options.embedded_cbor,
         };
         Line::from_value(&mut context, self).merge()
     }
 }
+
+/// Encodes a sequence of data items (RFC 8742) as annotated hex, with a blank
+/// line between the items.
+pub fn to_hex_seq(items: &[DataItem], options: &HexOptions) -> String {
+    items
+        .iter()
+        .map(|item| item.to_hex_with(options))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
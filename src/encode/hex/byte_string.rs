@@ -0,0 +1,114 @@
+//! Annotations for the contents of byte strings without an encoding hint, in
+//! the styles other than [`ByteStringStyle::Ascii`].
+//!
+//! The contents are always annotated as diagnostic notation byte string
+//! literals, so that they can be copied into diagnostic notation.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use uuid::{Uuid, Variant};
+
+use super::{ByteStringStyle, Line};
+
+fn is_printable(data: &[u8]) -> Option<&str> {
+    std::str::from_utf8(data)
+        .ok()
+        .filter(|text| !text.chars().any(char::is_control))
+}
+
+fn quoted(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len() + 2);
+    literal.push('\'');
+    for c in text.chars() {
+        if c == '\'' || c == '\\' {
+            literal.push('\\');
+        }
+        literal.push(c);
+    }
+    literal.push('\'');
+    literal
+}
+
+fn text_lines(text: &str) -> Vec<Line> {
+    let line = |text: &str| {
+        Line::new(
+            data_encoding::HEXLOWER.encode(text.as_bytes()),
+            quoted(text),
+        )
+    };
+
+    if text.len() <= 24 {
+        return vec![line(text)];
+    }
+
+    let mut lines = Vec::new();
+    let mut text = text;
+    while !text.is_empty() {
+        let mut split = 16.min(text.len());
+        while !text.is_char_boundary(split) {
+            split -= 1;
+        }
+        let (chunk, rest) = text.split_at(split);
+        lines.push(line(chunk));
+        text = rest;
+    }
+    lines
+}
+
+/// A hint at what a binary byte string might contain, based on its length.
+fn shape(data: &[u8]) -> Option<String> {
+    Some(match data.len() {
+        4 => format!(
+            "ipv4 address({})",
+            Ipv4Addr::from(<[u8; 4]>::try_from(data).unwrap())
+        ),
+        16 => {
+            let uuid = Uuid::from_slice(data).unwrap();
+            if uuid.get_variant() == Variant::RFC4122 && uuid.get_version().is_some() {
+                format!("uuid({uuid})")
+            } else {
+                format!(
+                    "ipv6 address({})",
+                    Ipv6Addr::from(<[u8; 16]>::try_from(data).unwrap())
+                )
+            }
+        }
+        32 => "32 byte digest, e.g. sha-256".to_owned(),
+        64 => "64 byte digest, e.g. sha-512".to_owned(),
+        _ => return None,
+    })
+}
+
+pub(super) fn contents(style: ByteStringStyle, data: &[u8]) -> Vec<Line> {
+    let (prefix, encode): (_, fn(&[u8]) -> String) = match style {
+        ByteStringStyle::Ascii => unreachable!(),
+        ByteStringStyle::Detect => {
+            if let Some(text) = is_printable(data) {
+                return text_lines(text);
+            }
+            ("h", |datum| data_encoding::HEXLOWER.encode(datum))
+        }
+        ByteStringStyle::Base64 => ("b64", |datum| data_encoding::BASE64URL_NOPAD.encode(datum)),
+        ByteStringStyle::Base32 => ("b32", |datum| data_encoding::BASE32.encode(datum)),
+    };
+
+    if data.is_empty() {
+        return vec![Line::new("", format!("{prefix}''"))];
+    }
+
+    let mut lines: Vec<Line> = data
+        .chunks(16)
+        .map(|datum| {
+            Line::new(
+                data_encoding::HEXLOWER.encode(datum),
+                format!("{prefix}'{}'", encode(datum)),
+            )
+        })
+        .collect();
+
+    if style == ByteStringStyle::Detect {
+        lines.extend(shape(data).map(|shape| Line::new("", format!("hint: {shape}"))));
+    }
+
+    lines
+}
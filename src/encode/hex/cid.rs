@@ -0,0 +1,115 @@
+//! Annotations for IPLD content identifiers, as defined by the [CID spec][]
+//! and embedded in CBOR by the [CID tag spec][].
+//!
+//! [CID spec]: https://github.com/multiformats/cid
+//! [CID tag spec]: https://github.com/ipld/cid-cbor/
+
+use super::Line;
+use crate::{ByteString, DataItem};
+
+/// The length of a CIDv0, a bare sha2-256 multihash.
+const CIDV0_LENGTH: usize = 34;
+
+fn codec_name(codec: u64) -> Option<&'static str> {
+    Some(match codec {
+        0x51 => "cbor",
+        0x55 => "raw",
+        0x70 => "dag-pb",
+        0x71 => "dag-cbor",
+        0x72 => "libp2p-key",
+        0x78 => "git-raw",
+        0x0129 => "dag-json",
+        0x0200 => "json",
+        _ => return None,
+    })
+}
+
+fn hash_name(hash: u64) -> Option<&'static str> {
+    Some(match hash {
+        0x00 => "identity",
+        0x11 => "sha1",
+        0x12 => "sha2-256",
+        0x13 => "sha2-512",
+        0x14 => "sha3-512",
+        0x15 => "sha3-384",
+        0x16 => "sha3-256",
+        0x17 => "sha3-224",
+        0x1b => "keccak-256",
+        0x1e => "blake3",
+        0xb220 => "blake2b-256",
+        0xb240 => "blake2b-512",
+        0xb260 => "blake2s-256",
+        _ => return None,
+    })
+}
+
+fn name(name: Option<&str>, code: u64) -> String {
+    name.map(String::from)
+        .unwrap_or_else(|| format!("{code:#x}"))
+}
+
+/// Reads an unsigned varint as used throughout multiformats, returning it
+/// along with the remaining bytes.
+fn varint(data: &[u8]) -> Result<(u64, &[u8]), String> {
+    let mut value = 0;
+    // Multiformats varints are limited to 63 bits, so 9 bytes
+    for (i, &byte) in data.iter().enumerate().take(9) {
+        value |= u64::from(byte & 0x7f) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((value, &data[i + 1..]));
+        }
+    }
+    Err("invalid cid, truncated varint".into())
+}
+
+pub(super) fn cid(value: &DataItem) -> Line {
+    let data = match value {
+        DataItem::ByteString(ByteString { data, .. }) => data,
+        _ => return Line::new("", "invalid type for cid"),
+    };
+    let data = match data.split_first() {
+        Some((0, data)) => data,
+        _ => return Line::new("", "invalid cid, missing multibase identity prefix"),
+    };
+
+    if data.len() == CIDV0_LENGTH && data.starts_with(&[0x12, 0x20]) {
+        let mut line = Line::new("", "cid(version(0), codec(dag-pb), hash(sha2-256))");
+        line.sublines.push(Line::new(
+            "",
+            format!("base58({})", bs58::encode(data).into_string()),
+        ));
+        return line;
+    }
+
+    (|| {
+        let (version, rest) = varint(data)?;
+        if version != 1 {
+            return Err(format!("invalid cid, unsupported version {version}"));
+        }
+        let (codec, rest) = varint(rest)?;
+        let (hash, rest) = varint(rest)?;
+        let (length, digest) = varint(rest)?;
+        if length != digest.len() as u64 {
+            return Err(format!(
+                "invalid cid, digest length {length} does not match remaining {} bytes",
+                digest.len()
+            ));
+        }
+
+        let mut line = Line::new(
+            "",
+            format!(
+                "cid(version(1), codec({}), hash({}))",
+                name(codec_name(codec), codec),
+                name(hash_name(hash), hash),
+            ),
+        );
+        let base32 = data_encoding::BASE32_NOPAD
+            .encode(data)
+            .to_ascii_lowercase();
+        line.sublines
+            .push(Line::new("", format!("base32(b{base32})")));
+        Ok(line)
+    })()
+    .unwrap_or_else(|err| Line::new("", err))
+}
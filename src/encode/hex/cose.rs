@@ -0,0 +1,292 @@
+//! Annotations for [COSE_Key][] and COSE_KeySet structures, see [RFC 9052 §
+//! 7][RFC 9052 7] and [RFC 9053][]. Names are taken from the [COSE IANA
+//! Registry][IANA].
+//!
+//! [COSE_Key]: https://www.rfc-editor.org/rfc/rfc9052#section-7
+//! [RFC 9052 7]: https://www.rfc-editor.org/rfc/rfc9052#section-7
+//! [RFC 9053]: https://www.rfc-editor.org/rfc/rfc9053
+//! [IANA]: https://www.iana.org/assignments/cose/cose.xhtml
+
+use super::Line;
+use crate::{DataItem, TextString};
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum KeyType {
+    Okp,
+    Ec2,
+    Rsa,
+    Symmetric,
+    HssLms,
+    WalnutDsa,
+    Other,
+}
+
+/// The value of a label in a COSE map, either an integer or a text string.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum Label<'a> {
+    Int(i128),
+    Text(&'a str),
+}
+
+impl<'a> Label<'a> {
+    fn of(item: &'a DataItem) -> Option<Label<'a>> {
+        match *item {
+            DataItem::Integer { value, .. } => Some(Label::Int(i128::from(value))),
+            DataItem::Negative { value, .. } => Some(Label::Int(-1 - i128::from(value))),
+            DataItem::TextString(TextString { ref data, .. }) => Some(Label::Text(data)),
+            _ => None,
+        }
+    }
+}
+
+/// Whether an entry can only be a key parameter, and not a header parameter
+/// sharing its label.
+fn is_key_only(key: &DataItem, value: &DataItem) -> bool {
+    matches!(
+        (Label::of(key), value),
+        (Some(Label::Int(..=-1)), _)
+            | (Some(Label::Int(2)), DataItem::ByteString(_))
+            | (Some(Label::Int(4)), DataItem::Array { .. })
+    )
+}
+
+fn key_type(values: &[(DataItem, DataItem)]) -> Option<KeyType> {
+    let (_, kty) = values
+        .iter()
+        .find(|(key, _)| Label::of(key) == Some(Label::Int(1)))?;
+    // Header maps also have label 1, holding an algorithm, so some entry that
+    // only a key has is needed too: key parameters have negative labels, and
+    // the key's kid and key_ops labels are a header's crit and kid labels
+    // with different types
+    if !values.iter().any(|(key, value)| is_key_only(key, value)) {
+        return None;
+    }
+    Some(match Label::of(kty)? {
+        Label::Int(1) => KeyType::Okp,
+        Label::Int(2) => KeyType::Ec2,
+        Label::Int(3) => KeyType::Rsa,
+        Label::Int(4) => KeyType::Symmetric,
+        Label::Int(5) => KeyType::HssLms,
+        Label::Int(6) => KeyType::WalnutDsa,
+        // Algorithms are often negative, so only key types that could be
+        // registered are accepted
+        Label::Int(0..) | Label::Text(_) => KeyType::Other,
+        Label::Int(_) => return None,
+    })
+}
+
+fn key_type_name(value: i128) -> Option<&'static str> {
+    Some(match value {
+        1 => "OKP",
+        2 => "EC2",
+        3 => "RSA",
+        4 => "Symmetric",
+        5 => "HSS-LMS",
+        6 => "WalnutDSA",
+        _ => return None,
+    })
+}
+
+fn curve_name(value: i128) -> Option<&'static str> {
+    Some(match value {
+        1 => "P-256",
+        2 => "P-384",
+        3 => "P-521",
+        4 => "X25519",
+        5 => "X448",
+        6 => "Ed25519",
+        7 => "Ed448",
+        8 => "secp256k1",
+        256 => "brainpoolP256r1",
+        257 => "brainpoolP320r1",
+        258 => "brainpoolP384r1",
+        259 => "brainpoolP512r1",
+        _ => return None,
+    })
+}
+
+fn algorithm_name(value: i128) -> Option<&'static str> {
+    Some(match value {
+        -259 => "RS512",
+        -258 => "RS384",
+        -257 => "RS256",
+        -47 => "ES256K",
+        -39 => "PS512",
+        -38 => "PS384",
+        -37 => "PS256",
+        -36 => "ES512",
+        -35 => "ES384",
+        -34 => "ECDH-SS + A256KW",
+        -33 => "ECDH-SS + A192KW",
+        -32 => "ECDH-SS + A128KW",
+        -31 => "ECDH-ES + A256KW",
+        -30 => "ECDH-ES + A192KW",
+        -29 => "ECDH-ES + A128KW",
+        -28 => "ECDH-SS + HKDF-512",
+        -27 => "ECDH-SS + HKDF-256",
+        -26 => "ECDH-ES + HKDF-512",
+        -25 => "ECDH-ES + HKDF-256",
+        -13 => "direct+HKDF-AES-256",
+        -12 => "direct+HKDF-AES-128",
+        -11 => "direct+HKDF-SHA-512",
+        -10 => "direct+HKDF-SHA-256",
+        -8 => "EdDSA",
+        -7 => "ES256",
+        -6 => "direct",
+        -5 => "A256KW",
+        -4 => "A192KW",
+        -3 => "A128KW",
+        1 => "A128GCM",
+        2 => "A192GCM",
+        3 => "A256GCM",
+        4 => "HMAC 256/64",
+        5 => "HMAC 256/256",
+        6 => "HMAC 384/384",
+        7 => "HMAC 512/512",
+        10 => "AES-CCM-16-64-128",
+        11 => "AES-CCM-16-64-256",
+        12 => "AES-CCM-64-64-128",
+        13 => "AES-CCM-64-64-256",
+        14 => "AES-MAC 128/64",
+        15 => "AES-MAC 256/64",
+        24 => "ChaCha20/Poly1305",
+        25 => "AES-MAC 128/128",
+        26 => "AES-MAC 256/128",
+        30 => "AES-CCM-16-128-128",
+        31 => "AES-CCM-16-128-256",
+        32 => "AES-CCM-64-128-128",
+        33 => "AES-CCM-64-128-256",
+        _ => return None,
+    })
+}
+
+fn key_op_name(value: i128) -> Option<&'static str> {
+    Some(match value {
+        1 => "sign",
+        2 => "verify",
+        3 => "encrypt",
+        4 => "decrypt",
+        5 => "wrap key",
+        6 => "unwrap key",
+        7 => "derive key",
+        8 => "derive bits",
+        9 => "MAC create",
+        10 => "MAC verify",
+        _ => return None,
+    })
+}
+
+/// Returns the name of the parameter along with whether it contains private
+/// key material.
+fn parameter(kty: KeyType, label: i128) -> Option<(&'static str, bool)> {
+    Some(match (kty, label) {
+        (_, 1) => ("kty", false),
+        (_, 2) => ("kid", false),
+        (_, 3) => ("alg", false),
+        (_, 4) => ("key_ops", false),
+        (_, 5) => ("Base IV", false),
+        (KeyType::Okp, -1) | (KeyType::Ec2, -1) => ("crv", false),
+        (KeyType::Okp, -2) | (KeyType::Ec2, -2) => ("x", false),
+        (KeyType::Ec2, -3) => ("y", false),
+        (KeyType::Okp, -4) | (KeyType::Ec2, -4) => ("d", true),
+        (KeyType::Rsa, -1) => ("n", false),
+        (KeyType::Rsa, -2) => ("e", false),
+        (KeyType::Rsa, -3) => ("d", true),
+        (KeyType::Rsa, -4) => ("p", true),
+        (KeyType::Rsa, -5) => ("q", true),
+        (KeyType::Rsa, -6) => ("dP", true),
+        (KeyType::Rsa, -7) => ("dQ", true),
+        (KeyType::Rsa, -8) => ("qInv", true),
+        (KeyType::Rsa, -9) => ("other", true),
+        (KeyType::Rsa, -10) => ("r_i", true),
+        (KeyType::Rsa, -11) => ("d_i", true),
+        (KeyType::Rsa, -12) => ("t_i", true),
+        (KeyType::Symmetric, -1) => ("k", true),
+        (KeyType::HssLms, -1) => ("pub", false),
+        (KeyType::WalnutDsa, -1) => ("N", false),
+        (KeyType::WalnutDsa, -2) => ("q", false),
+        (KeyType::WalnutDsa, -3) => ("t-values", false),
+        (KeyType::WalnutDsa, -4) => ("matrix 1", false),
+        (KeyType::WalnutDsa, -5) => ("permutation 1", false),
+        (KeyType::WalnutDsa, -6) => ("matrix 2", false),
+        _ => return None,
+    })
+}
+
+fn describe(line: &mut Line, description: &str) {
+    line.comment = format!("{description}, {}", line.comment);
+}
+
+fn describe_value(line: &mut Line, value: &DataItem, name: fn(i128) -> Option<&'static str>) {
+    if let Some(description) = Label::of(value).and_then(|label| match label {
+        Label::Int(value) => name(value),
+        Label::Text(_) => None,
+    }) {
+        describe(line, description);
+    }
+}
+
+/// Annotates the entries of `line`, which must be the output of `map_to_hex`
+/// for `values`, if the map looks like a COSE_Key (i.e. contains a `kty`
+/// parameter).
+pub(super) fn annotate_key(values: &[(DataItem, DataItem)], line: &mut Line) {
+    let kty = match key_type(values) {
+        Some(kty) => kty,
+        None => return,
+    };
+
+    describe(line, "COSE_Key");
+
+    for ((key, value), lines) in values.iter().zip(line.sublines.chunks_exact_mut(2)) {
+        let (key_line, value_line) = lines.split_at_mut(1);
+        let (key_line, value_line) = (&mut key_line[0], &mut value_line[0]);
+
+        let label = match Label::of(key) {
+            Some(Label::Int(label)) => label,
+            _ => continue,
+        };
+
+        let (name, private) = match parameter(kty, label) {
+            Some(parameter) => parameter,
+            None => continue,
+        };
+
+        describe(key_line, name);
+
+        match (kty, label) {
+            (_, 1) => describe_value(value_line, value, key_type_name),
+            (_, 3) => describe_value(value_line, value, algorithm_name),
+            (_, 4) => {
+                if let DataItem::Array { data, .. } = value {
+                    for (op, op_line) in data.iter().zip(&mut value_line.sublines) {
+                        describe_value(op_line, op, key_op_name);
+                    }
+                }
+            }
+            (KeyType::Okp, -1) | (KeyType::Ec2, -1) => {
+                describe_value(value_line, value, curve_name)
+            }
+            _ => {}
+        }
+
+        if private {
+            value_line
+                .sublines
+                .push(Line::new("", "warning: private key material"));
+        }
+    }
+}
+
+/// Annotates `line`, which must be the output of `array_to_hex` for `values`,
+/// as a COSE_KeySet if every element of the array is a COSE_Key.
+pub(super) fn annotate_key_set(values: &[DataItem], line: &mut Line) {
+    let is_key_set = !values.is_empty()
+        && values.iter().all(|value| match value {
+            DataItem::Map { data, .. } => key_type(data).is_some(),
+            _ => false,
+        });
+
+    if is_key_set {
+        describe(line, "COSE_KeySet");
+    }
+}
@@ -0,0 +1,74 @@
+//! Annotations for the geographic coordinate tags,
+//! [`Tag::GEOGRAPHIC_COORDINATES`] and
+//! [`Tag::GEOGRAPHIC_COORDINATE_REFERENCE_SYSTEM`].
+
+use super::Line;
+use crate::{DataItem, Tag};
+
+fn number(value: &DataItem) -> Option<f64> {
+    match *value {
+        DataItem::Integer { value, .. } => Some(value as f64),
+        DataItem::Negative { value, .. } => Some(-1.0 - value as f64),
+        DataItem::Float(value) => Some(value.as_f64()),
+        _ => None,
+    }
+}
+
+pub(super) fn geographic_coordinates(value: &DataItem) -> Line {
+    let data = match value {
+        DataItem::Array { data, .. } if data.len() == 2 || data.len() == 3 => data,
+        _ => return Line::new("", "invalid type for geographic coordinates"),
+    };
+    let Some(values) = data.iter().map(number).collect::<Option<Vec<f64>>>() else {
+        return Line::new("", "invalid type for geographic coordinates");
+    };
+
+    let (latitude, longitude) = (values[0], values[1]);
+    if !(-90.0..=90.0).contains(&latitude) {
+        return Line::new(
+            "",
+            format!("invalid geographic coordinates, latitude {latitude} out of range"),
+        );
+    }
+    if !(-180.0..=180.0).contains(&longitude) {
+        return Line::new(
+            "",
+            format!("invalid geographic coordinates, longitude {longitude} out of range"),
+        );
+    }
+
+    let mut description = format!("latitude({latitude}), longitude({longitude})");
+    if let Some(altitude) = values.get(2) {
+        description += &format!(", altitude({altitude})");
+    }
+    Line::new("", description)
+}
+
+pub(super) fn coordinate_reference_system(value: &DataItem) -> Vec<Line> {
+    let data = match value {
+        DataItem::Array { data, .. } if data.len() == 2 => data,
+        _ => {
+            return vec![Line::new(
+                "",
+                "invalid type for geographic coordinate reference system",
+            )]
+        }
+    };
+
+    let crs = match data[0] {
+        DataItem::Integer { value, .. } => Line::new("", format!("crs(EPSG:{value})")),
+        DataItem::TextString(_) | DataItem::IndefiniteTextString(_) => {
+            Line::new("", "crs(well-known text)")
+        }
+        _ => Line::new("", "invalid type for coordinate reference system"),
+    };
+
+    // Coordinates tagged with their own tag are annotated by it
+    match data[1] {
+        DataItem::Tag {
+            tag: Tag::GEOGRAPHIC_COORDINATES,
+            ..
+        } => vec![crs],
+        ref coordinates => vec![crs, geographic_coordinates(coordinates)],
+    }
+}
@@ -0,0 +1,215 @@
+//! A lenient decoder for annotating malformed encoded CBOR, it annotates as
+//! much of the data as possible before stopping at the first failure.
+//!
+//! Every well-formed item is annotated in full, for other items only their
+//! heads are annotated while descending into them to find the failure.
+
+use super::{bytes_to_hex, Context, Line};
+use crate::parse_bytes_partial;
+
+/// The result of leniently decoding some data.
+pub(super) struct Decoded {
+    /// The annotations of the data before the failure.
+    pub(super) lines: Vec<Line>,
+    /// The reason decoding failed, including its byte offset.
+    pub(super) error: String,
+}
+
+struct Decoder<'a, 'c> {
+    context: &'c mut Context,
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a, 'c> Decoder<'a, 'c> {
+    fn error<T>(&self, message: &str, offset: usize) -> Result<T, String> {
+        Err(format!("{message} at byte {offset}"))
+    }
+
+    fn peek(&self) -> Result<u8, String> {
+        match self.data.get(self.offset) {
+            Some(&byte) => Ok(byte),
+            None => self.error("unexpected end of data", self.data.len()),
+        }
+    }
+
+    fn take(&mut self, len: u64) -> Result<&'a [u8], String> {
+        match usize::try_from(len)
+            .ok()
+            .and_then(|len| self.data.get(self.offset..)?.get(..len))
+        {
+            Some(taken) => {
+                self.offset += taken.len();
+                Ok(taken)
+            }
+            None => self.error("unexpected end of data", self.data.len()),
+        }
+    }
+
+    /// Decodes the head of an item, returning its major type, argument (if
+    /// not indefinite) and hex.
+    fn head(&mut self) -> Result<(u8, Option<u64>, String), String> {
+        let start = self.offset;
+        let initial = self.peek()?;
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        let argument = match info {
+            0..=23 => {
+                self.offset += 1;
+                Some(u64::from(info))
+            }
+            24..=27 => {
+                let len = 1 << (info - 24);
+                let argument = match self.data.get(start + 1..start + 1 + len) {
+                    Some(argument) => argument,
+                    None => return self.error("unexpected end of data", self.data.len()),
+                };
+                self.offset += 1 + len;
+                Some(argument.iter().fold(0, |acc, &b| acc << 8 | u64::from(b)))
+            }
+            28..=30 => {
+                return self.error(&format!("reserved additional information {info}"), start)
+            }
+            _ => match major {
+                2..=5 => {
+                    self.offset += 1;
+                    None
+                }
+                7 => return self.error("unexpected break", start),
+                _ => return self.error("invalid indefinite length", start),
+            },
+        };
+        let hex = match self.data[start + 1..self.offset] {
+            [] => format!("{initial:02x}"),
+            ref argument => format!("{initial:02x} {}", data_encoding::HEXLOWER.encode(argument)),
+        };
+        Ok((major, argument, hex))
+    }
+
+    fn is_break(&mut self) -> Result<bool, String> {
+        if self.peek()? == 0xff {
+            self.offset += 1;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Decodes the items within an array, map or tag, using `count` items if
+    /// definite or up to a break if not.
+    fn items(&mut self, lines: &mut Vec<Line>, count: Option<u64>) -> Result<(), String> {
+        match count {
+            Some(count) => (0..count).try_for_each(|_| self.item(lines)),
+            None => loop {
+                if self.is_break()? {
+                    lines.push(Line::new("ff", "break"));
+                    return Ok(());
+                }
+                self.item(lines)?;
+            },
+        }
+    }
+
+    /// Decodes the chunks of an indefinite length string, each of which must
+    /// be a definite length string of the same major type.
+    fn chunks(&mut self, lines: &mut Vec<Line>, major: u8) -> Result<(), String> {
+        loop {
+            if self.is_break()? {
+                lines.push(Line::new("ff", "break"));
+                return Ok(());
+            }
+            let initial = self.peek()?;
+            if initial >> 5 != major || initial & 0x1f == 0x1f {
+                return self.error("invalid indefinite length string chunk", self.offset);
+            }
+            self.item(lines)?;
+        }
+    }
+
+    fn item(&mut self, lines: &mut Vec<Line>) -> Result<(), String> {
+        if let Ok(Some((value, len))) = parse_bytes_partial(&self.data[self.offset..]) {
+            lines.push(Line::from_value(self.context, &value));
+            self.offset += len;
+            return Ok(());
+        }
+
+        let (major, argument, hex) = self.head()?;
+        let length = argument.map_or("*".to_owned(), |argument| argument.to_string());
+        let (kind, count) = match major {
+            2 => ("bytes", None),
+            3 => ("text", None),
+            4 => ("array", argument),
+            5 => ("map", argument.map(|argument| argument.saturating_mul(2))),
+            6 => ("tag", Some(1)),
+            // Other items only fail in their head, which has already failed
+            _ => unreachable!(),
+        };
+
+        let mut line = Line::new(hex, format!("{kind}({length})"));
+        let result = match (major, argument) {
+            (2 | 3, Some(len)) => {
+                let start = self.offset;
+                self.take(len).and_then(|data| {
+                    line.sublines.extend(bytes_to_hex(None, data));
+                    match std::str::from_utf8(data) {
+                        Err(err) if major == 3 => {
+                            self.error("invalid utf-8", start + err.valid_up_to())
+                        }
+                        _ => Ok(()),
+                    }
+                })
+            }
+            (2 | 3, None) => self.chunks(&mut line.sublines, major),
+            _ => self.items(&mut line.sublines, count),
+        };
+        lines.push(line);
+        result
+    }
+}
+
+/// Leniently decodes `data` from `offset`, as a single item if `sequence` is
+/// false, otherwise as items up to the end of the data. Returns the
+/// annotations if the data is well-formed, or those up to the failure along
+/// with the reason otherwise.
+pub(super) fn decode(
+    context: &mut Context,
+    data: &[u8],
+    offset: usize,
+    sequence: bool,
+) -> Result<Vec<Line>, Decoded> {
+    let mut decoder = Decoder {
+        context,
+        data,
+        offset,
+    };
+    let mut lines = Vec::new();
+
+    let result = if sequence {
+        (|| {
+            while decoder.offset < data.len() {
+                decoder.item(&mut lines)?;
+            }
+            Ok(())
+        })()
+    } else {
+        decoder.item(&mut lines).and_then(|()| {
+            if decoder.offset < data.len() {
+                decoder.error("unexpected trailing bytes", decoder.offset)
+            } else {
+                Ok(())
+            }
+        })
+    };
+
+    match result {
+        Ok(()) => Ok(lines),
+        Err(error) => {
+            lines.extend(
+                bytes_to_hex(None, &data[decoder.offset..]).map(|line| Line {
+                    comment: format!("unparsed {}", line.comment),
+                    ..line
+                }),
+            );
+            Err(Decoded { lines, error })
+        }
+    }
+}
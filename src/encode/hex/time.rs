@@ -0,0 +1,292 @@
+//! Annotations for the extended time, duration and period tags defined in
+//! [RFC 9581][].
+//!
+//! [RFC 9581]: https://www.rfc-editor.org/rfc/rfc9581
+
+use chrono::NaiveDateTime;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{pow::pow, Signed, ToPrimitive, Zero};
+
+use super::{decimal_expansion, extract_fraction, Line};
+use crate::{DataItem, Simple, Tag};
+
+/// A point in time or a duration, in seconds, as extracted from an RFC 9581
+/// map.
+struct Time {
+    seconds: BigRational,
+    /// The number of decimal digits of precision given by a fractional
+    /// seconds key, if any.
+    precision: Option<usize>,
+    timescale: Option<u64>,
+}
+
+fn integer(value: &DataItem) -> Option<BigInt> {
+    match *value {
+        DataItem::Integer { value, .. } => Some(BigInt::from(value)),
+        DataItem::Negative { value, .. } => Some(BigInt::from(-1) - BigInt::from(value)),
+        _ => None,
+    }
+}
+
+fn extract_time(value: &DataItem, name: &str) -> Result<Time, String> {
+    let data = match value {
+        DataItem::Map { data, .. } => data,
+        _ => return Err(format!("invalid type for {name}")),
+    };
+
+    let mut base: Option<(BigRational, bool)> = None;
+    let mut fraction: Option<(BigInt, usize)> = None;
+    let mut timescale = None;
+
+    for (key, value) in data {
+        let key = match integer(key).and_then(|key| key.to_i64()) {
+            Some(key) => key,
+            None => return Err(format!("invalid key type for {name}")),
+        };
+
+        let seconds = match (key, value) {
+            (1, DataItem::Float(value)) => Some((
+                BigRational::from_float(value.as_f64())
+                    .ok_or_else(|| format!("invalid {name}, non-finite base value"))?,
+                false,
+            )),
+            (1, value) => Some((
+                BigRational::from_integer(
+                    integer(value).ok_or_else(|| format!("invalid type for {name} base value"))?,
+                ),
+                true,
+            )),
+            (4, value) => Some((
+                extract_fraction(value, 10).map_err(|err| format!("{err} for {name}"))?,
+                false,
+            )),
+            (5, value) => Some((
+                extract_fraction(value, 2).map_err(|err| format!("{err} for {name}"))?,
+                false,
+            )),
+            (-3 | -6 | -9 | -12 | -15 | -18, value) => {
+                let digits = key.unsigned_abs() as usize;
+                let value = match value {
+                    DataItem::Integer { value, .. } => BigInt::from(*value),
+                    _ => return Err(format!("invalid type for {name} fraction")),
+                };
+                if value >= pow(BigInt::from(10), digits) {
+                    return Err(format!("invalid {name}, fraction out of range"));
+                }
+                if fraction.replace((value, digits)).is_some() {
+                    return Err(format!("invalid {name}, multiple fractions"));
+                }
+                None
+            }
+            (-10, DataItem::Integer { value, .. }) => {
+                timescale = Some(*value);
+                None
+            }
+            (-10, _) => return Err(format!("invalid type for {name} timescale")),
+            (key, _) if key < 0 => {
+                return Err(format!("unsupported critical key({key}) for {name}"));
+            }
+            _ => None,
+        };
+
+        if let Some(seconds) = seconds {
+            if base.replace(seconds).is_some() {
+                return Err(format!("invalid {name}, multiple base values"));
+            }
+        }
+    }
+
+    let (mut seconds, integral) =
+        base.ok_or_else(|| format!("invalid {name}, missing base value"))?;
+
+    let precision = if let Some((value, digits)) = fraction {
+        if !integral {
+            return Err(format!(
+                "invalid {name}, fraction requires an integer base value"
+            ));
+        }
+        seconds += BigRational::new(value, pow(BigInt::from(10), digits));
+        Some(digits)
+    } else {
+        None
+    };
+
+    Ok(Time {
+        seconds,
+        precision,
+        timescale,
+    })
+}
+
+/// Formats the fractional part of a non-negative value, including the leading
+/// `.`, or nothing if there is no fractional part and no explicit precision.
+fn format_fraction(fract: &BigRational, precision: Option<usize>) -> String {
+    match precision {
+        Some(precision) => {
+            let digits =
+                (fract * BigRational::from_integer(pow(BigInt::from(10), precision))).to_integer();
+            format!(".{digits:0>precision$}")
+        }
+        None if fract.is_zero() => String::new(),
+        None => {
+            let (decimal, _) = decimal_expansion(fract, 30);
+            decimal.trim_start_matches('0').to_owned()
+        }
+    }
+}
+
+fn format_instant(time: &Time) -> Option<String> {
+    let whole = time.seconds.floor();
+    let fract = &time.seconds - &whole;
+    let date = NaiveDateTime::from_timestamp_opt(whole.to_integer().to_i64()?, 0)?;
+    let fraction = format_fraction(&fract, time.precision);
+    let suffix = match time.timescale {
+        None | Some(0) => "Z",
+        Some(_) => "",
+    };
+    Some(format!("{}{fraction}{suffix}", date.format("%FT%T")))
+}
+
+fn format_duration(time: &Time) -> String {
+    let sign = if time.seconds.is_negative() { "-" } else { "" };
+    let seconds = time.seconds.abs();
+    let whole = seconds.trunc();
+    let fraction = format_fraction(&(&seconds - &whole), time.precision);
+    format!("{sign}{}{fraction}s", whole.to_integer())
+}
+
+fn timescale_line(timescale: u64) -> Line {
+    match timescale {
+        0 => Line::new("", "timescale(UTC)"),
+        1 => Line::new("", "timescale(TAI)"),
+        _ => Line::new("", format!("timescale({timescale}), unknown")),
+    }
+}
+
+fn instant_lines(time: &Time) -> Vec<Line> {
+    let mut lines = vec![match format_instant(time) {
+        Some(instant) => Line::new("", format!("datetime({instant})")),
+        None => Line::new("", "offset is too large"),
+    }];
+    if let Some(timescale) = time.timescale {
+        lines.push(timescale_line(timescale));
+    }
+    lines
+}
+
+pub(super) fn extended_time(value: &DataItem) -> Vec<Line> {
+    match extract_time(value, "extended time") {
+        Ok(time) => instant_lines(&time),
+        Err(err) => vec![Line::new("", err)],
+    }
+}
+
+pub(super) fn duration(value: &DataItem) -> Vec<Line> {
+    match extract_time(value, "duration") {
+        Ok(time) => vec![Line::new(
+            "",
+            format!("duration({})", format_duration(&time)),
+        )],
+        Err(err) => vec![Line::new("", err)],
+    }
+}
+
+/// Extracts an element of a period, which may be given as either a bare epoch
+/// value or a map, optionally wrapped in the matching tag.
+fn extract_period_element(
+    value: &DataItem,
+    tags: &[Tag],
+    name: &str,
+) -> Result<Option<Time>, String> {
+    match value {
+        DataItem::Simple(Simple::NULL) => Ok(None),
+        DataItem::Tag { tag, value, .. } if tags.contains(tag) => {
+            extract_period_element(value, &[], name)
+        }
+        DataItem::Map { .. } => extract_time(value, name).map(Some),
+        DataItem::Integer { .. } | DataItem::Negative { .. } => Ok(Some(Time {
+            seconds: BigRational::from_integer(integer(value).unwrap()),
+            precision: None,
+            timescale: None,
+        })),
+        DataItem::Float(value) => BigRational::from_float(value.as_f64())
+            .map(|seconds| {
+                Some(Time {
+                    seconds,
+                    precision: None,
+                    timescale: None,
+                })
+            })
+            .ok_or_else(|| format!("invalid {name}, non-finite value")),
+        _ => Err(format!("invalid type for {name}")),
+    }
+}
+
+pub(super) fn period(value: &DataItem) -> Vec<Line> {
+    let data = match value {
+        DataItem::Array { data, .. } if data.len() == 2 || data.len() == 3 => data,
+        _ => return vec![Line::new("", "invalid type for period")],
+    };
+
+    let null = DataItem::Simple(Simple::NULL);
+    let elements = (
+        extract_period_element(
+            &data[0],
+            &[Tag::EPOCH_DATETIME, Tag::EXTENDED_TIME],
+            "period start",
+        ),
+        extract_period_element(
+            &data[1],
+            &[Tag::EPOCH_DATETIME, Tag::EXTENDED_TIME],
+            "period end",
+        ),
+        extract_period_element(
+            data.get(2).unwrap_or(&null),
+            &[Tag::DURATION],
+            "period duration",
+        ),
+    );
+
+    let (start, end, duration) = match elements {
+        (Ok(start), Ok(end), Ok(duration)) => (start, end, duration),
+        (Err(err), _, _) | (_, Err(err), _) | (_, _, Err(err)) => {
+            return vec![Line::new("", err)];
+        }
+    };
+
+    let given = [start.is_some(), end.is_some(), duration.is_some()]
+        .iter()
+        .filter(|&&given| given)
+        .count();
+    if given != 2 {
+        return vec![Line::new(
+            "",
+            "invalid period, exactly two of start, end and duration must be given",
+        )];
+    }
+
+    let mut lines = Vec::new();
+    for (name, time) in [("start", &start), ("end", &end)] {
+        if let Some(time) = time {
+            match format_instant(time) {
+                Some(instant) => lines.push(Line::new("", format!("{name}({instant})"))),
+                None => lines.push(Line::new("", format!("{name} offset is too large"))),
+            }
+        }
+    }
+    let duration = match (start, end, duration) {
+        (_, _, Some(duration)) => duration,
+        (Some(start), Some(end), None) => Time {
+            seconds: end.seconds - start.seconds,
+            precision: start.precision.max(end.precision),
+            timescale: None,
+        },
+        _ => unreachable!(),
+    };
+    lines.push(Line::new(
+        "",
+        format!("duration({})", format_duration(&duration)),
+    ));
+    lines
+}
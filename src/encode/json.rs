@@ -0,0 +1,350 @@
+//! A strict [RFC 8259][] JSON parser and printer, used to display JSON text
+//! embedded in byte strings.
+//!
+//! [RFC 8259]: https://tools.ietf.org/html/rfc8259
+
+use std::fmt::Write;
+
+/// Guards against stack overflows from deeply nested input.
+const MAX_DEPTH: usize = 128;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Json {
+    Null,
+    Bool(bool),
+    /// The number exactly as written, as JSON does not limit its precision.
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    offset: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, message: &str) -> Result<T, String> {
+        Err(format!("{message} at byte {}", self.offset))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.offset).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.offset += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), String> {
+        if self.peek() == Some(expected) {
+            self.offset += 1;
+            Ok(())
+        } else {
+            self.error(&format!("expected {:?}", char::from(expected)))
+        }
+    }
+
+    fn literal(&mut self, literal: &str, value: Json) -> Result<Json, String> {
+        if self.input[self.offset..].starts_with(literal) {
+            self.offset += literal.len();
+            Ok(value)
+        } else {
+            self.error("expected value")
+        }
+    }
+
+    fn digits(&mut self) -> Result<(), String> {
+        let start = self.offset;
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.offset += 1;
+        }
+        if self.offset == start {
+            self.error("expected digit")
+        } else {
+            Ok(())
+        }
+    }
+
+    fn number(&mut self) -> Result<Json, String> {
+        let start = self.offset;
+        if self.peek() == Some(b'-') {
+            self.offset += 1;
+        }
+        if self.peek() == Some(b'0') {
+            self.offset += 1;
+        } else {
+            self.digits()?;
+        }
+        if self.peek() == Some(b'.') {
+            self.offset += 1;
+            self.digits()?;
+        }
+        if let Some(b'e' | b'E') = self.peek() {
+            self.offset += 1;
+            if let Some(b'+' | b'-') = self.peek() {
+                self.offset += 1;
+            }
+            self.digits()?;
+        }
+        Ok(Json::Number(self.input[start..self.offset].to_owned()))
+    }
+
+    fn hex4(&mut self) -> Result<u16, String> {
+        match self
+            .input
+            .get(self.offset..self.offset + 4)
+            .filter(|digits| digits.bytes().all(|b| b.is_ascii_hexdigit()))
+        {
+            Some(digits) => {
+                self.offset += 4;
+                Ok(u16::from_str_radix(digits, 16).unwrap())
+            }
+            None => self.error("invalid unicode escape"),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect(b'"')?;
+        let mut string = String::new();
+        loop {
+            let c = match self.input[self.offset..].chars().next() {
+                Some(c) => c,
+                None => return self.error("unterminated string"),
+            };
+            match c {
+                '"' => {
+                    self.offset += 1;
+                    return Ok(string);
+                }
+                '\\' => {
+                    self.offset += 1;
+                    let escape = self.peek();
+                    self.offset += 1;
+                    string.push(match escape {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            let high = self.hex4()?;
+                            let code = if (0xd800..0xdc00).contains(&high) {
+                                if !self.input[self.offset..].starts_with("\\u") {
+                                    return self.error("unpaired surrogate");
+                                }
+                                self.offset += 2;
+                                let low = self.hex4()?;
+                                if !(0xdc00..0xe000).contains(&low) {
+                                    return self.error("unpaired surrogate");
+                                }
+                                0x10000
+                                    + ((u32::from(high) - 0xd800) << 10)
+                                    + (u32::from(low) - 0xdc00)
+                            } else {
+                                u32::from(high)
+                            };
+                            match char::from_u32(code) {
+                                Some(c) => c,
+                                None => return self.error("unpaired surrogate"),
+                            }
+                        }
+                        _ => {
+                            self.offset -= 1;
+                            return self.error("invalid escape");
+                        }
+                    });
+                }
+                '\0'..='\u{1f}' => return self.error("unescaped control character"),
+                c => {
+                    self.offset += c.len_utf8();
+                    string.push(c);
+                }
+            }
+        }
+    }
+
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, String>,
+    ) -> Result<T, String> {
+        if self.depth == MAX_DEPTH {
+            return self.error("nesting too deep");
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn array(&mut self) -> Result<Json, String> {
+        self.expect(b'[')?;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.offset += 1;
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.offset += 1,
+                Some(b']') => {
+                    self.offset += 1;
+                    return Ok(Json::Array(values));
+                }
+                _ => return self.error("expected ',' or ']'"),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, String> {
+        self.expect(b'{')?;
+        let mut members = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.offset += 1;
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+            members.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.offset += 1,
+                Some(b'}') => {
+                    self.offset += 1;
+                    return Ok(Json::Object(members));
+                }
+                _ => return self.error("expected ',' or '}'"),
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'n') => self.literal("null", Json::Null),
+            Some(b't') => self.literal("true", Json::Bool(true)),
+            Some(b'f') => self.literal("false", Json::Bool(false)),
+            Some(b'"') => self.string().map(Json::String),
+            Some(b'[') => self.nested(Self::array),
+            Some(b'{') => self.nested(Self::object),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => self.error("expected value"),
+            None => self.error("unexpected end of input"),
+        }
+    }
+}
+
+/// Parses a complete JSON text, returning the reason (including the byte
+/// offset) it is invalid otherwise.
+pub(crate) fn parse_json(data: &[u8]) -> Result<Json, String> {
+    let input = std::str::from_utf8(data)
+        .map_err(|err| format!("invalid utf-8 at byte {}", err.valid_up_to()))?;
+    let mut parser = Parser {
+        input,
+        offset: 0,
+        depth: 0,
+    };
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    if parser.offset != input.len() {
+        return parser.error("trailing characters");
+    }
+    Ok(value)
+}
+
+struct Printer {
+    output: String,
+    pretty: bool,
+    indent: usize,
+}
+
+impl Printer {
+    fn line(&mut self) {
+        if self.pretty {
+            self.output.push('\n');
+            self.output.push_str(&"    ".repeat(self.indent));
+        }
+    }
+
+    fn string(&mut self, string: &str) {
+        self.output.push('"');
+        for c in string.chars() {
+            match c {
+                '"' => self.output.push_str("\\\""),
+                '\\' => self.output.push_str("\\\\"),
+                '\n' => self.output.push_str("\\n"),
+                '\r' => self.output.push_str("\\r"),
+                '\t' => self.output.push_str("\\t"),
+                '\0'..='\u{1f}' => write!(self.output, "\\u{:04x}", u32::from(c)).unwrap(),
+                c => self.output.push(c),
+            }
+        }
+        self.output.push('"');
+    }
+
+    fn container<T>(
+        &mut self,
+        open: char,
+        items: &[T],
+        close: char,
+        mut item: impl FnMut(&mut Self, &T),
+    ) {
+        self.output.push(open);
+        if !items.is_empty() {
+            self.indent += 1;
+            for (i, value) in items.iter().enumerate() {
+                if i > 0 {
+                    self.output.push(',');
+                }
+                self.line();
+                item(self, value);
+            }
+            self.indent -= 1;
+            self.line();
+        }
+        self.output.push(close);
+    }
+
+    fn value(&mut self, value: &Json) {
+        match value {
+            Json::Null => self.output.push_str("null"),
+            Json::Bool(true) => self.output.push_str("true"),
+            Json::Bool(false) => self.output.push_str("false"),
+            Json::Number(number) => self.output.push_str(number),
+            Json::String(string) => self.string(string),
+            Json::Array(values) => self.container('[', values, ']', Self::value),
+            Json::Object(members) => self.container('{', members, '}', |this, (key, value)| {
+                this.string(key);
+                this.output.push_str(if this.pretty { ": " } else { ":" });
+                this.value(value);
+            }),
+        }
+    }
+}
+
+impl Json {
+    /// Outputs the value as JSON text, one member per line when `pretty`.
+    pub(crate) fn to_text(&self, pretty: bool) -> String {
+        let mut printer = Printer {
+            output: String::new(),
+            pretty,
+            indent: 0,
+        };
+        printer.value(self);
+        printer.output
+    }
+}
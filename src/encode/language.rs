@@ -0,0 +1,197 @@
+//! Support for language-tagged strings, [`crate::Tag::LANGUAGE_TAGGED_STRING`],
+//! including the well-formedness rules for [BCP 47][] language tags.
+//!
+//! [BCP 47]: https://tools.ietf.org/html/rfc5646#section-2.1
+
+use crate::{DataItem, Simple, TextString};
+
+/// The tags from [RFC 5646 § 2.2.8][RFC 2.2.8] that do not match the normal
+/// syntax, but are still well-formed.
+///
+/// [RFC 2.2.8]: https://tools.ietf.org/html/rfc5646#section-2.2.8
+const IRREGULAR: &[&str] = &[
+    "en-gb-oed",
+    "i-ami",
+    "i-bnn",
+    "i-default",
+    "i-enochian",
+    "i-hak",
+    "i-klingon",
+    "i-lux",
+    "i-mingo",
+    "i-navajo",
+    "i-pwn",
+    "i-tao",
+    "i-tay",
+    "i-tsu",
+    "sgn-be-fr",
+    "sgn-be-nl",
+    "sgn-ch-de",
+];
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub(crate) enum Direction {
+    LeftToRight,
+    RightToLeft,
+}
+
+pub(crate) struct LanguageTaggedString<'a> {
+    pub(crate) language: &'a str,
+    pub(crate) direction: Option<Direction>,
+}
+
+fn is_alpha(subtag: &str, len: impl std::ops::RangeBounds<usize>) -> bool {
+    len.contains(&subtag.len()) && subtag.bytes().all(|b| b.is_ascii_alphabetic())
+}
+
+fn is_digit(subtag: &str, len: usize) -> bool {
+    subtag.len() == len && subtag.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn is_variant(subtag: &str) -> bool {
+    match subtag.len() {
+        5..=8 => true,
+        4 => subtag.as_bytes()[0].is_ascii_digit(),
+        _ => false,
+    }
+}
+
+/// Checks that `language` is a well-formed language tag, returning the reason
+/// it is not otherwise.
+pub(crate) fn validate_language_tag(language: &str) -> Result<(), String> {
+    if IRREGULAR
+        .iter()
+        .any(|irregular| irregular.eq_ignore_ascii_case(language))
+    {
+        return Ok(());
+    }
+
+    if language.is_empty() {
+        return Err("empty".into());
+    }
+
+    let subtags: Vec<&str> = language.split('-').collect();
+    for subtag in &subtags {
+        if subtag.is_empty() {
+            return Err("empty subtag".into());
+        }
+        if subtag.len() > 8 {
+            return Err(format!("subtag {subtag:?} is too long"));
+        }
+        if !subtag.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return Err(format!("invalid character in subtag {subtag:?}"));
+        }
+    }
+
+    let mut subtags = subtags.into_iter().peekable();
+
+    if !subtags.peek().unwrap().eq_ignore_ascii_case("x") {
+        let primary = subtags.next().unwrap();
+        if !is_alpha(primary, 2..=8) {
+            return Err(format!("invalid primary language subtag {primary:?}"));
+        }
+        if primary.len() <= 3 {
+            for _ in 0..3 {
+                if subtags.next_if(|&subtag| is_alpha(subtag, 3..=3)).is_none() {
+                    break;
+                }
+            }
+        }
+        subtags.next_if(|&subtag| is_alpha(subtag, 4..=4));
+        subtags.next_if(|&subtag| is_alpha(subtag, 2..=2) || is_digit(subtag, 3));
+
+        let mut variants: Vec<&str> = Vec::new();
+        while let Some(variant) = subtags.next_if(|&subtag| is_variant(subtag)) {
+            if variants
+                .iter()
+                .any(|other| variant.eq_ignore_ascii_case(other))
+            {
+                return Err(format!("duplicate variant {variant:?}"));
+            }
+            variants.push(variant);
+        }
+
+        let mut singletons: Vec<&str> = Vec::new();
+        while let Some(singleton) =
+            subtags.next_if(|&subtag| subtag.len() == 1 && !subtag.eq_ignore_ascii_case("x"))
+        {
+            if singletons
+                .iter()
+                .any(|other| singleton.eq_ignore_ascii_case(other))
+            {
+                return Err(format!("duplicate extension {singleton:?}"));
+            }
+            singletons.push(singleton);
+            if subtags.next_if(|subtag| subtag.len() >= 2).is_none() {
+                return Err(format!("extension {singleton:?} has no subtags"));
+            }
+            while subtags.next_if(|subtag| subtag.len() >= 2).is_some() {}
+        }
+    }
+
+    // Private use subtags can be anything, so must be last
+    if subtags
+        .next_if(|subtag| subtag.eq_ignore_ascii_case("x"))
+        .is_some()
+    {
+        return match subtags.next() {
+            Some(_) => Ok(()),
+            None => Err("private use has no subtags".into()),
+        };
+    }
+
+    match subtags.next() {
+        Some(subtag) => Err(format!("unexpected subtag {subtag:?}")),
+        None => Ok(()),
+    }
+}
+
+impl<'a> LanguageTaggedString<'a> {
+    pub(crate) fn extract(value: &'a DataItem) -> Result<Self, String> {
+        let data = match value {
+            DataItem::Array { data, .. } if data.len() == 2 || data.len() == 3 => data,
+            _ => return Err("invalid type for language-tagged string".into()),
+        };
+
+        let language = match &data[0] {
+            DataItem::TextString(TextString { data, .. }) => data.as_str(),
+            _ => return Err("invalid type for language tag".into()),
+        };
+
+        match data[1] {
+            DataItem::TextString(_) | DataItem::IndefiniteTextString(_) => {}
+            _ => return Err("invalid type for language-tagged string text".into()),
+        }
+
+        let direction = match data.get(2) {
+            None | Some(DataItem::Simple(Simple::NULL)) => None,
+            Some(DataItem::Simple(Simple::FALSE)) => Some(Direction::LeftToRight),
+            Some(DataItem::Simple(Simple::TRUE)) => Some(Direction::RightToLeft),
+            Some(_) => return Err("invalid type for language-tagged string direction".into()),
+        };
+
+        // Only a string with an explicit direction may omit the language
+        if !(language.is_empty() && data.len() == 3) {
+            validate_language_tag(language)
+                .map_err(|err| format!("invalid language tag, {err}"))?;
+        }
+
+        Ok(Self {
+            language,
+            direction,
+        })
+    }
+
+    pub(crate) fn describe(&self) -> String {
+        let language = if self.language.is_empty() {
+            "no language".to_owned()
+        } else {
+            format!("language({})", self.language)
+        };
+        match self.direction {
+            None => language,
+            Some(Direction::LeftToRight) => format!("{language}, left-to-right"),
+            Some(Direction::RightToLeft) => format!("{language}, right-to-left"),
+        }
+    }
+}
@@ -1,6 +1,16 @@
 mod bytes;
 mod diag;
+mod embedded;
 mod hex;
+mod json;
+mod language;
+mod multi_dimensional;
+
+pub use self::{
+    diag::{to_diag_seq, DiagOptions, TypedArrayStyle},
+    embedded::EmbeddedCbor,
+    hex::{to_hex_seq, AnnotationProfile, ByteStringStyle, HexOptions},
+};
 
 #[derive(Copy, Clone)]
 #[allow(clippy::enum_variant_names)]
@@ -0,0 +1,170 @@
+//! Support for the multi-dimensional and homogeneous arrays defined in [RFC
+//! 8746 § 3][RFC 3].
+//!
+//! [RFC 3]: https://tools.ietf.org/html/rfc8746#section-3
+
+use num_bigint::BigUint;
+
+use crate::{typed_array::TypedArray, ByteString, DataItem, IntegerWidth, Simple, Tag};
+
+/// A multi-dimensional array, with its elements in row-major order regardless
+/// of the order they were encoded in.
+pub(crate) struct MultiDimensionalArray {
+    pub(crate) dimensions: Vec<usize>,
+    elements: Vec<DataItem>,
+}
+
+fn typed_array_elements(tag: Tag, data: &[u8]) -> Result<Vec<DataItem>, String> {
+    let array =
+        TypedArray::from_tag(tag).ok_or("invalid type for multi-dimensional array elements")?;
+    let elements = array
+        .format(data)
+        .map_err(|err| format!("{err} for multi-dimensional array elements"))?;
+    // Elements that diagnostic notation cannot show exactly, such as large
+    // f128 values or NaNs with a payload, are not shown at all
+    elements
+        .iter()
+        .map(|element| {
+            crate::parse_diag(element)
+                .ok()
+                .filter(|item| item.to_diag() == *element)
+                .ok_or_else(|| {
+                    format!("element {element} of multi-dimensional array cannot be shown")
+                })
+        })
+        .collect()
+}
+
+fn elements(value: &DataItem) -> Result<Vec<DataItem>, String> {
+    match value {
+        DataItem::Array { data, .. } => Ok(data.clone()),
+        DataItem::Tag {
+            tag: Tag::HOMOGENEOUS_ARRAY,
+            value,
+            ..
+        } => match **value {
+            DataItem::Array { ref data, .. } => Ok(data.clone()),
+            _ => Err("invalid type for multi-dimensional array elements".into()),
+        },
+        DataItem::Tag { tag, value, .. } => match **value {
+            DataItem::ByteString(ByteString { ref data, .. }) => typed_array_elements(*tag, data),
+            _ => Err("invalid type for multi-dimensional array elements".into()),
+        },
+        _ => Err("invalid type for multi-dimensional array elements".into()),
+    }
+}
+
+fn dimensions(value: &DataItem) -> Option<Vec<usize>> {
+    match value {
+        DataItem::Array { data, .. } if !data.is_empty() => data
+            .iter()
+            .map(|dimension| match *dimension {
+                DataItem::Integer { value, .. } => usize::try_from(value).ok(),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+impl MultiDimensionalArray {
+    pub(crate) fn extract(value: &DataItem, column_major: bool) -> Result<Self, String> {
+        let (dimensions, elements) = match value {
+            DataItem::Array { data, .. } if data.len() == 2 => (
+                self::dimensions(&data[0])
+                    .ok_or("invalid type for multi-dimensional array dimensions")?,
+                self::elements(&data[1])?,
+            ),
+            _ => return Err("invalid type for multi-dimensional array".into()),
+        };
+
+        let count: BigUint = dimensions
+            .iter()
+            .map(|&dimension| BigUint::from(dimension))
+            .product();
+        if count != BigUint::from(elements.len()) {
+            return Err(format!(
+                "invalid multi-dimensional array, dimensions product {count} does not match element count {}",
+                elements.len()
+            ));
+        }
+
+        let elements = if column_major {
+            // For each element in row-major order find its offset in the
+            // column-major elements, where the first dimension varies fastest
+            (0..elements.len())
+                .map(|mut index| {
+                    let mut offset = 0;
+                    for (i, &dimension) in dimensions.iter().enumerate().rev() {
+                        let stride: usize = dimensions[..i].iter().product();
+                        offset += (index % dimension) * stride;
+                        index /= dimension;
+                    }
+                    elements[offset].clone()
+                })
+                .collect()
+        } else {
+            elements
+        };
+
+        Ok(Self {
+            dimensions,
+            elements,
+        })
+    }
+
+    /// The elements as nested arrays, one level per dimension.
+    pub(crate) fn nested(&self) -> DataItem {
+        fn nest(dimensions: &[usize], elements: &[DataItem]) -> DataItem {
+            let data = match dimensions {
+                [_] => elements.to_vec(),
+                [dimension, rest @ ..] => {
+                    let size: usize = rest.iter().product();
+                    (0..*dimension)
+                        .map(|i| nest(rest, &elements[i * size..(i + 1) * size]))
+                        .collect()
+                }
+                [] => unreachable!(),
+            };
+            DataItem::Array {
+                data,
+                bitwidth: Some(IntegerWidth::Unknown),
+            }
+        }
+
+        nest(&self.dimensions, &self.elements)
+    }
+}
+
+/// Whether all the elements of an array have the same type, as required for a
+/// [`Tag::HOMOGENEOUS_ARRAY`].
+pub(crate) fn is_homogeneous(data: &[DataItem]) -> bool {
+    #[derive(PartialEq)]
+    enum Kind {
+        Integer,
+        ByteString,
+        TextString,
+        Array,
+        Map,
+        Tag(Tag),
+        Float,
+        Boolean,
+        Simple(Simple),
+    }
+
+    fn kind(value: &DataItem) -> Kind {
+        match *value {
+            DataItem::Integer { .. } | DataItem::Negative { .. } => Kind::Integer,
+            DataItem::ByteString(_) | DataItem::IndefiniteByteString(_) => Kind::ByteString,
+            DataItem::TextString(_) | DataItem::IndefiniteTextString(_) => Kind::TextString,
+            DataItem::Array { .. } => Kind::Array,
+            DataItem::Map { .. } => Kind::Map,
+            DataItem::Tag { tag, .. } => Kind::Tag(tag),
+            DataItem::Float(_) => Kind::Float,
+            DataItem::Simple(Simple::FALSE) | DataItem::Simple(Simple::TRUE) => Kind::Boolean,
+            DataItem::Simple(simple) => Kind::Simple(simple),
+        }
+    }
+
+    data.windows(2).all(|pair| kind(&pair[0]) == kind(&pair[1]))
+}
@@ -0,0 +1,266 @@
+//! A software implementation of IEEE 754 binary128 floating point values,
+//! supporting just enough to losslessly convert them to and from decimal.
+
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::{cmp::Ordering, fmt};
+
+const EXPONENT_BIAS: i64 = 16383;
+const FRACTION_BITS: u32 = 112;
+const MAX_BIASED_EXPONENT: u128 = 0x7fff;
+/// The exponent of the least significant bit of a subnormal value.
+const MIN_EXPONENT: i64 = 1 - EXPONENT_BIAS - FRACTION_BITS as i64;
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub(crate) struct F128(pub(crate) u128);
+
+/// The decoded parts of a binary128 value.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub(crate) enum Parts {
+    /// A value equal to `mantissa * 2^exponent`.
+    Finite {
+        negative: bool,
+        mantissa: BigUint,
+        exponent: i64,
+    },
+    Infinity {
+        negative: bool,
+    },
+    NaN {
+        negative: bool,
+        quiet: bool,
+        payload: u128,
+    },
+}
+
+impl F128 {
+    pub(crate) const NAN: F128 = F128(0x7fff_8000_0000_0000_0000_0000_0000_0000);
+
+    pub(crate) fn from_be_bytes(bytes: [u8; 16]) -> F128 {
+        F128(u128::from_be_bytes(bytes))
+    }
+
+    pub(crate) fn from_le_bytes(bytes: [u8; 16]) -> F128 {
+        F128(u128::from_le_bytes(bytes))
+    }
+
+    pub(crate) fn to_be_bytes(self) -> [u8; 16] {
+        self.0.to_be_bytes()
+    }
+
+    pub(crate) fn to_le_bytes(self) -> [u8; 16] {
+        self.0.to_le_bytes()
+    }
+
+    pub(crate) fn parts(self) -> Parts {
+        let negative = self.0 >> 127 == 1;
+        let biased_exponent = (self.0 >> FRACTION_BITS) & MAX_BIASED_EXPONENT;
+        let fraction = self.0 & ((1 << FRACTION_BITS) - 1);
+
+        match biased_exponent {
+            MAX_BIASED_EXPONENT if fraction == 0 => Parts::Infinity { negative },
+            MAX_BIASED_EXPONENT => Parts::NaN {
+                negative,
+                quiet: fraction >> (FRACTION_BITS - 1) == 1,
+                payload: fraction & ((1 << (FRACTION_BITS - 1)) - 1),
+            },
+            0 => Parts::Finite {
+                negative,
+                mantissa: fraction.into(),
+                exponent: MIN_EXPONENT,
+            },
+            _ => Parts::Finite {
+                negative,
+                mantissa: (fraction | (1 << FRACTION_BITS)).into(),
+                exponent: biased_exponent as i64 - EXPONENT_BIAS - i64::from(FRACTION_BITS),
+            },
+        }
+    }
+
+    /// Like the [`fmt::Display`] output, but includes the sign, kind and
+    /// payload of any NaN other than the default quiet NaN.
+    pub(crate) fn describe(self) -> String {
+        match self.parts() {
+            Parts::NaN {
+                negative,
+                quiet,
+                payload,
+            } if negative || !quiet || payload != 0 => format!(
+                "{}NaN, {}, payload(0x{payload:x})",
+                if negative { "-" } else { "" },
+                if quiet { "quiet" } else { "signaling" },
+            ),
+            _ => self.to_string(),
+        }
+    }
+
+    /// Rounds the value `numerator / denominator` to the nearest binary128
+    /// value, with ties to even.
+    fn from_ratio(negative: bool, numerator: &BigUint, denominator: &BigUint) -> F128 {
+        let sign = u128::from(negative) << 127;
+
+        if numerator.is_zero() {
+            return F128(sign);
+        }
+
+        // Find the exponent giving a mantissa with 113 significant bits, or
+        // the minimum exponent for subnormal values
+        let mut exponent =
+            (numerator.bits() as i64 - denominator.bits() as i64 - i64::from(FRACTION_BITS) - 1)
+                .max(MIN_EXPONENT);
+        let (mantissa, remainder, divisor) = loop {
+            let (scaled_numerator, scaled_denominator) = if exponent < 0 {
+                (numerator << (-exponent) as u64, denominator.clone())
+            } else {
+                (numerator.clone(), denominator << exponent as u64)
+            };
+            let mantissa = &scaled_numerator / &scaled_denominator;
+            let remainder = &scaled_numerator % &scaled_denominator;
+            if mantissa.bits() > u64::from(FRACTION_BITS) + 1 {
+                exponent += 1;
+            } else if mantissa.bits() <= u64::from(FRACTION_BITS) && exponent > MIN_EXPONENT {
+                exponent -= 1;
+            } else {
+                break (mantissa, remainder, scaled_denominator);
+            }
+        };
+
+        let round_up = match (remainder << 1u8).cmp(&divisor) {
+            Ordering::Less => false,
+            Ordering::Equal => mantissa.bit(0),
+            Ordering::Greater => true,
+        };
+        let mut mantissa = if round_up { mantissa + 1u8 } else { mantissa };
+        if mantissa.bits() > u64::from(FRACTION_BITS) + 1 {
+            mantissa >>= 1u8;
+            exponent += 1;
+        }
+
+        let mantissa = u128::try_from(mantissa).unwrap();
+        let biased_exponent = if mantissa >> FRACTION_BITS == 0 {
+            0
+        } else {
+            exponent + EXPONENT_BIAS + i64::from(FRACTION_BITS)
+        };
+        if biased_exponent >= MAX_BIASED_EXPONENT as i64 {
+            return F128(sign | (MAX_BIASED_EXPONENT << FRACTION_BITS));
+        }
+
+        F128(
+            sign | ((biased_exponent as u128) << FRACTION_BITS)
+                | (mantissa & ((1 << FRACTION_BITS) - 1)),
+        )
+    }
+}
+
+/// Outputs the exact decimal value, `Infinity` or `NaN` (ignoring any payload)
+/// in diagnostic notation float syntax.
+impl fmt::Display for F128 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (negative, mantissa, exponent) = match self.parts() {
+            Parts::Finite {
+                negative,
+                mantissa,
+                exponent,
+            } => (negative, mantissa, exponent),
+            Parts::Infinity { negative } => {
+                return f.write_str(if negative { "-Infinity" } else { "Infinity" });
+            }
+            Parts::NaN { .. } => return f.write_str("NaN"),
+        };
+
+        if negative {
+            f.write_str("-")?;
+        }
+
+        if mantissa.is_zero() {
+            return f.write_str("0.0");
+        }
+
+        // The value is `digits * 10^-scale`
+        let (digits, scale) = if exponent >= 0 {
+            ((mantissa << exponent as u64).to_string(), 0)
+        } else {
+            let five: BigUint = 5u8.into();
+            (
+                (mantissa * five.pow((-exponent) as u32)).to_string(),
+                -exponent,
+            )
+        };
+        let trimmed = digits.trim_end_matches('0');
+        let scale = scale - (digits.len() - trimmed.len()) as i64;
+        let digits = trimmed;
+
+        // The power of ten of the leading digit
+        let magnitude = digits.len() as i64 - 1 - scale;
+        if (-7..21).contains(&magnitude) {
+            if scale <= 0 {
+                write!(f, "{digits}{}.0", "0".repeat((-scale) as usize))
+            } else if magnitude >= 0 {
+                let (integer, fraction) = digits.split_at(digits.len() - scale as usize);
+                write!(f, "{integer}.{fraction}")
+            } else {
+                write!(f, "0.{}{digits}", "0".repeat((-magnitude - 1) as usize))
+            }
+        } else {
+            let (first, rest) = digits.split_at(1);
+            let rest = if rest.is_empty() { "0" } else { rest };
+            write!(f, "{first}.{rest}e{magnitude}")
+        }
+    }
+}
+
+/// Parses a decimal float (rounding to the nearest value), `Infinity` or
+/// `NaN` with an optional sign.
+impl std::str::FromStr for F128 {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<F128, ()> {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let sign = u128::from(negative) << 127;
+
+        match s {
+            "Infinity" => return Ok(F128(sign | (MAX_BIASED_EXPONENT << FRACTION_BITS))),
+            "NaN" => return Ok(F128(sign | F128::NAN.0)),
+            _ => {}
+        }
+
+        let (significand, exponent) = match s.find(['e', 'E']) {
+            Some(index) => (&s[..index], s[index + 1..].parse::<i64>().map_err(|_| ())?),
+            None => (s, 0),
+        };
+        let (integer, fraction) = significand.split_once('.').unwrap_or((significand, ""));
+        if integer.is_empty()
+            || !integer.bytes().all(|b| b.is_ascii_digit())
+            || !fraction.bytes().all(|b| b.is_ascii_digit())
+        {
+            return Err(());
+        }
+
+        let digits: BigUint = format!("{integer}{fraction}").parse().map_err(|_| ())?;
+        // Saturating at the extremes of the exponent keeps the value outside
+        // the range of binary128
+        let scale = exponent.saturating_sub(fraction.len() as i64);
+
+        // Avoid huge intermediate values for values far outside the range of
+        // binary128, which is roughly 6.5e-4966 to 1.2e4932
+        let magnitude = (digits.to_string().len() as i64).saturating_add(scale);
+        if !digits.is_zero() && magnitude > 4934 {
+            return Ok(F128(sign | (MAX_BIASED_EXPONENT << FRACTION_BITS)));
+        }
+        if digits.is_zero() || magnitude < -4967 {
+            return Ok(F128(sign));
+        }
+
+        let ten: BigUint = 10u8.into();
+        let power = ten.pow(u32::try_from(scale.unsigned_abs()).map_err(|_| ())?);
+        Ok(if scale >= 0 {
+            F128::from_ratio(negative, &(digits * power), &BigUint::one())
+        } else {
+            F128::from_ratio(negative, &digits, &power)
+        })
+    }
+}
@@ -0,0 +1,129 @@
+//! Conversions between the bits of [`Float`]s at each width and `f64`, which
+//! unlike `half` and `as` casts keep the sign and payload of NaNs, including
+//! whether they are signaling.
+
+use half::f16;
+
+use crate::{Float, FloatWidth};
+
+const F64_EXPONENT: u64 = 0x7ff << 52;
+
+/// Widens the bits of a NaN of the total and fraction widths given,
+/// keeping the fraction in the most significant bits.
+fn widen_nan(bits: u64, total: u32, fraction_bits: u32) -> f64 {
+    let sign = bits >> (total - 1) << 63;
+    let fraction = (bits & ((1 << fraction_bits) - 1)) << (52 - fraction_bits);
+    f64::from_bits(sign | F64_EXPONENT | fraction)
+}
+
+/// Narrows a NaN to the total width, exponent bits and fraction width given,
+/// keeping the most significant bits of the fraction. The low bits of
+/// the fraction are dropped, so a fraction of only low bits becomes a quiet
+/// NaN rather than infinity.
+fn narrow_nan(value: f64, total: u32, exponent: u64, fraction_bits: u32) -> u64 {
+    let bits = value.to_bits();
+    let sign = bits >> 63 << (total - 1);
+    let mut fraction = (bits & ((1 << 52) - 1)) >> (52 - fraction_bits);
+    if fraction == 0 {
+        fraction = 1 << (fraction_bits - 1);
+    }
+    sign | exponent | fraction
+}
+
+fn f16_to_f64(bits: u16) -> f64 {
+    let value = f16::from_bits(bits);
+    if value.is_nan() {
+        widen_nan(bits.into(), 16, 10)
+    } else {
+        value.to_f64()
+    }
+}
+
+fn f32_to_f64(bits: u32) -> f64 {
+    let value = f32::from_bits(bits);
+    if value.is_nan() {
+        widen_nan(bits.into(), 32, 23)
+    } else {
+        value.into()
+    }
+}
+
+fn f64_to_f16(value: f64) -> u16 {
+    if value.is_nan() {
+        narrow_nan(value, 16, 0x1f << 10, 10) as u16
+    } else {
+        f16::from_f64(value).to_bits()
+    }
+}
+
+fn f64_to_f32(value: f64) -> u32 {
+    if value.is_nan() {
+        narrow_nan(value, 32, 0xff << 23, 23) as u32
+    } else {
+        (value as f32).to_bits()
+    }
+}
+
+impl Float {
+    /// The half precision value with the given `bits`, encoded with
+    /// [`FloatWidth::Sixteen`].
+    pub fn from_f16_bits(bits: u16) -> Float {
+        Float {
+            bits: bits.into(),
+            bitwidth: FloatWidth::Sixteen,
+        }
+    }
+
+    /// `value` exactly, encoded with [`FloatWidth::ThirtyTwo`].
+    pub fn from_f32(value: f32) -> Float {
+        Float {
+            bits: value.to_bits().into(),
+            bitwidth: FloatWidth::ThirtyTwo,
+        }
+    }
+
+    /// `value` exactly, encoded with [`FloatWidth::SixtyFour`].
+    pub fn from_f64(value: f64) -> Float {
+        Float {
+            bits: value.to_bits(),
+            bitwidth: FloatWidth::SixtyFour,
+        }
+    }
+
+    /// `value` rounded to `bitwidth`, keeping the most significant bits of
+    /// the payload of a NaN.
+    pub fn new(value: f64, bitwidth: FloatWidth) -> Float {
+        let bits = match bitwidth {
+            FloatWidth::Sixteen => f64_to_f16(value).into(),
+            FloatWidth::ThirtyTwo => f64_to_f32(value).into(),
+            FloatWidth::SixtyFour | FloatWidth::Unknown => value.to_bits(),
+        };
+        Float { bits, bitwidth }
+    }
+
+    /// The bits of the value at its width, ignoring any bits above it.
+    pub fn encoded_bits(self) -> u64 {
+        match self.bitwidth {
+            FloatWidth::Sixteen => self.bits & 0xffff,
+            FloatWidth::ThirtyTwo => self.bits & 0xffff_ffff,
+            FloatWidth::SixtyFour | FloatWidth::Unknown => self.bits,
+        }
+    }
+
+    /// The exact value, including the payload of a NaN.
+    pub fn as_f64(self) -> f64 {
+        let bits = self.encoded_bits();
+        match self.bitwidth {
+            FloatWidth::Sixteen => f16_to_f64(bits as u16),
+            FloatWidth::ThirtyTwo => f32_to_f64(bits as u32),
+            FloatWidth::SixtyFour | FloatWidth::Unknown => f64::from_bits(bits),
+        }
+    }
+
+    /// Whether the value can be encoded at `bitwidth` without rounding it or
+    /// dropping bits of the payload of a NaN.
+    pub fn is_exactly_representable_in(self, bitwidth: FloatWidth) -> bool {
+        let value = self.as_f64();
+        Float::new(value, bitwidth).as_f64().to_bits() == value.to_bits()
+    }
+}
@@ -10,13 +10,27 @@
 
 #![warn(rust_2018_idioms)]
 
+mod app_literal;
 mod encode;
 mod error;
+mod f128;
+mod float;
+mod magic;
 mod parse;
+mod resolve;
 mod syntax;
+mod typed_array;
 
 pub use self::{
+    encode::{
+        to_diag_seq, to_hex_seq, AnnotationProfile, ByteStringStyle, DiagOptions, EmbeddedCbor,
+        HexOptions, TypedArrayStyle,
+    },
     error::{Error, Result},
-    parse::{parse_bytes, parse_bytes_partial, parse_diag, parse_hex},
-    syntax::{ByteString, DataItem, FloatWidth, IntegerWidth, Simple, Tag, TextString},
+    magic::Magic,
+    parse::{
+        parse_bytes, parse_bytes_partial, parse_bytes_seq, parse_bytes_with, parse_diag,
+        parse_diag_seq, parse_hex, parse_hex_seq, parse_hex_with, HexInputFormat, ParseOptions,
+    },
+    syntax::{ByteString, DataItem, Float, FloatWidth, IntegerWidth, Simple, Tag, TextString},
 };
@@ -0,0 +1,125 @@
+//! Detection of the magic numbers that can start CBOR data, as defined in
+//! [RFC 8949 § 3.4.6][RFC 3.4.6] and [RFC 9277][].
+//!
+//! [RFC 3.4.6]: https://tools.ietf.org/html/rfc8949#section-3.4.6
+//! [RFC 9277]: https://tools.ietf.org/html/rfc9277
+
+use crate::Tag;
+
+/// The byte string every RFC 9277 magic number that is not applied to a
+/// single data item is tagging, `h'424f52'`.
+const BOR: &[u8] = b"\x43BOR";
+
+/// A magic number identifying the format of the data following it.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Magic {
+    /// A single data item tagged with [`Tag::SELF_DESCRIBE_CBOR`].
+    SelfDescribed,
+
+    /// A single data item tagged with the [content-format
+    /// tag](Tag::from_content_format) of the contained value.
+    ContentFormat(u16),
+
+    /// A CBOR sequence, starting with [`Tag::SELF_DESCRIBE_CBOR_SEQUENCE`]
+    /// applied to `'BOR'`.
+    Sequence,
+
+    /// A CBOR sequence, starting with the [content-format
+    /// tag](Tag::from_content_format) of the sequence applied to `'BOR'`.
+    ContentFormatSequence(u16),
+
+    /// Data that is not CBOR, starting with
+    /// [`Tag::CBOR_LABELED_NON_CBOR_DATA`] applied to `'BOR'`.
+    LabeledNonCbor,
+}
+
+impl Magic {
+    /// Detect the magic number at the start of `data`, returning it along
+    /// with its length in bytes.
+    ///
+    /// For the magic numbers applied to a single data item only the tag is
+    /// counted, for the others the `'BOR'` byte string is included.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use cbor_diag::Magic;
+    ///
+    /// assert_eq!(
+    ///     Magic::detect(b"\xd9\xd9\xf7\x00"),
+    ///     Some((Magic::SelfDescribed, 3)));
+    ///
+    /// assert_eq!(
+    ///     Magic::detect(b"\xd9\xd9\xf8\x43BOR\x00\x01"),
+    ///     Some((Magic::Sequence, 7)));
+    ///
+    /// assert_eq!(
+    ///     Magic::detect(b"\xda\x63\x74\x00\x3c\x43BOR\x00\x01"),
+    ///     Some((Magic::ContentFormatSequence(60), 9)));
+    ///
+    /// assert_eq!(Magic::detect(b"\x00"), None);
+    /// ```
+    pub fn detect(data: &[u8]) -> Option<(Magic, usize)> {
+        let is_bor = |offset: usize| data[offset..].starts_with(BOR);
+        match *data {
+            [0xd9, 0xd9, 0xf7, ..] => Some((Magic::SelfDescribed, 3)),
+            [0xd9, 0xd9, 0xf8, ..] if is_bor(3) => Some((Magic::Sequence, 7)),
+            [0xd9, 0xd9, 0xf9, ..] if is_bor(3) => Some((Magic::LabeledNonCbor, 7)),
+            [0xda, a, b, c, d, ..] => {
+                let content_format =
+                    Tag(u32::from_be_bytes([a, b, c, d]).into()).content_format()?;
+                if is_bor(5) {
+                    Some((Magic::ContentFormatSequence(content_format), 9))
+                } else {
+                    Some((Magic::ContentFormat(content_format), 5))
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The media type (and any content coding) registered for a CoAP
+/// content-format in the [IANA registry][IANA].
+///
+/// [IANA]: https://www.iana.org/assignments/core-parameters/core-parameters.xhtml#content-formats
+fn content_format_name(content_format: u16) -> Option<&'static str> {
+    Some(match content_format {
+        0 => "text/plain;charset=utf-8",
+        16 => "application/cose;cose-type=\"cose-encrypt0\"",
+        17 => "application/cose;cose-type=\"cose-mac0\"",
+        18 => "application/cose;cose-type=\"cose-sign1\"",
+        40 => "application/link-format",
+        41 => "application/xml",
+        42 => "application/octet-stream",
+        47 => "application/exi",
+        50 => "application/json",
+        51 => "application/json-patch+json",
+        52 => "application/merge-patch+json",
+        60 => "application/cbor",
+        61 => "application/cwt",
+        62 => "application/multipart-core",
+        63 => "application/cbor-seq",
+        96 => "application/cose;cose-type=\"cose-encrypt\"",
+        97 => "application/cose;cose-type=\"cose-mac\"",
+        98 => "application/cose;cose-type=\"cose-sign\"",
+        101 => "application/cose-key",
+        102 => "application/cose-key-set",
+        110 => "application/senml+json",
+        112 => "application/senml+cbor",
+        256 => "application/coap-group+json",
+        10000 => "application/vnd.ocf+cbor",
+        11542 => "application/vnd.oma.lwm2m+tlv",
+        11543 => "application/vnd.oma.lwm2m+json",
+        11544 => "application/vnd.oma.lwm2m+cbor",
+        _ => return None,
+    })
+}
+
+/// Describes a content-format as its number and, if known, its media type.
+pub(crate) fn describe_content_format(content_format: u16) -> String {
+    match content_format_name(content_format) {
+        Some(name) => format!("content-format({content_format}, {name})"),
+        None => format!("content-format({content_format})"),
+    }
+}
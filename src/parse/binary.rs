@@ -1,7 +1,6 @@
 #![allow(clippy::useless_let_if_seq)]
 use std::{convert::TryFrom, str};
 
-use half::f16;
 use nom::{
     bits::{bits, bytes},
     branch::alt,
@@ -9,12 +8,14 @@ use nom::{
     combinator::{map, map_res, verify},
     error::{make_error, ErrorKind},
     multi::{count, many_till},
-    number::streaming::{be_f32, be_f64, be_u16},
+    number::streaming::{be_u16, be_u32, be_u64},
     sequence::{pair, preceded},
     Err, IResult,
 };
 
-use crate::{ByteString, DataItem, FloatWidth, IntegerWidth, Result, Simple, Tag, TextString};
+use crate::{
+    ByteString, DataItem, Float, FloatWidth, IntegerWidth, Result, Simple, Tag, TextString,
+};
 
 pub fn take_bits<I, O>(count: usize) -> impl FnMut((I, usize)) -> IResult<(I, usize), O>
 where
@@ -211,23 +212,23 @@ fn float(input: &[u8]) -> IResult<&[u8], DataItem> {
                 preceded(
                     tag_bits(25, 5),
                     bytes::<_, _, nom::error::Error<&[u8]>, _, _>(map(be_u16, |u| {
-                        (f16::from_bits(u).to_f64(), FloatWidth::Sixteen)
+                        (u.into(), FloatWidth::Sixteen)
                     })),
                 ),
                 preceded(
                     tag_bits(26, 5),
-                    bytes::<_, _, nom::error::Error<&[u8]>, _, _>(map(be_f32, |f| {
-                        (f64::from(f), FloatWidth::ThirtyTwo)
+                    bytes::<_, _, nom::error::Error<&[u8]>, _, _>(map(be_u32, |u| {
+                        (u.into(), FloatWidth::ThirtyTwo)
                     })),
                 ),
                 preceded(
                     tag_bits(27, 5),
-                    bytes::<_, _, nom::error::Error<&[u8]>, _, _>(map(be_f64, |f| {
-                        (f, FloatWidth::SixtyFour)
+                    bytes::<_, _, nom::error::Error<&[u8]>, _, _>(map(be_u64, |u| {
+                        (u, FloatWidth::SixtyFour)
                     })),
                 ),
             )),
-            |(value, bitwidth)| DataItem::Float { value, bitwidth },
+            |(bits, bitwidth)| DataItem::Float(Float { bits, bitwidth }),
         ),
     ))(input)
 }
@@ -344,3 +345,30 @@ pub fn parse_bytes_partial(bytes: impl AsRef<[u8]>) -> Result<Option<(DataItem,
         Err(nom::Err::Failure(_)) | Err(nom::Err::Error(_)) => Err("Parser error".into()),
     }
 }
+
+/// Parse a string containing a sequence of binary encoded CBOR data items
+/// (RFC 8742), each directly following the previous.
+///
+/// # Examples
+///
+/// ```rust
+/// use cbor_diag::{DataItem, IntegerWidth};
+///
+/// assert_eq!(
+///     cbor_diag::parse_bytes_seq(b"\x01\x18\x20").unwrap(),
+///     vec![
+///         DataItem::Integer { value: 1, bitwidth: IntegerWidth::Zero },
+///         DataItem::Integer { value: 32, bitwidth: IntegerWidth::Eight },
+///     ]);
+/// ```
+pub fn parse_bytes_seq(bytes: impl AsRef<[u8]>) -> Result<Vec<DataItem>> {
+    let mut bytes = bytes.as_ref();
+    let mut items = Vec::new();
+    while !bytes.is_empty() {
+        let (item, len) = parse_bytes_partial(bytes)?
+            .ok_or_else(|| format!("{} bytes remaining after last item", bytes.len()))?;
+        items.push(item);
+        bytes = &bytes[len..];
+    }
+    Ok(items)
+}
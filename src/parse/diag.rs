@@ -5,25 +5,188 @@ use std::str::FromStr;
 
 use nom::{
     branch::alt,
-    bytes::complete::{escaped_transform, tag},
-    character::complete::{char, digit1, hex_digit1, none_of, oct_digit1},
-    combinator::{map, map_res, opt, recognize, value, verify},
-    error::context,
+    bytes::complete::{escaped_transform, tag, take_while_m_n},
+    character::complete::{
+        alphanumeric1, char, digit1, hex_digit1, multispace0, multispace1, none_of, oct_digit1,
+    },
+    combinator::{cut, eof, map, map_opt, map_res, not, opt, peek, recognize, value, verify},
+    error::{ContextError, ErrorKind, FromExternalError, ParseError},
     multi::{many0, many0_count, many1, separated_list0},
-    sequence::{delimited, pair, preceded, separated_pair, tuple},
-    IResult,
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
 };
+use num_bigint::{BigInt, BigUint, Sign};
+use num_traits::{ToPrimitive, Zero};
 
-use crate::{ByteString, DataItem, FloatWidth, IntegerWidth, Result, Simple, Tag, TextString};
+use crate::{
+    app_literal::AppLiteral, typed_array::TypedArray, ByteString, DataItem, Float, FloatWidth,
+    IntegerWidth, Result, Simple, Tag, TextString,
+};
+
+/// The error of the parsers, keeping how much of the text remained at the
+/// furthest point a parser wrapped by [`expected`] or [`validate`] failed,
+/// and what was expected there. Only the furthest failure is kept as most
+/// failures are followed by those of the alternatives tried after them.
+#[derive(Debug, Default)]
+struct Error {
+    furthest: Option<Expected>,
+}
+
+#[derive(Debug)]
+struct Expected {
+    remaining: usize,
+    what: Vec<&'static str>,
+    /// Whether the text was recognised but invalid, rather than not matching
+    /// at all, making this more useful than what other alternatives expected
+    /// at the same point.
+    specific: bool,
+}
+
+impl Error {
+    fn expected(remaining: usize, what: &'static str, specific: bool) -> Error {
+        Error {
+            furthest: Some(Expected {
+                remaining,
+                what: vec![what],
+                specific,
+            }),
+        }
+    }
+
+    /// Whichever of the errors failed furthest into the text, or what both
+    /// expected if they failed at the same point.
+    fn furthest(self, other: Error) -> Error {
+        let furthest = match (self.furthest, other.furthest) {
+            (Some(mut first), Some(second)) if first.remaining == second.remaining => {
+                match (first.specific, second.specific) {
+                    (true, false) => Some(first),
+                    (false, true) => Some(second),
+                    _ => {
+                        for what in second.what {
+                            if !first.what.contains(&what) {
+                                first.what.push(what);
+                            }
+                        }
+                        Some(first)
+                    }
+                }
+            }
+            (Some(first), Some(second)) => Some(if first.remaining < second.remaining {
+                first
+            } else {
+                second
+            }),
+            (first, second) => first.or(second),
+        };
+        Error { furthest }
+    }
+}
+
+impl<I> ParseError<I> for Error {
+    fn from_error_kind(_: I, _: ErrorKind) -> Self {
+        Error::default()
+    }
+
+    fn append(_: I, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn or(self, other: Self) -> Self {
+        self.furthest(other)
+    }
+}
+
+impl<I> ContextError<I> for Error {}
+
+impl<I, E> FromExternalError<I, E> for Error {
+    fn from_external_error(_: I, _: ErrorKind, _: E) -> Self {
+        Error::default()
+    }
+}
+
+type IResult<I, O> = nom::IResult<I, O, Error>;
+
+/// Records that `what` was expected at the start of the input if `parser`
+/// fails there, to describe the error if the whole parse fails.
+fn expected<'a, T>(
+    what: &'static str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, T> {
+    move |input| {
+        parser(input)
+            .map_err(|err| err.map(|err| err.furthest(Error::expected(input.len(), what, false))))
+    }
+}
+
+/// Checks the output of `parser` with `check`, recording what `check`
+/// expected instead at the start of the input if it fails, in place of the
+/// less specific expectations of other alternatives there.
+fn validate<'a, O, T>(
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+    mut check: impl FnMut(O) -> std::result::Result<T, &'static str>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, T> {
+    move |input| {
+        let (rest, output) = parser(input)?;
+        match check(output) {
+            Ok(value) => Ok((rest, value)),
+            Err(what) => Err(nom::Err::Error(Error::expected(input.len(), what, true))),
+        }
+    }
+}
+
+fn comma(input: &str) -> IResult<&str, &str> {
+    expected("','", tag(","))(input)
+}
+
+/// Items separated by commas, followed by `close`. If `close` fails, what
+/// ended the items is reported with it, as the items could have continued
+/// there instead.
+fn list<'a, T, C>(
+    mut item: impl FnMut(&'a str) -> IResult<&'a str, T>,
+    mut close: impl FnMut(&'a str) -> IResult<&'a str, C>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>> {
+    move |mut input| {
+        let mut items = Vec::new();
+        let ended = loop {
+            let rest = if items.is_empty() {
+                input
+            } else {
+                match comma(input) {
+                    Ok((rest, _)) => rest,
+                    Err(nom::Err::Error(err)) => break err,
+                    Err(err) => return Err(err),
+                }
+            };
+            match item(rest) {
+                Ok((rest, parsed)) => {
+                    input = rest;
+                    items.push(parsed);
+                }
+                Err(nom::Err::Error(err)) => break err,
+                Err(err) => return Err(err),
+            }
+        };
+        match close(input) {
+            Ok((input, _)) => Ok((input, items)),
+            Err(err) => Err(err.map(|err| ended.furthest(err))),
+        }
+    }
+}
 
 fn ws<O: Default>(input: &str) -> IResult<&str, O> {
     map(nom::character::complete::multispace1, |_| O::default())(input)
 }
 
 fn comment<O: Default>(input: &str) -> IResult<&str, O> {
-    map(delimited(tag("/"), many0(none_of("/")), tag("/")), |_| {
-        O::default()
-    })(input)
+    alt((
+        // End of line comments, `//` must be checked before a block comment
+        map(
+            preceded(alt((tag("#"), tag("//"))), many0_count(none_of("\n"))),
+            |_| O::default(),
+        ),
+        map(delimited(tag("/"), many0(none_of("/")), tag("/")), |_| {
+            O::default()
+        }),
+    ))(input)
 }
 
 fn ws_or_comment<O: Default>(input: &str) -> IResult<&str, O> {
@@ -142,83 +305,261 @@ where
     )
 }
 
-fn encoding(input: &str) -> IResult<&str, u64> {
-    preceded(tag("_"), verify(map_res(digit1, u64::from_str), |&e| e < 4))(input)
+/// An encoding indicator giving the width of the argument of an item, `_i`
+/// for an immediate value in the initial byte or `_0` to `_3` for one to
+/// eight additional bytes.
+fn encoding(input: &str) -> IResult<&str, IntegerWidth> {
+    preceded(
+        tag("_"),
+        alt((
+            value(IntegerWidth::Zero, char('i')),
+            value(IntegerWidth::Eight, char('0')),
+            value(IntegerWidth::Sixteen, char('1')),
+            value(IntegerWidth::ThirtyTwo, char('2')),
+            value(IntegerWidth::SixtyFour, char('3')),
+        )),
+    )(input)
+}
+
+/// Whether `value` can be encoded as an argument of the width given by an
+/// encoding indicator.
+fn fits(value: u64, bitwidth: IntegerWidth) -> bool {
+    match bitwidth {
+        IntegerWidth::Zero => value <= 23,
+        IntegerWidth::Eight => value <= u8::MAX.into(),
+        IntegerWidth::Sixteen => value <= u16::MAX.into(),
+        IntegerWidth::ThirtyTwo => value <= u32::MAX.into(),
+        IntegerWidth::SixtyFour | IntegerWidth::Unknown => true,
+    }
+}
+
+/// The width of the length of a string, array or map with an optional
+/// encoding indicator, if the length fits it.
+fn length_width(len: usize, bitwidth: Option<IntegerWidth>) -> Option<IntegerWidth> {
+    let bitwidth = bitwidth.unwrap_or(IntegerWidth::Unknown);
+    fits(len as u64, bitwidth).then_some(bitwidth)
 }
 
-fn hexadecimal(input: &str) -> IResult<&str, u128> {
+/// The opening of an indefinite length item, `_` or the equivalent encoding
+/// indicator `_7` (additional information 31), which must be followed by
+/// whitespace to distinguish it from a first item `7`.
+fn indefinite<'a>(open: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
+    map(
+        wrapws(tuple((
+            tag(open),
+            tag("_"),
+            opt(terminated(char('7'), peek(multispace1))),
+        ))),
+        |_| (),
+    )
+}
+
+/// The opening of a definite length array or map, with an optional encoding
+/// indicator for its length which must be followed by whitespace.
+fn definite<'a>(open: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, Option<IntegerWidth>> {
+    wrapws(preceded(
+        tag(open),
+        opt(terminated(encoding, peek(multispace1))),
+    ))
+}
+
+fn hexadecimal(input: &str) -> IResult<&str, BigUint> {
     preceded(
         tag("0x"),
-        map_res(hex_digit1, |s| u128::from_str_radix(s, 16)),
+        map_opt(hex_digit1, |s: &str| BigUint::parse_bytes(s.as_bytes(), 16)),
     )(input)
 }
 
-fn octal(input: &str) -> IResult<&str, u128> {
+fn octal(input: &str) -> IResult<&str, BigUint> {
     preceded(
         tag("0o"),
-        map_res(oct_digit1, |s| u128::from_str_radix(s, 8)),
+        map_opt(oct_digit1, |s: &str| BigUint::parse_bytes(s.as_bytes(), 8)),
     )(input)
 }
 
-fn binary(input: &str) -> IResult<&str, u128> {
+fn binary(input: &str) -> IResult<&str, BigUint> {
     preceded(
         tag("0b"),
-        map_res(bin_digit1, |s| u128::from_str_radix(s, 2)),
+        map_opt(bin_digit1, |s: &str| BigUint::parse_bytes(s.as_bytes(), 2)),
     )(input)
 }
 
-fn decimal(input: &str) -> IResult<&str, u128> {
-    map_res(digit1, u128::from_str)(input)
+fn decimal(input: &str) -> IResult<&str, BigUint> {
+    map_res(digit1, BigUint::from_str)(input)
 }
 
-fn number<T: TryFrom<u128>>(input: &str) -> IResult<&str, (T, IntegerWidth)> {
+fn number<T: TryFrom<BigUint>>(input: &str) -> IResult<&str, (T, IntegerWidth)> {
     let (input, value) = map_res(alt((hexadecimal, octal, binary, decimal)), T::try_from)(input)?;
     let (input, encoding) = opt(encoding)(input)?;
-    Ok((
-        input,
-        (
-            value,
-            match encoding {
-                Some(0) => IntegerWidth::Eight,
-                Some(1) => IntegerWidth::Sixteen,
-                Some(2) => IntegerWidth::ThirtyTwo,
-                Some(3) => IntegerWidth::SixtyFour,
-                None => IntegerWidth::Unknown,
-                Some(_) => unreachable!(),
-            },
-        ),
-    ))
+    Ok((input, (value, encoding.unwrap_or(IntegerWidth::Unknown))))
+}
+
+/// What is expected of an integer given the encoding indicator it has, when
+/// its value does not fit it.
+fn too_large(bitwidth: IntegerWidth, negative: bool) -> &'static str {
+    match (bitwidth, negative) {
+        (IntegerWidth::Zero, false) => "an integer below 24 for _i",
+        (IntegerWidth::Eight, false) => "an integer below 256 for _0",
+        (IntegerWidth::Sixteen, false) => "an integer below 65536 for _1",
+        (IntegerWidth::ThirtyTwo, false) => "an integer below 4294967296 for _2",
+        (_, false) => "an integer below 18446744073709551616 for an encoding indicator",
+        (IntegerWidth::Zero, true) => "an integer from -24 for _i",
+        (IntegerWidth::Eight, true) => "an integer from -256 for _0",
+        (IntegerWidth::Sixteen, true) => "an integer from -65536 for _1",
+        (IntegerWidth::ThirtyTwo, true) => "an integer from -4294967296 for _2",
+        (_, true) => "an integer from -18446744073709551616 for an encoding indicator",
+    }
+}
+
+/// An integer too large for a plain integer, which can only be represented
+/// as a bignum so cannot have an encoding indicator.
+fn bignum(value: BigInt, bitwidth: IntegerWidth) -> std::result::Result<DataItem, &'static str> {
+    if bitwidth == IntegerWidth::Unknown {
+        Ok(DataItem::from_integer(value))
+    } else {
+        Err(too_large(bitwidth, value.sign() == Sign::Minus))
+    }
 }
 
 fn integer(input: &str) -> IResult<&str, DataItem> {
-    map_res(number::<u64>, |(value, bitwidth)| {
-        Ok::<_, std::num::TryFromIntError>(DataItem::Integer {
-            value,
-            bitwidth: if bitwidth == IntegerWidth::Unknown && value <= 23 {
-                IntegerWidth::Zero
-            } else {
-                bitwidth
-            },
-        })
+    validate(number::<BigUint>, |(value, bitwidth)| {
+        match u64::try_from(&value) {
+            Ok(value) if !fits(value, bitwidth) => Err(too_large(bitwidth, false)),
+            Ok(value) => Ok(DataItem::Integer {
+                value,
+                bitwidth: if bitwidth == IntegerWidth::Unknown && value <= 23 {
+                    IntegerWidth::Zero
+                } else {
+                    bitwidth
+                },
+            }),
+            Err(_) => bignum(value.into(), bitwidth),
+        }
     })(input)
 }
 
 fn negative(input: &str) -> IResult<&str, DataItem> {
+    validate(
+        preceded(
+            tag("-"),
+            verify(number::<BigUint>, |(value, _)| *value > BigUint::zero()),
+        ),
+        |(value, bitwidth)| match u64::try_from(&value - 1u8) {
+            Ok(encoded) if !fits(encoded, bitwidth) => Err(too_large(bitwidth, true)),
+            Ok(encoded) => Ok(DataItem::Negative {
+                value: encoded,
+                bitwidth: if bitwidth == IntegerWidth::Unknown && encoded <= 23 {
+                    IntegerWidth::Zero
+                } else {
+                    bitwidth
+                },
+            }),
+            Err(_) => bignum(-BigInt::from(value), bitwidth),
+        },
+    )(input)
+}
+
+fn typed_array_element(input: &str) -> IResult<&str, &str> {
+    delimited(
+        multispace0,
+        recognize(pair(
+            opt(alt((char('+'), char('-')))),
+            alt((
+                tag("Infinity"),
+                tag("NaN"),
+                recognize(tuple((
+                    digit1,
+                    opt(pair(char('.'), digit1)),
+                    opt(tuple((
+                        alt((char('e'), char('E'))),
+                        opt(alt((char('+'), char('-')))),
+                        digit1,
+                    ))),
+                ))),
+            )),
+        )),
+        multispace0,
+    )(input)
+}
+
+/// A byte string containing the elements of a typed array, e.g. `f32le'1.5, 2.0'`
+fn typed_array_literal(input: &str) -> IResult<&str, Vec<u8>> {
+    map_res(
+        pair(
+            map_opt(alphanumeric1, TypedArray::from_prefix),
+            delimited(
+                tag("'"),
+                terminated(separated_list0(tag(","), typed_array_element), multispace0),
+                tag("'"),
+            ),
+        ),
+        |(array, elements)| array.encode(elements),
+    )(input)
+}
+
+fn hex4(input: &str) -> IResult<&str, u32> {
+    map_res(take_while_m_n(4, 4, |c: char| c.is_ascii_hexdigit()), |s| {
+        u32::from_str_radix(s, 16)
+    })(input)
+}
+
+/// A `\uXXXX` escape, where characters outside the basic multilingual plane
+/// are written as a surrogate pair `\uXXXX\uXXXX`, or the `\u{X...}` form.
+fn unicode_escape(input: &str) -> IResult<&str, char> {
     preceded(
-        tag("-"),
-        map_res(
-            verify(number::<u128>, |&(value, _)| value > 0),
-            |(value, bitwidth)| {
-                Ok::<_, std::num::TryFromIntError>(DataItem::Negative {
-                    value: u64::try_from(value - 1)?,
-                    bitwidth: if bitwidth == IntegerWidth::Unknown && value <= 24 {
-                        IntegerWidth::Zero
-                    } else {
-                        bitwidth
-                    },
-                })
-            },
+        tag("u"),
+        alt((
+            map_opt(
+                separated_pair(
+                    verify(hex4, |high| (0xd800..0xdc00).contains(high)),
+                    tag("\\u"),
+                    verify(hex4, |low| (0xdc00..0xe000).contains(low)),
+                ),
+                |(high, low)| char::from_u32(0x10000 + ((high - 0xd800) << 10 | (low - 0xdc00))),
+            ),
+            map_opt(hex4, char::from_u32),
+            map_opt(
+                delimited(
+                    tag("{"),
+                    map_res(hex_digit1, |s| u32::from_str_radix(s, 16)),
+                    tag("}"),
+                ),
+                char::from_u32,
+            ),
+        )),
+    )(input)
+}
+
+/// The escape sequences shared by text strings and single-quoted byte strings,
+/// following the backslash.
+fn escape(input: &str) -> IResult<&str, char> {
+    alt((
+        value('\\', tag("\\")),
+        value('"', tag("\"")),
+        value('\'', tag("'")),
+        value('/', tag("/")),
+        value('\u{8}', tag("b")),
+        value('\u{c}', tag("f")),
+        value('\n', tag("n")),
+        value('\r', tag("r")),
+        value('\t', tag("t")),
+        unicode_escape,
+    ))(input)
+}
+
+fn single_quoted(input: &str) -> IResult<&str, String> {
+    map(
+        delimited(
+            tag("'"),
+            opt(escaped_transform(
+                none_of("\\'"),
+                '\\',
+                // Nothing else can follow a backslash in a string
+                expected("an escape sequence", cut(escape)),
+            )),
+            expected("\"'\"", tag("'")),
         ),
+        Option::unwrap_or_default,
     )(input)
 }
 
@@ -227,78 +568,162 @@ fn definite_bytestring(input: &str) -> IResult<&str, Vec<u8>> {
         map_res(
             preceded(
                 tag("h"),
-                delimited(tag("'"), wrapws_strings(base16_digit0), tag("'")),
+                delimited(
+                    tag("'"),
+                    wrapws_strings(base16_digit0),
+                    expected("\"'\"", tag("'")),
+                ),
             ),
             |s| data_encoding::HEXLOWER_PERMISSIVE.decode(s.as_bytes()),
         ),
         map_res(
             preceded(
                 tag("b32"),
-                delimited(tag("'"), wrapws_strings(base32_digit0), tag("'")),
+                delimited(
+                    tag("'"),
+                    wrapws_strings(base32_digit0),
+                    expected("\"'\"", tag("'")),
+                ),
             ),
             |s| data_encoding::BASE32.decode(s.as_bytes()),
         ),
         map_res(
             preceded(
                 tag("h32"),
-                delimited(tag("'"), wrapws_strings(base32hex_digit0), tag("'")),
+                delimited(
+                    tag("'"),
+                    wrapws_strings(base32hex_digit0),
+                    expected("\"'\"", tag("'")),
+                ),
             ),
             |s| data_encoding::BASE32HEX.decode(s.as_bytes()),
         ),
         map_res(
             preceded(
                 tag("b64"),
-                delimited(tag("'"), wrapws_strings(base64url_digit0), tag("'")),
+                delimited(
+                    tag("'"),
+                    wrapws_strings(base64url_digit0),
+                    expected("\"'\"", tag("'")),
+                ),
             ),
             |s| data_encoding::BASE64URL_NOPAD.decode(s.as_bytes()),
         ),
         map_res(
             preceded(
                 tag("b64"),
-                delimited(tag("'"), wrapws_strings(base64_digit0), tag("'")),
+                delimited(
+                    tag("'"),
+                    wrapws_strings(base64_digit0),
+                    expected("\"'\"", tag("'")),
+                ),
             ),
             |s| data_encoding::BASE64.decode(s.as_bytes()),
         ),
+        typed_array_literal,
         map(
-            delimited(tag("<<"), separated_list0(tag(","), data_item), tag(">>")),
+            preceded(tag("<<"), list(data_item, expected("'>>'", tag(">>")))),
             |items| items.into_iter().flat_map(|item| item.to_bytes()).collect(),
         ),
-        map(
-            delimited(
-                tag("'"),
-                opt(escaped_transform(
-                    none_of("\\'"),
-                    '\\',
-                    alt((tag("\\"), tag("'"))),
-                )),
-                tag("'"),
-            ),
-            |s| s.unwrap_or_default().into_bytes(),
-        ),
+        map(single_quoted, String::into_bytes),
     )))(input)
 }
 
 fn concatenated_definite_bytestring(input: &str) -> IResult<&str, ByteString> {
-    map(many1(definite_bytestring), |data| ByteString {
-        data: data.into_iter().flatten().collect(),
-        bitwidth: IntegerWidth::Unknown,
-    })(input)
+    map_opt(
+        pair(many1(definite_bytestring), opt(encoding)),
+        |(data, bitwidth)| {
+            let data: Vec<u8> = data.into_iter().flatten().collect();
+            Some(ByteString {
+                bitwidth: length_width(data.len(), bitwidth)?,
+                data,
+            })
+        },
+    )(input)
 }
 
 fn indefinite_bytestring(input: &str) -> IResult<&str, DataItem> {
     map(
-        delimited(
-            tag("(_"),
-            separated_list0(tag(","), concatenated_definite_bytestring),
-            opt_comma_tag(")"),
+        preceded(
+            indefinite("("),
+            list(
+                concatenated_definite_bytestring,
+                expected("')'", opt_comma_tag(")")),
+            ),
         ),
         DataItem::IndefiniteByteString,
     )(input)
 }
 
+/// The parts of a concatenated string, with `None` for each elision `...`.
+fn elided_parts<'a>(
+    part: impl FnMut(&'a str) -> IResult<&'a str, Vec<u8>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Option<Vec<u8>>>> {
+    many1(alt((map(part, Some), map(wrapws(tag("...")), |_| None))))
+}
+
+/// Joins the parts of a concatenated string into a string using `string`, or
+/// if any parts were elided into a [`Tag::ELISION`] of the strings joined
+/// from each run of parts between the elisions. An encoding indicator
+/// following the parts applies to the length of the joined string, so cannot
+/// follow elided parts.
+fn elided_string(
+    parts: Vec<Option<Vec<u8>>>,
+    bitwidth: Option<IntegerWidth>,
+    string: fn(Vec<u8>, IntegerWidth) -> Option<DataItem>,
+) -> Option<DataItem> {
+    if parts.iter().all(Option::is_some) {
+        let data: Vec<u8> = parts.into_iter().flatten().flatten().collect();
+        let bitwidth = length_width(data.len(), bitwidth)?;
+        return string(data, bitwidth);
+    }
+    if parts.iter().all(Option::is_none) || bitwidth.is_some() {
+        return None;
+    }
+
+    let mut items = Vec::new();
+    let mut run: Option<Vec<u8>> = None;
+    for part in parts {
+        match part {
+            Some(part) => run.get_or_insert_with(Vec::new).extend(part),
+            None => {
+                if let Some(run) = run.take() {
+                    items.push(string(run, IntegerWidth::Unknown)?);
+                }
+                items.push(DataItem::Simple(Simple::NULL));
+            }
+        }
+    }
+    if let Some(run) = run {
+        items.push(string(run, IntegerWidth::Unknown)?);
+    }
+    Some(elision(DataItem::Array {
+        data: items,
+        bitwidth: Some(IntegerWidth::Unknown),
+    }))
+}
+
+/// An empty indefinite length string, written as an empty string followed by
+/// the indefinite length indicator `_`, e.g. `""_`, which unlike `(_)` says
+/// which type of string it is.
+fn empty_indefinite<'a>(empty: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, ()> {
+    map(tuple((tag(empty), not(encoding), tag("_"))), |_| ())
+}
+
 fn bytestring(input: &str) -> IResult<&str, DataItem> {
     alt((
-        map(concatenated_definite_bytestring, DataItem::ByteString),
+        map(
+            alt((empty_indefinite("''"), empty_indefinite("h''"))),
+            |()| DataItem::IndefiniteByteString(Vec::new()),
+        ),
+        map_opt(
+            pair(elided_parts(definite_bytestring), opt(encoding)),
+            |(parts, bitwidth)| {
+                elided_string(parts, bitwidth, |data, bitwidth| {
+                    Some(DataItem::ByteString(ByteString { data, bitwidth }))
+                })
+            },
+        ),
         indefinite_bytestring,
     ))(input)
 }
@@ -310,17 +735,18 @@ fn definite_textstring(input: &str) -> IResult<&str, String> {
             opt(escaped_transform(
                 none_of("\\\""),
                 '\\',
-                alt((tag("\\"), tag("\""))),
+                // Nothing else can follow a backslash in a string
+                expected("an escape sequence", cut(escape)),
             )),
-            tag("\""),
+            expected("'\"'", tag("\"")),
         ),
         |data| data.unwrap_or_default(),
     ))(input)
 }
 
 fn concatenated_definite_textstring(input: &str) -> IResult<&str, TextString> {
-    map(
-        pair(
+    map_opt(
+        tuple((
             definite_textstring,
             map_res(
                 many0(alt((
@@ -329,20 +755,26 @@ fn concatenated_definite_textstring(input: &str) -> IResult<&str, TextString> {
                 ))),
                 |rest| String::from_utf8(rest.into_iter().flatten().collect()),
             ),
-        ),
-        |(first, rest)| TextString {
-            data: first + &rest,
-            bitwidth: IntegerWidth::Unknown,
+            opt(encoding),
+        )),
+        |(first, rest, bitwidth)| {
+            let data = first + &rest;
+            Some(TextString {
+                bitwidth: length_width(data.len(), bitwidth)?,
+                data,
+            })
         },
     )(input)
 }
 
 fn indefinite_textstring(input: &str) -> IResult<&str, DataItem> {
     map(
-        delimited(
-            tag("(_"),
-            separated_list0(tag(","), concatenated_definite_textstring),
-            opt_comma_tag(")"),
+        preceded(
+            indefinite("("),
+            list(
+                concatenated_definite_textstring,
+                expected("')'", opt_comma_tag(")")),
+            ),
         ),
         DataItem::IndefiniteTextString,
     )(input)
@@ -350,31 +782,50 @@ fn indefinite_textstring(input: &str) -> IResult<&str, DataItem> {
 
 fn textstring(input: &str) -> IResult<&str, DataItem> {
     alt((
-        map(concatenated_definite_textstring, DataItem::TextString),
+        map(empty_indefinite("\"\""), |()| {
+            DataItem::IndefiniteTextString(Vec::new())
+        }),
+        map_opt(
+            pair(
+                elided_parts(alt((
+                    map(definite_textstring, String::into_bytes),
+                    definite_bytestring,
+                ))),
+                opt(encoding),
+            ),
+            |(parts, bitwidth)| {
+                elided_string(parts, bitwidth, |data, bitwidth| {
+                    Some(DataItem::TextString(TextString {
+                        data: String::from_utf8(data).ok()?,
+                        bitwidth,
+                    }))
+                })
+            },
+        ),
         indefinite_textstring,
     ))(input)
 }
 
 fn definite_array(input: &str) -> IResult<&str, DataItem> {
-    map(
-        delimited(
-            wrapws(tag("[")),
-            separated_list0(tag(","), data_item),
-            opt_comma_tag("]"),
+    map_opt(
+        pair(
+            definite("["),
+            list(data_item, expected("']'", opt_comma_tag("]"))),
         ),
-        |data| DataItem::Array {
-            data,
-            bitwidth: Some(IntegerWidth::Unknown),
+        |(bitwidth, data)| {
+            Some(DataItem::Array {
+                bitwidth: Some(length_width(data.len(), bitwidth)?),
+                data,
+            })
         },
     )(input)
 }
 
 fn indefinite_array(input: &str) -> IResult<&str, DataItem> {
     map(
-        delimited(
-            wrapws(tag("[_")),
-            separated_list0(tag(","), data_item),
-            opt_comma_tag("]"),
+        preceded(
+            indefinite("["),
+            list(data_item, expected("']'", opt_comma_tag("]"))),
         ),
         |data| DataItem::Array {
             data,
@@ -387,26 +838,41 @@ fn array(input: &str) -> IResult<&str, DataItem> {
     alt((definite_array, indefinite_array))(input)
 }
 
+/// A key and value, or an elision `...` of an entry as an elided key and value
+fn map_entry(input: &str) -> IResult<&str, (DataItem, DataItem)> {
+    alt((
+        separated_pair(
+            data_item,
+            expected("':' after map key", tag(":")),
+            data_item,
+        ),
+        map(wrapws(tag("...")), |_| {
+            let elided = elision(DataItem::Simple(Simple::NULL));
+            (elided.clone(), elided)
+        }),
+    ))(input)
+}
+
 fn definite_map(input: &str) -> IResult<&str, DataItem> {
-    map(
-        delimited(
-            wrapws(tag("{")),
-            separated_list0(tag(","), separated_pair(data_item, tag(":"), data_item)),
-            opt_comma_tag("}"),
+    map_opt(
+        pair(
+            definite("{"),
+            list(map_entry, expected("'}'", opt_comma_tag("}"))),
         ),
-        |data| DataItem::Map {
-            data,
-            bitwidth: Some(IntegerWidth::Unknown),
+        |(bitwidth, data)| {
+            Some(DataItem::Map {
+                bitwidth: Some(length_width(data.len(), bitwidth)?),
+                data,
+            })
         },
     )(input)
 }
 
 fn indefinite_map(input: &str) -> IResult<&str, DataItem> {
     map(
-        delimited(
-            wrapws(tag("{_")),
-            separated_list0(tag(","), separated_pair(data_item, tag(":"), data_item)),
-            opt_comma_tag("}"),
+        preceded(
+            indefinite("{"),
+            list(map_entry, expected("'}'", opt_comma_tag("}"))),
         ),
         |data| DataItem::Map {
             data,
@@ -420,8 +886,13 @@ fn data_map(input: &str) -> IResult<&str, DataItem> {
 }
 
 fn tagged(input: &str) -> IResult<&str, DataItem> {
-    let (input, (tag_, bitwidth)) = number::<u64>(input)?;
-    let (input, value) = delimited(tag("("), data_item, tag(")"))(input)?;
+    let (input, (tag_, bitwidth)) =
+        verify(number::<u64>, |&(tag_, bitwidth)| fits(tag_, bitwidth))(input)?;
+    // Nothing else can follow a number and opening parenthesis
+    let (input, value) = preceded(
+        tag("("),
+        cut(terminated(data_item, expected("')'", tag(")")))),
+    )(input)?;
     Ok((
         input,
         DataItem::Tag {
@@ -451,7 +922,7 @@ fn recognize_decimal_float(input: &str) -> IResult<&str, &str> {
 fn hexadecimal_float(input: &str) -> IResult<&str, f64> {
     let (input, sign) = opt(alt((char('+'), char('-'))))(input)?;
     let (input, value) = hexadecimal(input)?;
-    let mut value = value as f64;
+    let mut value = value.to_f64().unwrap_or(f64::INFINITY);
     let (input, radix) = opt(preceded(
         tag("."),
         map_res(hex_digit1, |s| {
@@ -462,7 +933,7 @@ fn hexadecimal_float(input: &str) -> IResult<&str, f64> {
         value += radix as f64 / (16.0f64).powi(radix_len as i32);
     }
     let (input, (exp_sign, exponent)) = preceded(tag("p"), pair(opt(char('-')), decimal))(input)?;
-    let mut exponent = exponent as f64;
+    let mut exponent = exponent.to_f64().unwrap_or(f64::INFINITY);
     if exp_sign == Some('-') {
         exponent *= -1.0;
     }
@@ -485,58 +956,116 @@ fn float_value(input: &str) -> IResult<&str, f64> {
 
 fn float(input: &str) -> IResult<&str, DataItem> {
     let (input, value) = float_value(input)?;
-    let (input, encoding) = opt(verify(encoding, |&e| e > 0))(input)?;
+    let (input, bitwidth) = opt(map_opt(encoding, |encoding| match encoding {
+        IntegerWidth::Sixteen => Some(FloatWidth::Sixteen),
+        IntegerWidth::ThirtyTwo => Some(FloatWidth::ThirtyTwo),
+        IntegerWidth::SixtyFour => Some(FloatWidth::SixtyFour),
+        _ => None,
+    }))(input)?;
     Ok((
         input,
-        DataItem::Float {
-            value,
-            bitwidth: match encoding {
-                Some(1) => FloatWidth::Sixteen,
-                Some(2) => FloatWidth::ThirtyTwo,
-                Some(3) => FloatWidth::SixtyFour,
-                Some(_) => unreachable!(),
-                None => FloatWidth::Unknown,
-            },
-        },
+        DataItem::Float(Float::new(value, bitwidth.unwrap_or(FloatWidth::Unknown))),
     ))
 }
 
 fn simple(input: &str) -> IResult<&str, DataItem> {
-    map(
-        alt((
-            value(Simple::FALSE, tag("false")),
-            value(Simple::TRUE, tag("true")),
-            value(Simple::NULL, tag("null")),
-            value(Simple::UNDEFINED, tag("undefined")),
-            map(
-                preceded(
-                    tag("simple"),
-                    map_res(delimited(tag("("), digit1, tag(")")), u8::from_str),
+    validate(
+        pair(
+            alt((
+                value(Some(Simple::FALSE), tag("false")),
+                value(Some(Simple::TRUE), tag("true")),
+                value(Some(Simple::NULL), tag("null")),
+                value(Some(Simple::UNDEFINED), tag("undefined")),
+                map(
+                    preceded(tag("simple"), delimited(tag("("), digit1, tag(")"))),
+                    |value| u8::from_str(value).ok().map(Simple),
                 ),
-                Simple,
-            ),
-        )),
-        DataItem::Simple,
+            )),
+            opt(encoding),
+        ),
+        // Each simple value has a single encoding, so only its own encoding
+        // indicator is accepted
+        |(simple, bitwidth)| {
+            let simple = simple.ok_or("a simple value below 256")?;
+            match bitwidth {
+                None => Ok(DataItem::Simple(simple)),
+                Some(IntegerWidth::Zero) if simple.0 < 24 => Ok(DataItem::Simple(simple)),
+                Some(IntegerWidth::Zero) => Err("a simple value below 24 for _i"),
+                Some(IntegerWidth::Eight) if simple.0 >= 32 => Ok(DataItem::Simple(simple)),
+                Some(IntegerWidth::Eight) => Err("a simple value from 32 for _0"),
+                Some(_) => Err("a simple value with no encoding indicator, _i or _0"),
+            }
+        },
     )(input)
 }
 
-fn data_item(input: &str) -> IResult<&str, DataItem> {
-    context(
-        "data item",
-        wrapws(alt((
-            context("float", float),
-            context("tagged", tagged),
-            context("integer", integer),
-            context("negative", negative),
-            context("bytestring", bytestring),
-            context("textstring", textstring),
-            context("array", array),
-            context("map", data_map),
-            context("simple", simple),
-        ))),
+fn elision(value: DataItem) -> DataItem {
+    DataItem::Tag {
+        tag: Tag::ELISION,
+        bitwidth: IntegerWidth::Unknown,
+        value: Box::new(value),
+    }
+}
+
+/// An elided data item `...`
+fn elided(input: &str) -> IResult<&str, DataItem> {
+    map(tag("..."), |_| elision(DataItem::Simple(Simple::NULL)))(input)
+}
+
+/// An application-extension literal, e.g. `dt'2023-01-01T00:00:00Z'`
+fn app_literal(input: &str) -> IResult<&str, DataItem> {
+    validate(
+        pair(
+            map_opt(alphanumeric1, AppLiteral::from_prefix),
+            single_quoted,
+        ),
+        |(app, text)| app.parse(&text).ok_or(app.expected()),
     )(input)
 }
 
+fn data_item(input: &str) -> IResult<&str, DataItem> {
+    wrapws(expected(
+        "a data item",
+        alt((
+            float,
+            tagged,
+            integer,
+            negative,
+            bytestring,
+            textstring,
+            app_literal,
+            array,
+            data_map,
+            simple,
+            elided,
+        )),
+    ))(input)
+}
+
+/// Describes a parse error at `offset` in `text`, with the line it is on
+/// and a caret under the error.
+fn parse_error(text: &str, offset: usize, expected: &[&str]) -> String {
+    let (before, after) = text.split_at(offset);
+    let start = before.rfind('\n').map_or(0, |i| i + 1);
+    let end = after.find('\n').map_or(text.len(), |i| offset + i);
+    let line = before.matches('\n').count() + 1;
+    let column = before[start..].chars().count() + 1;
+    let found = after
+        .chars()
+        .next()
+        .map_or_else(|| "end of input".to_owned(), |c| format!("{c:?}"));
+    // Keep tabs so the caret lines up with the snippet
+    let indent: String = before[start..]
+        .chars()
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    format!(
+        "line {line}, column {column}: expected {}, found {found}\n{}\n{indent}^",
+        expected.join(" or "),
+        text[start..end].trim_end_matches('\r'),
+    )
+}
+
 /// Parse a string containing a diagnostic notation encoded CBOR data item.
 ///
 /// ⚠️ Take special note of the warning from [RFC 7049 § 6][RFC 6]:
@@ -555,6 +1084,13 @@ fn data_item(input: &str) -> IResult<&str, DataItem> {
 ///
 /// [RFC 6]: https://tools.ietf.org/html/rfc7049#section-6
 ///
+/// The application-extension literals `dt''`, `DT''`, `ip''`, `uuid''` and
+/// `float''` of the [CBOR EDN draft][EDN] are parsed to the items they
+/// represent. Extensions which compute their value are not supported, so
+/// `hash''` is an error.
+///
+/// [EDN]: https://datatracker.ietf.org/doc/draft-ietf-cbor-edn-literals/
+///
 /// # Examples
 ///
 /// ```rust
@@ -572,10 +1108,54 @@ fn data_item(input: &str) -> IResult<&str, DataItem> {
 ///     });
 /// ```
 pub fn parse_diag(text: impl AsRef<str>) -> Result<DataItem> {
-    let (remaining, parsed) =
-        data_item(text.as_ref()).map_err(|e| format!("Parsing error ({e:?})"))?;
-    if !remaining.is_empty() {
-        return Err(format!("Remaining text ({remaining:?})").into());
+    parse_all(text.as_ref(), data_item)
+}
+
+/// Parse a string containing a sequence of diagnostic notation encoded CBOR
+/// data items (RFC 8742), separated by commas as in an array without the
+/// brackets. An empty string or one of only whitespace and comments is an
+/// empty sequence.
+///
+/// The same warning applies as to [`parse_diag`].
+///
+/// # Examples
+///
+/// ```rust
+/// use cbor_diag::{DataItem, IntegerWidth};
+///
+/// assert_eq!(
+///     cbor_diag::parse_diag_seq("1, 32_0").unwrap(),
+///     vec![
+///         DataItem::Integer { value: 1, bitwidth: IntegerWidth::Zero },
+///         DataItem::Integer { value: 32, bitwidth: IntegerWidth::Eight },
+///     ]);
+///
+/// assert_eq!(cbor_diag::parse_diag_seq(" # nothing").unwrap(), vec![]);
+/// ```
+pub fn parse_diag_seq(text: impl AsRef<str>) -> Result<Vec<DataItem>> {
+    parse_all(
+        text.as_ref(),
+        preceded(
+            ws_or_comment::<()>,
+            list(data_item, expected("end of input", eof)),
+        ),
+    )
+}
+
+/// Runs `parser` over the whole of `text`, describing where and why it
+/// failed if it did.
+fn parse_all<'a, T>(
+    text: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+) -> Result<T> {
+    match terminated(parser, expected("end of input", eof))(text) {
+        Ok((_, parsed)) => Ok(parsed),
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => match err.furthest {
+            Some(Expected {
+                remaining, what, ..
+            }) => Err(parse_error(text, text.len() - remaining, &what).into()),
+            None => Err("Parsing error".into()),
+        },
+        Err(err) => Err(format!("Parsing error ({err:?})").into()),
     }
-    Ok(parsed)
 }
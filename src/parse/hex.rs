@@ -0,0 +1,294 @@
+//! Decoding the many ways bytes get written as hex, from plain hex streams to
+//! the output of hex dump tools and C byte arrays.
+
+use crate::Result;
+
+/// The format of the text given to [`parse_hex_with`](crate::parse_hex_with).
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Default)]
+pub enum HexInputFormat {
+    /// Detect which of the other formats the text is in.
+    #[default]
+    Auto,
+
+    /// Hex digits, ignoring whitespace and comments from `#` to the end of
+    /// the line, such as annotated hex or a Wireshark "Copy as Hex Stream".
+    Plain,
+
+    /// The output of `xxd`, each line an offset followed by `:`, the hex
+    /// digits in groups, then the bytes as text after two spaces.
+    Xxd,
+
+    /// The output of `hexdump -C`, `od -A x -t x1z` or a Wireshark "Copy as
+    /// Hex Dump", each line an offset followed by the bytes as pairs of hex
+    /// digits, then optionally the bytes as text between `|`s, `>` and `<`,
+    /// or after three spaces. Repeated lines elided as `*` are restored
+    /// using the offsets, in hex, octal or decimal.
+    Dump,
+
+    /// Bytes written with `0x` prefixes separated by commas or whitespace,
+    /// optionally as a C array initializer such as `{0xa1, 0x01}` with C and
+    /// `#` comments. A prefix followed by more than two hex digits is a run
+    /// of bytes, e.g. `0xa101`.
+    Prefixed,
+}
+
+impl HexInputFormat {
+    /// The format of `text`, looking for the offsets starting each line of a
+    /// dump then `0x` prefixes outside comments. Plain hex can start with a
+    /// run of digits that looks like an offset, so a dump must also show its
+    /// bytes as text or have offsets following the bytes of each line,
+    /// unless the text is not plain hex.
+    fn detect(text: &str) -> HexInputFormat {
+        let first_token = text.split_whitespace().next().unwrap_or_default();
+        if first_token.strip_suffix(':').is_some_and(is_offset) {
+            return HexInputFormat::Xxd;
+        }
+        let dump = dump_lines(text);
+        if let Some((lines, text_shown)) = &dump {
+            if *text_shown || offset_radix(lines).is_some() {
+                return HexInputFormat::Dump;
+            }
+        }
+        let uncommented = strip_comments(text, true);
+        if uncommented.contains("0x") || uncommented.contains("0X") {
+            return HexInputFormat::Prefixed;
+        }
+        if dump.is_some() && plain(text).is_err() {
+            return HexInputFormat::Dump;
+        }
+        HexInputFormat::Plain
+    }
+}
+
+fn is_hex(digits: &str) -> bool {
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn is_offset(offset: &str) -> bool {
+    offset.len() >= 4 && is_hex(offset)
+}
+
+/// The offset and bytes of a line of a dump, `None` for a repeated line
+/// marker.
+type DumpLine<'a> = Option<(&'a str, Vec<u8>)>;
+
+/// The lines of `text` if every line starts with an offset of the same
+/// width, with at least one followed by a byte, along with whether any line
+/// shows its bytes as text.
+fn dump_lines(text: &str) -> Option<(Vec<DumpLine<'_>>, bool)> {
+    let mut width = None;
+    let mut lines = Vec::new();
+    let mut text_shown = false;
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+        if line == "*" {
+            lines.push(None);
+            continue;
+        }
+        let (offset, line) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+        if !is_offset(offset) || *width.get_or_insert(offset.len()) != offset.len() {
+            return None;
+        }
+        text_shown |= !split_dump_line(line).1.trim().is_empty();
+        lines.push(Some((offset, dump_line(line).ok()?)));
+    }
+    lines
+        .iter()
+        .flatten()
+        .any(|(_, bytes)| !bytes.is_empty())
+        .then_some((lines, text_shown))
+}
+
+/// Removes comments from `#` to the end of each line and, if `c` is set,
+/// C comments.
+fn strip_comments(text: &str, c: bool) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(|ch| ch == '#' || (c && ch == '/')) {
+        let (before, comment) = rest.split_at(start);
+        stripped.push_str(before);
+        let end = if comment.starts_with("/*") {
+            comment.find("*/").map_or(comment.len(), |end| end + 2)
+        } else if comment.starts_with('#') || comment.starts_with("//") {
+            comment.find('\n').unwrap_or(comment.len())
+        } else {
+            stripped.push('/');
+            rest = &comment[1..];
+            continue;
+        };
+        // Comments still separate the bytes either side of them
+        stripped.push(' ');
+        rest = &comment[end..];
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+fn decode_hex(digits: &str) -> Result<Vec<u8>> {
+    Ok(data_encoding::HEXLOWER_PERMISSIVE.decode(digits.as_bytes())?)
+}
+
+fn plain(text: &str) -> Result<Vec<u8>> {
+    let digits: String = strip_comments(text, false)
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    decode_hex(&digits)
+}
+
+/// The bytes of a line of `xxd` output following the offset.
+fn xxd_line(line: &str) -> Result<Vec<u8>> {
+    let line = line.trim_start();
+    let hex = line.split("  ").next().unwrap_or_default();
+    decode_hex(&hex.replace(' ', ""))
+}
+
+/// Splits a line of a hex dump following the offset into the bytes as hex
+/// and as text.
+fn split_dump_line(line: &str) -> (&str, &str) {
+    let line = line.trim_start();
+    let end = [line.find(['|', '>']), line.find("   ")]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(line.len());
+    line.split_at(end)
+}
+
+/// The bytes of a line of a hex dump following the offset.
+fn dump_line(line: &str) -> Result<Vec<u8>> {
+    split_dump_line(line)
+        .0
+        .split_whitespace()
+        .map(|byte| {
+            if byte.len() == 2 && is_hex(byte) {
+                Ok(u8::from_str_radix(byte, 16).unwrap())
+            } else {
+                Err(format!("Invalid byte in hex dump ({byte:?})").into())
+            }
+        })
+        .collect()
+}
+
+/// The radix of the offsets of a dump, found from the first pair of adjacent
+/// lines as hex, octal (the default of `od`) or decimal, whichever makes the
+/// offsets differ by the length of the first line. `None` if there is no
+/// such pair.
+fn offset_radix(lines: &[DumpLine<'_>]) -> Option<u32> {
+    let (first, second, len) = lines.windows(2).find_map(|pair| match pair {
+        [Some((first, bytes)), Some((second, _))] => Some((*first, *second, bytes.len() as u64)),
+        _ => None,
+    })?;
+    [16, 8, 10].into_iter().find(|&radix| {
+        let offset = |offset| u64::from_str_radix(offset, radix).ok();
+        offset(first)
+            .zip(offset(second))
+            .is_some_and(|(first, second)| second.checked_sub(first) == Some(len))
+    })
+}
+
+/// The most bytes a dump may expand to through repeated lines, as the offset
+/// after a `*` could otherwise ask for any amount of memory.
+const MAX_REPEATED_LEN: u64 = 1 << 26;
+
+/// Joins the bytes of each line of a dump, following the offset separated
+/// from the line by `separator`, repeating the line before each `*` up to
+/// the offset of the line after it.
+fn lines(
+    text: &str,
+    separator: fn(char) -> bool,
+    line_bytes: fn(&str) -> Result<Vec<u8>>,
+) -> Result<Vec<u8>> {
+    let lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            if line == "*" {
+                return Ok(None);
+            }
+            let (offset, line) = line.split_once(separator).unwrap_or((line, ""));
+            Ok(Some((offset, line_bytes(line)?)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let radix = offset_radix(&lines).unwrap_or(16);
+    let offset = |offset: &str| {
+        u64::from_str_radix(offset, radix).map_err(|_| format!("Invalid offset ({offset:?})"))
+    };
+    let mut bytes = Vec::new();
+    let mut start = None;
+    let mut last: &[u8] = &[];
+    let mut repeated = false;
+    for line in &lines {
+        let Some((line_offset, line)) = line else {
+            repeated = true;
+            continue;
+        };
+        if repeated {
+            let start = start.ok_or("Repeated line marker before any line")?;
+            let end = offset(line_offset)?
+                .checked_sub(start)
+                .ok_or("Offset before the start of the dump")?;
+            if end > MAX_REPEATED_LEN {
+                return Err(format!(
+                    "Repeated lines expand beyond {MAX_REPEATED_LEN} bytes ({line_offset})"
+                )
+                .into());
+            }
+            while (bytes.len() as u64) < end && !last.is_empty() {
+                bytes.extend_from_slice(last);
+            }
+            if bytes.len() as u64 != end {
+                return Err(
+                    format!("Offset does not follow repeated lines ({line_offset})").into(),
+                );
+            }
+            repeated = false;
+        }
+        if start.is_none() {
+            start = Some(offset(line_offset)?);
+        }
+        last = line;
+        bytes.extend_from_slice(line);
+    }
+    Ok(bytes)
+}
+
+fn prefixed(text: &str) -> Result<Vec<u8>> {
+    let text = strip_comments(text, true);
+    let elements = match (text.find('{'), text.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &text[start + 1..end],
+        _ => &text,
+    };
+    let mut bytes = Vec::new();
+    for element in elements.split(|c: char| c == ',' || c.is_whitespace()) {
+        if element.is_empty() {
+            continue;
+        }
+        let digits = element
+            .strip_prefix("0x")
+            .or_else(|| element.strip_prefix("0X"))
+            .filter(|digits| is_hex(digits))
+            .ok_or_else(|| format!("Invalid prefixed hex byte ({element:?})"))?;
+        if digits.len() == 1 {
+            bytes.push(u8::from_str_radix(digits, 16).unwrap());
+        } else {
+            bytes.extend(decode_hex(digits)?);
+        }
+    }
+    Ok(bytes)
+}
+
+/// Decodes the bytes written as hex in `text` in the given format.
+pub(super) fn decode(text: &str, format: HexInputFormat) -> Result<Vec<u8>> {
+    match format {
+        HexInputFormat::Auto => decode(text, HexInputFormat::detect(text)),
+        HexInputFormat::Plain => plain(text),
+        HexInputFormat::Xxd => lines(text, |c| c == ':', xxd_line),
+        HexInputFormat::Dump => lines(text, char::is_whitespace, dump_line),
+        HexInputFormat::Prefixed => prefixed(text),
+    }
+}
@@ -1,24 +1,20 @@
-use crate::{DataItem, Result};
+use crate::{DataItem, Magic, Result};
 
 mod binary;
 mod diag;
+mod hex;
 
-pub use self::binary::{parse_bytes, parse_bytes_partial};
-pub use self::diag::parse_diag;
-
-fn remove_comments(hex: impl AsRef<str>) -> String {
-    hex.as_ref()
-        .lines()
-        .map(|line| line.split('#').next().unwrap().replace(' ', ""))
-        .collect()
-}
+pub use self::binary::{parse_bytes, parse_bytes_partial, parse_bytes_seq};
+pub use self::diag::{parse_diag, parse_diag_seq};
+pub use self::hex::HexInputFormat;
 
 /// Parse a string containing a hex encoded CBOR data item.
 ///
 /// The provided string may contain comments, where a comment is started with a
-/// `#` character and proceeds until the end of the line. Any spaces in the
+/// `#` character and proceeds until the end of the line. Any whitespace in the
 /// string will also be ignored, but any other non-hex characters will cause an
-/// error.
+/// error, unless the string is detected as one of the other formats of
+/// [`HexInputFormat`] such as the output of `xxd` or a C byte array.
 ///
 /// # Examples
 ///
@@ -41,7 +37,122 @@ fn remove_comments(hex: impl AsRef<str>) -> String {
 ///     });
 /// ```
 pub fn parse_hex(hex: impl AsRef<str>) -> Result<DataItem> {
-    let hex = remove_comments(hex);
-    let bytes = data_encoding::HEXLOWER_PERMISSIVE.decode(hex.as_bytes())?;
+    parse_hex_with(hex, HexInputFormat::Auto)
+}
+
+/// Parse a string containing a hex encoded CBOR data item written in the
+/// given format, see [`HexInputFormat`] for the formats supported.
+///
+/// # Examples
+///
+/// ```rust
+/// use cbor_diag::{DataItem, HexInputFormat, IntegerWidth};
+///
+/// let item = DataItem::Map {
+///     data: vec![(
+///         DataItem::Integer { value: 1, bitwidth: IntegerWidth::Zero },
+///         DataItem::Integer { value: 2, bitwidth: IntegerWidth::Zero },
+///     )],
+///     bitwidth: Some(IntegerWidth::Zero),
+/// };
+///
+/// assert_eq!(
+///     cbor_diag::parse_hex_with(
+///         "00000000: a101 02                                  ...",
+///         HexInputFormat::Xxd,
+///     ).unwrap(),
+///     item);
+///
+/// assert_eq!(
+///     cbor_diag::parse_hex_with("{0xa1, 0x01, 0x02}", HexInputFormat::Prefixed).unwrap(),
+///     item);
+/// ```
+pub fn parse_hex_with(hex: impl AsRef<str>, format: HexInputFormat) -> Result<DataItem> {
+    parse_bytes(hex::decode(hex.as_ref(), format)?)
+}
+
+/// Parse a string containing a hex encoded sequence of CBOR data items
+/// (RFC 8742), each directly following the previous.
+///
+/// The same formats are accepted as by [`parse_hex`], so the annotated hex
+/// of each item can be given one after the other.
+///
+/// # Examples
+///
+/// ```rust
+/// use cbor_diag::{DataItem, IntegerWidth};
+///
+/// assert_eq!(
+///     cbor_diag::parse_hex_seq("
+///         01    # unsigned(1)
+///
+///         18 20 # unsigned(32)
+///     ").unwrap(),
+///     vec![
+///         DataItem::Integer { value: 1, bitwidth: IntegerWidth::Zero },
+///         DataItem::Integer { value: 32, bitwidth: IntegerWidth::Eight },
+///     ]);
+/// ```
+pub fn parse_hex_seq(hex: impl AsRef<str>) -> Result<Vec<DataItem>> {
+    parse_bytes_seq(hex::decode(hex.as_ref(), HexInputFormat::Auto)?)
+}
+
+/// Options controlling the behaviour of [`parse_bytes_with`].
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Whether to strip a [magic number](Magic) from the start of the data
+    /// before parsing it; the data item wrapped by
+    /// [`Tag::SELF_DESCRIBE_CBOR`](crate::Tag::SELF_DESCRIBE_CBOR) or a
+    /// content-format tag is returned in place of the tag, repeatedly if it
+    /// starts with another magic number, and the single item of a sequence
+    /// is returned after its `'BOR'` item. Data labeled as not being CBOR or
+    /// as a sequence of other than one item is an error.
+    pub strip_magic: bool,
+}
+
+/// Parse a string containing a binary encoded CBOR data item, with the given
+/// options.
+///
+/// # Examples
+///
+/// ```rust
+/// use cbor_diag::{DataItem, IntegerWidth, ParseOptions};
+///
+/// let options = ParseOptions { strip_magic: true };
+///
+/// assert_eq!(
+///     cbor_diag::parse_bytes_with(b"\xd9\xd9\xf7\x01", &options).unwrap(),
+///     DataItem::Integer { value: 1, bitwidth: IntegerWidth::Zero });
+///
+/// assert_eq!(
+///     cbor_diag::parse_bytes_with(b"\xd9\xd9\xf8\x43BOR\x01", &options).unwrap(),
+///     DataItem::Integer { value: 1, bitwidth: IntegerWidth::Zero });
+/// ```
+pub fn parse_bytes_with(bytes: impl AsRef<[u8]>, options: &ParseOptions) -> Result<DataItem> {
+    let mut bytes = bytes.as_ref();
+    if options.strip_magic {
+        // A tagged item may itself start with another magic number, such as a
+        // content-format tag within a self-described item
+        while let Some((magic, len)) = Magic::detect(bytes) {
+            bytes = &bytes[len..];
+            match magic {
+                Magic::SelfDescribed | Magic::ContentFormat(_) => {}
+                Magic::Sequence | Magic::ContentFormatSequence(_) => {
+                    let mut items = parse_bytes_seq(bytes)?;
+                    if items.len() != 1 {
+                        return Err(format!(
+                            "invalid cbor, data is labeled as a sequence of {} items",
+                            items.len()
+                        )
+                        .into());
+                    }
+                    return Ok(items.remove(0));
+                }
+                Magic::LabeledNonCbor => {
+                    return Err("invalid cbor, data is labeled as not being cbor".into())
+                }
+            }
+        }
+    }
     parse_bytes(bytes)
 }
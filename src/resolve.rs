@@ -0,0 +1,305 @@
+//! Expansion of [value sharing][] ([`Tag::SHAREABLE`] and
+//! [`Tag::SHARED_REF`]) and [string references][] ([`Tag::STRINGREF_NAMESPACE`]
+//! and [`Tag::STRINGREF`]).
+//!
+//! [value sharing]: http://cbor.schmorp.de/value-sharing
+//! [string references]: http://cbor.schmorp.de/stringref
+
+use crate::{ByteString, DataItem, IntegerWidth, Result, Simple, Tag, TextString};
+
+/// The most items [`DataItem::resolve_references`] copies in place of shared
+/// references, as a few nested references can expand to a tree far larger
+/// than the data.
+const MAX_EXPANDED_ITEMS: usize = 1 << 20;
+
+/// The state of a traversal that tracks which values and strings are
+/// available to be referenced, in the order they are encountered in the
+/// encoded data.
+#[derive(Debug, Default)]
+pub(crate) struct References {
+    /// Shared values, `None` while the value itself is still being traversed.
+    /// Unless they are being expanded these keep the references to other
+    /// shared values within them.
+    shared: Vec<Option<DataItem>>,
+    /// The strings of each currently open stringref namespace, innermost last.
+    namespaces: Vec<Vec<DataItem>>,
+    /// How many more items may be copied in place of shared references, or
+    /// `None` if they are kept as references instead of being expanded.
+    expansion_budget: Option<usize>,
+}
+
+/// The number of items within `value`, including itself.
+fn size(value: &DataItem) -> usize {
+    1 + match value {
+        DataItem::Tag { value, .. } => size(value),
+        DataItem::Array { data, .. } => data.iter().map(size).sum(),
+        DataItem::Map { data, .. } => data
+            .iter()
+            .map(|(key, value)| size(key) + size(value))
+            .sum(),
+        _ => 0,
+    }
+}
+
+/// The minimum length a string must have to be added to a stringref namespace
+/// that currently contains `count` strings.
+fn minimum_string_length(count: usize) -> usize {
+    let count = count as u64;
+    if count < 24 {
+        3
+    } else if count < 256 {
+        4
+    } else if count < 65536 {
+        5
+    } else if count < 1 << 32 {
+        7
+    } else {
+        11
+    }
+}
+
+impl References {
+    /// Reserves the index of a newly encountered shareable value, which will
+    /// be available once [`References::set_shared`] is called.
+    pub(crate) fn share(&mut self) -> usize {
+        self.shared.push(None);
+        self.shared.len() - 1
+    }
+
+    pub(crate) fn set_shared(&mut self, index: usize, value: DataItem) {
+        self.shared[index] = Some(value);
+    }
+
+    /// Looks up a shared value, returns `Some(None)` if the value is still
+    /// being traversed.
+    fn lookup_shared(&self, index: u64) -> Option<Option<&DataItem>> {
+        usize::try_from(index)
+            .ok()
+            .and_then(|i| self.shared.get(i))
+            .map(Option::as_ref)
+    }
+
+    fn shared(&self, index: u64) -> Result<&DataItem> {
+        match self.lookup_shared(index) {
+            Some(Some(value)) => Ok(value),
+            Some(None) => Err(format!("cyclic reference to shared value {index}").into()),
+            None => Err(format!("reference to unknown shared value {index}").into()),
+        }
+    }
+
+    pub(crate) fn enter_namespace(&mut self) {
+        self.namespaces.push(Vec::new());
+    }
+
+    pub(crate) fn exit_namespace(&mut self) {
+        self.namespaces.pop();
+    }
+
+    /// Adds `value` to the current stringref namespace if it is a definite
+    /// length string long enough to be referenced.
+    pub(crate) fn record_string(&mut self, value: &DataItem) {
+        let len = match value {
+            DataItem::ByteString(ByteString { data, .. }) => data.len(),
+            DataItem::TextString(TextString { data, .. }) => data.len(),
+            _ => return,
+        };
+        if let Some(strings) = self.namespaces.last_mut() {
+            if len >= minimum_string_length(strings.len()) {
+                strings.push(value.clone());
+            }
+        }
+    }
+
+    /// Looks up a string in the current namespace, returns `None` if there is
+    /// no current namespace.
+    pub(crate) fn lookup_string(&self, index: u64) -> Option<Option<&DataItem>> {
+        let strings = self.namespaces.last()?;
+        Some(usize::try_from(index).ok().and_then(|i| strings.get(i)))
+    }
+
+    fn string(&self, index: u64) -> Result<&DataItem> {
+        match self.lookup_string(index) {
+            Some(Some(value)) => Ok(value),
+            Some(None) => Err(format!("reference to unknown string {index}").into()),
+            None => Err("stringref outside of a stringref namespace".into()),
+        }
+    }
+
+    /// Resolves `value` as if it were encountered next, then forgets the
+    /// values and strings recorded while doing so, leaving the references as
+    /// they were.
+    pub(crate) fn resolve_ahead(&mut self, value: &DataItem) -> Result<DataItem> {
+        let shared = self.shared.len();
+        let namespaces = self.namespaces.len();
+        let strings = self.namespaces.last().map_or(0, Vec::len);
+        let resolved = self.resolve(value);
+        self.shared.truncate(shared);
+        self.namespaces.truncate(namespaces);
+        if let Some(current) = self.namespaces.last_mut() {
+            current.truncate(strings);
+        }
+        resolved
+    }
+
+    /// Expands the references to other shared values within the shared value
+    /// `index`, to at most `limit` items with [elisions](Tag::ELISION) in
+    /// place of the rest, to describe it.
+    pub(crate) fn describe_shared(&self, index: u64, limit: usize) -> Option<Option<DataItem>> {
+        let mut budget = limit;
+        self.lookup_shared(index)
+            .map(|shared| shared.map(|shared| self.expand(shared, &mut budget)))
+    }
+
+    fn expand(&self, value: &DataItem, budget: &mut usize) -> DataItem {
+        if *budget == 0 {
+            return DataItem::Tag {
+                tag: Tag::ELISION,
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::Simple(Simple::NULL)),
+            };
+        }
+        *budget -= 1;
+        match value {
+            DataItem::Tag {
+                tag: Tag::SHARED_REF,
+                value: index,
+                ..
+            } => match **index {
+                DataItem::Integer { value: index, .. } => match self.lookup_shared(index) {
+                    Some(Some(shared)) => self.expand(shared, budget),
+                    _ => value.clone(),
+                },
+                _ => value.clone(),
+            },
+            DataItem::Tag {
+                tag,
+                bitwidth,
+                value,
+            } => DataItem::Tag {
+                tag: *tag,
+                bitwidth: *bitwidth,
+                value: Box::new(self.expand(value, budget)),
+            },
+            DataItem::Array { data, bitwidth } => DataItem::Array {
+                data: data
+                    .iter()
+                    .map(|value| self.expand(value, budget))
+                    .collect(),
+                bitwidth: *bitwidth,
+            },
+            DataItem::Map { data, bitwidth } => DataItem::Map {
+                data: data
+                    .iter()
+                    .map(|(key, value)| (self.expand(key, budget), self.expand(value, budget)))
+                    .collect(),
+                bitwidth: *bitwidth,
+            },
+            _ => value.clone(),
+        }
+    }
+
+    pub(crate) fn resolve(&mut self, value: &DataItem) -> Result<DataItem> {
+        Ok(match value {
+            DataItem::Tag {
+                tag: Tag::SHAREABLE,
+                value,
+                ..
+            } => {
+                let index = self.share();
+                let value = self.resolve(value)?;
+                self.set_shared(index, value.clone());
+                value
+            }
+            DataItem::Tag {
+                tag: Tag::SHARED_REF,
+                bitwidth,
+                value,
+            } => match **value {
+                DataItem::Integer { value: index, .. } => {
+                    let shared = self.shared(index)?;
+                    match self.expansion_budget {
+                        Some(budget) => {
+                            let budget = budget
+                                .checked_sub(size(shared))
+                                .ok_or("shared values expand to too many items to be resolved")?;
+                            let shared = shared.clone();
+                            self.expansion_budget = Some(budget);
+                            shared
+                        }
+                        None => DataItem::Tag {
+                            tag: Tag::SHARED_REF,
+                            bitwidth: *bitwidth,
+                            value: value.clone(),
+                        },
+                    }
+                }
+                _ => return Err("invalid type for shared ref".into()),
+            },
+            DataItem::Tag {
+                tag: Tag::STRINGREF_NAMESPACE,
+                value,
+                ..
+            } => {
+                self.enter_namespace();
+                let value = self.resolve(value);
+                self.exit_namespace();
+                value?
+            }
+            DataItem::Tag {
+                tag: Tag::STRINGREF,
+                value,
+                ..
+            } => match **value {
+                DataItem::Integer { value, .. } => self.string(value)?.clone(),
+                _ => return Err("invalid type for stringref".into()),
+            },
+            DataItem::Tag {
+                tag,
+                bitwidth,
+                value,
+            } => DataItem::Tag {
+                tag: *tag,
+                bitwidth: *bitwidth,
+                value: Box::new(self.resolve(value)?),
+            },
+            DataItem::Array { data, bitwidth } => DataItem::Array {
+                data: data
+                    .iter()
+                    .map(|value| self.resolve(value))
+                    .collect::<Result<_>>()?,
+                bitwidth: *bitwidth,
+            },
+            DataItem::Map { data, bitwidth } => DataItem::Map {
+                data: data
+                    .iter()
+                    .map(|(key, value)| Ok((self.resolve(key)?, self.resolve(value)?)))
+                    .collect::<Result<_>>()?,
+                bitwidth: *bitwidth,
+            },
+            DataItem::ByteString(_) | DataItem::TextString(_) => {
+                self.record_string(value);
+                value.clone()
+            }
+            _ => value.clone(),
+        })
+    }
+}
+
+impl DataItem {
+    /// Returns an expanded copy of this item with all [shared
+    /// values](Tag::SHARED_REF) and [string references](Tag::STRINGREF)
+    /// replaced by the values they refer to, and the [`Tag::SHAREABLE`] and
+    /// [`Tag::STRINGREF_NAMESPACE`] tags removed.
+    ///
+    /// Fails if a reference cannot be resolved, including when a shared value
+    /// refers to itself, as the cyclic structure cannot be expanded into a
+    /// tree, or when shared values nested within each other would expand to
+    /// more than about a million items.
+    pub fn resolve_references(&self) -> Result<DataItem> {
+        References {
+            expansion_budget: Some(MAX_EXPANDED_ITEMS),
+            ..References::default()
+        }
+        .resolve(self)
+    }
+}
@@ -1,5 +1,7 @@
 mod tags;
 
+use num_bigint::{BigInt, Sign};
+
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 /// How many additional bytes are used to encode this integer (in bits).
 ///
@@ -85,6 +87,22 @@ pub struct TextString {
     pub bitwidth: IntegerWidth,
 }
 
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+/// A floating point value, stored as the bits it is encoded as so that NaN
+/// payloads and the exact value at its width are kept.
+///
+/// See [RFC 7049 § 2.3][RFC 2.3].
+///
+/// [RFC 2.3]: https://tools.ietf.org/html/rfc7049#section-2.3
+pub struct Float {
+    /// The bits of the value at `bitwidth` in the low bits, or of an `f64`
+    /// for [`FloatWidth::Unknown`]. Any higher bits are ignored, see
+    /// [`Float::encoded_bits`]
+    pub bits: u64,
+    /// The bitwidth used for encoding the value
+    pub bitwidth: FloatWidth,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 /// A CBOR data item.
 ///
@@ -197,16 +215,8 @@ pub enum DataItem {
 
     /// A floating point value.
     ///
-    /// See [RFC 7049 § 2.3][RFC 2.3].
-    ///
-    /// [RFC 2.3]: https://tools.ietf.org/html/rfc7049#section-2.3
-    Float {
-        /// The floating point value.
-        value: f64,
-
-        /// The bitwidth used for encoding the value.
-        bitwidth: FloatWidth,
-    },
+    /// See the docs for [`Float`] for more details.
+    Float(Float),
 
     /// A "simple value" data item.
     ///
@@ -244,3 +254,41 @@ impl Simple {
     /// concept.
     pub const UNDEFINED: Simple = Simple(23);
 }
+
+impl DataItem {
+    /// The preferred serialization of an integer, a plain integer if it fits
+    /// in one, otherwise a [`Tag::POSITIVE_BIGNUM`] or [`Tag::NEGATIVE_BIGNUM`]
+    /// with no leading zero bytes.
+    pub(crate) fn from_integer(value: BigInt) -> DataItem {
+        let (sign, magnitude) = value.into_parts();
+        let (tag, magnitude) = match sign {
+            Sign::Minus => {
+                let magnitude = magnitude - 1u8;
+                if let Ok(value) = u64::try_from(&magnitude) {
+                    return DataItem::Negative {
+                        value,
+                        bitwidth: IntegerWidth::Unknown,
+                    };
+                }
+                (Tag::NEGATIVE_BIGNUM, magnitude)
+            }
+            Sign::NoSign | Sign::Plus => {
+                if let Ok(value) = u64::try_from(&magnitude) {
+                    return DataItem::Integer {
+                        value,
+                        bitwidth: IntegerWidth::Unknown,
+                    };
+                }
+                (Tag::POSITIVE_BIGNUM, magnitude)
+            }
+        };
+        DataItem::Tag {
+            tag,
+            bitwidth: IntegerWidth::Unknown,
+            value: Box::new(DataItem::ByteString(ByteString {
+                data: magnitude.to_bytes_be(),
+                bitwidth: IntegerWidth::Unknown,
+            })),
+        }
+    }
+}
@@ -139,6 +139,13 @@ impl Tag {
     /// [RFC 2.4.4.1]: https://tools.ietf.org/html/rfc7049#section-2.4.4.1
     pub const ENCODED_CBOR: Tag = Tag(24);
 
+    /// Marks this item as being a reference to a previous string within the
+    /// enclosing [`Tag::STRINGREF_NAMESPACE`][]; can only be applied to an
+    /// [unsigned integer](DataItem::Integer) data item.
+    ///
+    /// See <http://cbor.schmorp.de/stringref> for more details on what this means.
+    pub const STRINGREF: Tag = Tag(25);
+
     /// Marks this item as being potentially shared and referenced by a later [`Tag::SHARED_REF`][];
     /// can be applied to any sort of data item.
     ///
@@ -151,6 +158,22 @@ impl Tag {
     /// See <http://cbor.schmorp.de/value-sharing> for more details on what this means.
     pub const SHARED_REF: Tag = Tag(29);
 
+    /// A "rational number"; must only be applied to an [array](DataItem::Array)
+    /// containing exactly two data items, the numerator and denominator. The
+    /// numerator must be either an [unsigned integer](DataItem::Integer),
+    /// [negative integer](DataItem::Negative) or a
+    /// [positive](Tag::POSITIVE_BIGNUM) or [negative](Tag::NEGATIVE_BIGNUM)
+    /// bignum, the denominator must be a non-zero unsigned integer or
+    /// positive bignum.
+    ///
+    /// See [the spec][RationalSpec] for more details on how to interpret the
+    /// values.
+    ///
+    /// Defined in [non-RFC specification][RationalSpec].
+    ///
+    /// [RationalSpec]: http://peteroupc.github.io/CBOR/rational.html
+    pub const RATIONAL: Tag = Tag(30);
+
     /// Marks this item as being a valid URI; must only be applied
     /// to a [text string](DataItem::TextString) (or the [indefinite
     /// variant](DataItem::IndefiniteTextString) of) data item.
@@ -227,6 +250,45 @@ impl Tag {
     /// [UuidSpec]: https://github.com/lucas-clemente/cbor-specs/blob/master/uuid.md
     pub const UUID: Tag = Tag(37);
 
+    /// Marks this item as being a text string in a specific language; must
+    /// only be applied to an [array](DataItem::Array) of a [BCP 47][] language
+    /// tag and the [text string](DataItem::TextString), optionally followed by
+    /// a boolean (or null) giving the direction of the text.
+    ///
+    /// Defined in [non-RFC specification][LangTagSpec], the direction is
+    /// defined in [RFC 9290 § 6.3][RFC 6.3].
+    ///
+    /// [BCP 47]: https://tools.ietf.org/html/rfc5646
+    /// [LangTagSpec]: http://peteroupc.github.io/CBOR/langtags.html
+    /// [RFC 6.3]: https://tools.ietf.org/html/rfc9290#section-6.3
+    pub const LANGUAGE_TAGGED_STRING: Tag = Tag(38);
+
+    /// A multi-dimensional array in row-major order; must only be applied to
+    /// an [array](DataItem::Array) of two data items, an array of the
+    /// dimensions and either an array or typed array of the elements.
+    ///
+    /// Defined in [RFC 8746 § 3.1.1][RFC 3.1.1].
+    ///
+    /// [RFC 3.1.1]: https://tools.ietf.org/html/rfc8746#section-3.1.1
+    pub const MULTI_DIMENSIONAL_ARRAY: Tag = Tag(40);
+
+    /// Marks this item as being an [array](DataItem::Array) whose elements
+    /// are all of the same type.
+    ///
+    /// Defined in [RFC 8746 § 3.2][RFC 3.2].
+    ///
+    /// [RFC 3.2]: https://tools.ietf.org/html/rfc8746#section-3.2
+    pub const HOMOGENEOUS_ARRAY: Tag = Tag(41);
+
+    /// Marks this item as being an IPLD content identifier; must only be
+    /// applied to a [byte string](DataItem::ByteString) containing the
+    /// multibase identity prefix (a zero byte) followed by a binary CID.
+    ///
+    /// Defined in [non-RFC specification][CidSpec].
+    ///
+    /// [CidSpec]: https://github.com/ipld/cid-cbor/
+    pub const CID: Tag = Tag(42);
+
     /// Marks this item as being an encoded CBOR sequence; must only be applied
     /// to a [byte string](DataItem::ByteString) (or the [indefinite
     /// variant](DataItem::IndefiniteByteString) of) data item.
@@ -475,6 +537,50 @@ impl Tag {
     /// [RFC 8943]: https://tools.ietf.org/html/rfc8943
     pub const EPOCH_DATE: Tag = Tag(100);
 
+    /// Marks this item as being a geographic coordinate; must only be
+    /// applied to an [array](DataItem::Array) of a latitude, longitude and
+    /// optional altitude, each of which is an integer or float.
+    ///
+    /// Defined in [non-RFC specification][GeoSpec].
+    ///
+    /// [GeoSpec]: https://github.com/allthingstalk/cbor/blob/master/CBOR-Tag103-Geographic-Coordinates.md
+    pub const GEOGRAPHIC_COORDINATES: Tag = Tag(103);
+
+    /// Marks this item as being a geographic coordinate in a specific
+    /// coordinate reference system; must only be applied to an
+    /// [array](DataItem::Array) of the reference system, as either an EPSG
+    /// number or WKT text string, and the coordinates.
+    ///
+    /// Defined in [non-RFC specification][GeoCrsSpec].
+    ///
+    /// [GeoCrsSpec]: https://github.com/allthingstalk/cbor/blob/master/CBOR-Tag104-Geographic-Coordinate-Reference-System.md
+    pub const GEOGRAPHIC_COORDINATE_REFERENCE_SYSTEM: Tag = Tag(104);
+
+    /// Marks this item as introducing a new namespace for
+    /// [`Tag::STRINGREF`][] references; can be applied to any sort of data
+    /// item.
+    ///
+    /// See <http://cbor.schmorp.de/stringref> for more details on what this means.
+    pub const STRINGREF_NAMESPACE: Tag = Tag(256);
+
+    /// A mathematical finite set; must only be applied to an
+    /// [array](DataItem::Array) of unique data items, whose order is not
+    /// significant.
+    ///
+    /// See [the spec][SetSpec] for more details on what this means.
+    ///
+    /// [SetSpec]: https://github.com/input-output-hk/cbor-sets-spec/blob/master/CBOR_SETS.md
+    pub const SET: Tag = Tag(258);
+
+    /// A map which may contain keys of any type, intended for round-tripping
+    /// maps with non-string keys through JavaScript; must only be applied to a
+    /// [map](DataItem::Map).
+    ///
+    /// See [the spec][MapSpec] for more details on what this means.
+    ///
+    /// [MapSpec]: https://github.com/shanewholloway/js-cbor-codec/blob/master/docs/CBOR-259-spec--explicit-maps.md
+    pub const MAP_WITH_ANY_KEYS: Tag = Tag(259);
+
     /// Marks this item as being a Network Address (IPv4 or IPv6 or MAC
     /// Address); must only be applied to a [byte string](DataItem::ByteString)
     /// (or the [indefinite variant](DataItem::IndefiniteByteString) of) data
@@ -490,6 +596,52 @@ impl Tag {
     /// [RFC 9164]
     pub const NETWORK_ADDRESS: Tag = Tag(260);
 
+    /// Marks this item as being an embedded JSON text; must only be applied
+    /// to a [byte string](DataItem::ByteString) data item containing UTF-8
+    /// encoded JSON.
+    ///
+    /// Defined in [non-RFC specification][EmbeddedJsonSpec].
+    ///
+    /// [EmbeddedJsonSpec]: https://github.com/toravir/CBOR-Tag-Specs/blob/master/embeddedJSON.md
+    pub const EMBEDDED_JSON: Tag = Tag(262);
+
+    /// Marks data elided from diagnostic notation with `...`; applied to
+    /// [null](crate::Simple::NULL) for an elided data item, or to an
+    /// [array](DataItem::Array) of strings and nulls for a string with parts
+    /// elided.
+    ///
+    /// Defined in [draft-ietf-cbor-edn-literals][EDN].
+    ///
+    /// [EDN]: https://datatracker.ietf.org/doc/draft-ietf-cbor-edn-literals/
+    pub const ELISION: Tag = Tag(888);
+
+    /// An "extended time"; must only be applied to a [map](DataItem::Map) data
+    /// item with keys giving a base time in seconds since the epoch along with
+    /// optional fractional seconds, timescale and other metadata.
+    ///
+    /// Defined in [RFC 9581][].
+    ///
+    /// [RFC 9581]: https://www.rfc-editor.org/rfc/rfc9581
+    pub const EXTENDED_TIME: Tag = Tag(1001);
+
+    /// A "duration"; must only be applied to a [map](DataItem::Map) data item
+    /// using the same keys as [`Tag::EXTENDED_TIME`], with the base value being
+    /// a duration in seconds.
+    ///
+    /// Defined in [RFC 9581][].
+    ///
+    /// [RFC 9581]: https://www.rfc-editor.org/rfc/rfc9581
+    pub const DURATION: Tag = Tag(1002);
+
+    /// A "period"; must only be applied to an [array](DataItem::Array) data
+    /// item containing a start time, end time and optionally a duration, where
+    /// exactly two of these are not [null](crate::Simple::NULL).
+    ///
+    /// Defined in [RFC 9581][].
+    ///
+    /// [RFC 9581]: https://www.rfc-editor.org/rfc/rfc9581
+    pub const PERIOD: Tag = Tag(1003);
+
     /// A "Standard date string"; must only be applied to a [text
     /// string](DataItem::TextString) (or the [indefinite
     /// variant](DataItem::IndefiniteTextString) of) data item.
@@ -500,6 +652,15 @@ impl Tag {
     /// [RFC 3339]: https://tools.ietf.org/html/rfc3339
     pub const DATE: Tag = Tag(1004);
 
+    /// A multi-dimensional array in column-major order; must only be applied
+    /// to an [array](DataItem::Array) of two data items, an array of the
+    /// dimensions and either an array or typed array of the elements.
+    ///
+    /// Defined in [RFC 8746 § 3.1.2][RFC 3.1.2].
+    ///
+    /// [RFC 3.1.2]: https://tools.ietf.org/html/rfc8746#section-3.1.2
+    pub const MULTI_DIMENSIONAL_ARRAY_COLUMN_MAJOR: Tag = Tag(1040);
+
     /// Marks this item as being CBOR, a no-op; can be applied to any type of
     /// data item.
     ///
@@ -511,4 +672,45 @@ impl Tag {
     /// [RFC 2.4]: https://tools.ietf.org/html/rfc7049#section-2.4
     /// [RFC 2.4.5]: https://tools.ietf.org/html/rfc7049#section-2.4.5
     pub const SELF_DESCRIBE_CBOR: Tag = Tag(55799);
+
+    /// Marks the start of a CBOR sequence; must only be applied to the [byte
+    /// string](DataItem::ByteString) `'BOR'`, as the first item of the
+    /// sequence.
+    ///
+    /// Defined in [RFC 9277 § 2.2][RFC 2.2].
+    ///
+    /// [RFC 2.2]: https://tools.ietf.org/html/rfc9277#section-2.2
+    pub const SELF_DESCRIBE_CBOR_SEQUENCE: Tag = Tag(55800);
+
+    /// Marks the start of data that is not CBOR; must only be applied to the
+    /// [byte string](DataItem::ByteString) `'BOR'`, which is then followed by
+    /// the data.
+    ///
+    /// Defined in [RFC 9277 § 3.1][RFC 3.1].
+    ///
+    /// [RFC 3.1]: https://tools.ietf.org/html/rfc9277#section-3.1
+    pub const CBOR_LABELED_NON_CBOR_DATA: Tag = Tag(55801);
+
+    /// The first of the range of tags identifying a CoAP content-format, see
+    /// [`Tag::from_content_format`].
+    const CONTENT_FORMAT_BASE: u64 = 1668546560;
+
+    /// The tag identifying data of the CoAP content-format `content_format`;
+    /// can be applied to a single data item, or to the [byte
+    /// string](DataItem::ByteString) `'BOR'` at the start of a sequence.
+    ///
+    /// Defined in [RFC 9277 § 2.3][RFC 2.3].
+    ///
+    /// [RFC 2.3]: https://tools.ietf.org/html/rfc9277#section-2.3
+    pub const fn from_content_format(content_format: u16) -> Tag {
+        Tag(Self::CONTENT_FORMAT_BASE + content_format as u64)
+    }
+
+    /// The CoAP content-format identified by this tag, if it is one of the
+    /// tags created by [`Tag::from_content_format`].
+    pub fn content_format(self) -> Option<u16> {
+        self.0
+            .checked_sub(Self::CONTENT_FORMAT_BASE)
+            .and_then(|content_format| u16::try_from(content_format).ok())
+    }
 }
@@ -0,0 +1,203 @@
+//! The element types of the typed arrays defined in [RFC 8746 § 2][RFC 2],
+//! used to convert their contents to and from lists of numbers.
+//!
+//! [RFC 2]: https://tools.ietf.org/html/rfc8746#section-2
+
+use half::f16;
+use std::{convert::TryInto, str::FromStr};
+
+use crate::{f128::F128, Tag};
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub(crate) enum Element {
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+    F16,
+    F32,
+    F64,
+    F128,
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub(crate) struct TypedArray {
+    pub(crate) element: Element,
+    pub(crate) little_endian: bool,
+}
+
+fn decode<const LEN: usize>(
+    data: &[u8],
+    format: impl Fn([u8; LEN]) -> String,
+) -> Result<Vec<String>, &'static str> {
+    if data.len().is_multiple_of(LEN) {
+        Ok(data
+            .chunks_exact(LEN)
+            .map(|chunk| format(chunk.try_into().unwrap()))
+            .collect())
+    } else {
+        Err("invalid data length for typed array")
+    }
+}
+
+/// Formats a float the same way as a float data item in diagnostic notation,
+/// `display` must be the shortest representation of the value in its original
+/// precision.
+fn format_float(value: f64, display: String) -> String {
+    if value.is_nan() {
+        "NaN".into()
+    } else if value.is_infinite() {
+        if value.is_sign_negative() {
+            "-Infinity".into()
+        } else {
+            "Infinity".into()
+        }
+    } else if display.contains('.') || display.contains('e') {
+        display
+    } else {
+        display + ".0"
+    }
+}
+
+fn parse<T: FromStr>(element: &str) -> Result<T, String> {
+    element
+        .parse()
+        .map_err(|_| format!("invalid typed array element {element:?}"))
+}
+
+impl TypedArray {
+    pub(crate) fn from_tag(tag: Tag) -> Option<TypedArray> {
+        let (element, little_endian) = match tag {
+            Tag::TYPED_ARRAY_U8 | Tag::TYPED_ARRAY_U8_CLAMPED => (Element::U8, false),
+            Tag::TYPED_ARRAY_U16_BIG_ENDIAN => (Element::U16, false),
+            Tag::TYPED_ARRAY_U32_BIG_ENDIAN => (Element::U32, false),
+            Tag::TYPED_ARRAY_U64_BIG_ENDIAN => (Element::U64, false),
+            Tag::TYPED_ARRAY_U16_LITTLE_ENDIAN => (Element::U16, true),
+            Tag::TYPED_ARRAY_U32_LITTLE_ENDIAN => (Element::U32, true),
+            Tag::TYPED_ARRAY_U64_LITTLE_ENDIAN => (Element::U64, true),
+            Tag::TYPED_ARRAY_I8 => (Element::I8, false),
+            Tag::TYPED_ARRAY_I16_BIG_ENDIAN => (Element::I16, false),
+            Tag::TYPED_ARRAY_I32_BIG_ENDIAN => (Element::I32, false),
+            Tag::TYPED_ARRAY_I64_BIG_ENDIAN => (Element::I64, false),
+            Tag::TYPED_ARRAY_I16_LITTLE_ENDIAN => (Element::I16, true),
+            Tag::TYPED_ARRAY_I32_LITTLE_ENDIAN => (Element::I32, true),
+            Tag::TYPED_ARRAY_I64_LITTLE_ENDIAN => (Element::I64, true),
+            Tag::TYPED_ARRAY_F16_BIG_ENDIAN => (Element::F16, false),
+            Tag::TYPED_ARRAY_F32_BIG_ENDIAN => (Element::F32, false),
+            Tag::TYPED_ARRAY_F64_BIG_ENDIAN => (Element::F64, false),
+            Tag::TYPED_ARRAY_F128_BIG_ENDIAN => (Element::F128, false),
+            Tag::TYPED_ARRAY_F16_LITTLE_ENDIAN => (Element::F16, true),
+            Tag::TYPED_ARRAY_F32_LITTLE_ENDIAN => (Element::F32, true),
+            Tag::TYPED_ARRAY_F64_LITTLE_ENDIAN => (Element::F64, true),
+            Tag::TYPED_ARRAY_F128_LITTLE_ENDIAN => (Element::F128, true),
+            _ => return None,
+        };
+        Some(TypedArray {
+            element,
+            little_endian,
+        })
+    }
+
+    /// Parses the prefix of a typed array byte string literal, e.g. `u8` or
+    /// `f32le`.
+    pub(crate) fn from_prefix(prefix: &str) -> Option<TypedArray> {
+        (64..=87)
+            .filter_map(|tag| TypedArray::from_tag(Tag(tag)))
+            .find(|array| array.prefix() == prefix)
+    }
+
+    pub(crate) fn prefix(self) -> String {
+        let name = match self.element {
+            Element::U8 => return "u8".into(),
+            Element::I8 => return "i8".into(),
+            Element::U16 => "u16",
+            Element::U32 => "u32",
+            Element::U64 => "u64",
+            Element::I16 => "i16",
+            Element::I32 => "i32",
+            Element::I64 => "i64",
+            Element::F16 => "f16",
+            Element::F32 => "f32",
+            Element::F64 => "f64",
+            Element::F128 => "f128",
+        };
+        let endianness = if self.little_endian { "le" } else { "be" };
+        format!("{name}{endianness}")
+    }
+
+    /// Formats each element of the typed array in diagnostic notation.
+    pub(crate) fn format(self, data: &[u8]) -> Result<Vec<String>, &'static str> {
+        macro_rules! decode {
+            ($ty:ty, $format:expr) => {
+                decode::<{ std::mem::size_of::<$ty>() }>(data, |bytes| {
+                    let value = if self.little_endian {
+                        <$ty>::from_le_bytes(bytes)
+                    } else {
+                        <$ty>::from_be_bytes(bytes)
+                    };
+                    $format(value)
+                })
+            };
+        }
+
+        match self.element {
+            Element::U8 => decode!(u8, |value: u8| value.to_string()),
+            Element::U16 => decode!(u16, |value: u16| value.to_string()),
+            Element::U32 => decode!(u32, |value: u32| value.to_string()),
+            Element::U64 => decode!(u64, |value: u64| value.to_string()),
+            Element::I8 => decode!(i8, |value: i8| value.to_string()),
+            Element::I16 => decode!(i16, |value: i16| value.to_string()),
+            Element::I32 => decode!(i32, |value: i32| value.to_string()),
+            Element::I64 => decode!(i64, |value: i64| value.to_string()),
+            Element::F16 => decode!(f16, |value: f16| {
+                format_float(value.to_f64(), value.to_string())
+            }),
+            Element::F32 => decode!(f32, |value: f32| {
+                format_float(value.into(), value.to_string())
+            }),
+            Element::F64 => decode!(f64, |value: f64| format_float(value, value.to_string())),
+            Element::F128 => decode!(F128, |value: F128| value.to_string()),
+        }
+    }
+
+    /// Encodes a list of elements in diagnostic notation, as output by
+    /// [`TypedArray::format`], back to the contents of the typed array.
+    pub(crate) fn encode<'a>(
+        self,
+        elements: impl IntoIterator<Item = &'a str>,
+    ) -> Result<Vec<u8>, String> {
+        let mut data = Vec::new();
+        for element in elements {
+            macro_rules! encode {
+                ($ty:ty) => {{
+                    let value: $ty = parse(element)?;
+                    data.extend_from_slice(&if self.little_endian {
+                        value.to_le_bytes()
+                    } else {
+                        value.to_be_bytes()
+                    });
+                }};
+            }
+
+            match self.element {
+                Element::U8 => encode!(u8),
+                Element::U16 => encode!(u16),
+                Element::U32 => encode!(u32),
+                Element::U64 => encode!(u64),
+                Element::I8 => encode!(i8),
+                Element::I16 => encode!(i16),
+                Element::I32 => encode!(i32),
+                Element::I64 => encode!(i64),
+                Element::F16 => encode!(f16),
+                Element::F32 => encode!(f32),
+                Element::F64 => encode!(f64),
+                Element::F128 => encode!(F128),
+            }
+        }
+        Ok(data)
+    }
+}
@@ -1,4 +1,4 @@
-use cbor_diag::{ByteString, DataItem, IntegerWidth};
+use cbor_diag::{ByteString, ByteStringStyle, DataItem, EmbeddedCbor, IntegerWidth};
 use data_encoding_macro::hexlower as hex;
 use indoc::indoc;
 
@@ -502,6 +502,14 @@ testcases! {
     }
 
     mod encodings {
+        escaped(diag2value) {
+            DataItem::ByteString(ByteString {
+                data: hex!("275c0a09c3a9f09f9880").into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { r#"'\'\\\n\t\u00e9\u{1f600}'"# }
+        }
+
         base16(diag2value) {
             DataItem::ByteString(ByteString {
                 data: hex!("12345678").into(),
@@ -595,6 +603,112 @@ testcases! {
         }
     }
 
+    mod typed_arrays {
+        u8(diag2value) {
+            DataItem::ByteString(ByteString {
+                data: hex!("0102ff").into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { "u8'1, 2, 255'" }
+        }
+
+        u16_big_endian(diag2value) {
+            DataItem::ByteString(ByteString {
+                data: hex!("00010102").into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { "u16be'1, 258'" }
+        }
+
+        u32_little_endian(diag2value) {
+            DataItem::ByteString(ByteString {
+                data: hex!("0100000000000100").into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { "u32le'1,65536'" }
+        }
+
+        i8(diag2value) {
+            DataItem::ByteString(ByteString {
+                data: hex!("ff7f80").into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { "i8'-1, 127, -128'" }
+        }
+
+        i64_little_endian(diag2value) {
+            DataItem::ByteString(ByteString {
+                data: hex!("feffffffffffffff").into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { "i64le'-2'" }
+        }
+
+        f16_big_endian(diag2value) {
+            DataItem::ByteString(ByteString {
+                data: hex!("3c00fc00").into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { "f16be'1.0, -Infinity'" }
+        }
+
+        f32_little_endian(diag2value) {
+            DataItem::ByteString(ByteString {
+                data: hex!("0000c03fcdcccc3d0000c07f").into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { "f32le'1.5, 0.1, NaN'" }
+        }
+
+        f64_big_endian(diag2value) {
+            DataItem::ByteString(ByteString {
+                data: hex!("80000000000000007e37e43c8800759c").into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { "f64be'-0.0, 1e300'" }
+        }
+
+        f128_big_endian(diag2value) {
+            DataItem::ByteString(ByteString {
+                data: hex!("3fff80000000000000000000000000003ffb999999999999999999999999999a").into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { "f128be'1.5, 0.1'" }
+        }
+
+        f128_little_endian(diag2value) {
+            DataItem::ByteString(ByteString {
+                data: hex!("0000000000000000000000000000ffff020000000000000000000000000000000000000000000000000000000000ff7f").into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { "f128le'-Infinity, 1e-4965, 1e5000'" }
+        }
+
+        f128_extreme_exponents(diag2value) {
+            DataItem::ByteString(ByteString {
+                data: hex!("7fff000000000000000000000000000000000000000000000000000000000000").into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { "f128be'1e9223372036854775807, 1.5e-9223372036854775808'" }
+        }
+
+        empty(diag2value) {
+            DataItem::ByteString(ByteString {
+                data: vec![],
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { "f32be''" }
+        }
+
+        whitespace(diag2value) {
+            DataItem::ByteString(ByteString {
+                data: hex!("01000200").into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { "u16le' 1 ,\n 2 '" }
+        }
+    }
+
     // RFC 8610 Appendix G.4
     mod concatenated {
         one(diag2value) {
@@ -689,3 +803,337 @@ testcases! {
         }
     }
 }
+
+#[test]
+fn typed_array_out_of_range() {
+    assert!(utils::parse_diag("u8'256'").is_err());
+    assert!(utils::parse_diag("i8'-129'").is_err());
+    assert!(utils::parse_diag("u16be'-1'").is_err());
+    assert!(utils::parse_diag("u32'1'").is_err());
+    assert!(utils::parse_diag("f128be'1.0.0'").is_err());
+}
+
+fn with_embedded_cbor(diag: &str, embedded_cbor: EmbeddedCbor) -> String {
+    let value = utils::parse_diag(diag).unwrap();
+    let output = value.to_diag_with(&cbor_diag::DiagOptions {
+        embedded_cbor: Some(embedded_cbor),
+        ..cbor_diag::DiagOptions::default()
+    });
+    assert_eq!(
+        utils::parse_diag(&output).unwrap().to_bytes(),
+        value.to_bytes()
+    );
+    output
+}
+
+fn annotated_with_embedded_cbor(diag: &str) -> String {
+    utils::parse_diag(diag)
+        .unwrap()
+        .to_hex_with(&cbor_diag::HexOptions {
+            embedded_cbor: Some(EmbeddedCbor::default()),
+            ..cbor_diag::HexOptions::default()
+        })
+}
+
+#[test]
+fn embedded_diag_item() {
+    assert_eq!(
+        with_embedded_cbor("h'a201020304'", EmbeddedCbor::default()),
+        "<<{1:2,3:4}>>"
+    );
+}
+
+#[test]
+fn embedded_diag_sequence() {
+    assert_eq!(
+        with_embedded_cbor("h'8301020304'", EmbeddedCbor::default()),
+        "<<[1,2,3],4>>"
+    );
+}
+
+#[test]
+fn embedded_diag_not_cbor() {
+    assert_eq!(
+        with_embedded_cbor("h'83010203ff'", EmbeddedCbor::default()),
+        "h'83010203ff'"
+    );
+    assert_eq!(
+        with_embedded_cbor("h'8301'", EmbeddedCbor::default()),
+        "h'8301'"
+    );
+}
+
+#[test]
+fn embedded_diag_min_length() {
+    assert_eq!(
+        with_embedded_cbor("h'a10102'", EmbeddedCbor::default()),
+        "h'a10102'"
+    );
+    assert_eq!(
+        with_embedded_cbor(
+            "h'a10102'",
+            EmbeddedCbor {
+                min_length: 1,
+                ..EmbeddedCbor::default()
+            }
+        ),
+        "<<{1:2}>>"
+    );
+}
+
+#[test]
+fn embedded_diag_max_depth() {
+    let embedded_cbor = EmbeddedCbor {
+        min_length: 1,
+        max_depth: 2,
+    };
+    assert_eq!(
+        with_embedded_cbor("<<<<<<1>>>>>>", embedded_cbor),
+        "<<<<h'01'>>>>"
+    );
+}
+
+#[test]
+fn embedded_diag_tagged() {
+    assert_eq!(
+        with_embedded_cbor(
+            "[2(h'a201020304'), 24(h'a201020304')]",
+            EmbeddedCbor::default()
+        ),
+        "[2(h'a201020304'),24(<<{1:2,3:4}>>)]"
+    );
+}
+
+#[test]
+fn embedded_diag_within_tagged() {
+    assert_eq!(
+        with_embedded_cbor("18([h'a201020304'])", EmbeddedCbor::default()),
+        "18([<<{1:2,3:4}>>])"
+    );
+}
+
+#[test]
+fn embedded_diag_not_canonical() {
+    // Non-minimal encodings are preserved, so decoded the same
+    assert_eq!(
+        with_embedded_cbor("h'1800a0a0'", EmbeddedCbor::default()),
+        "<<0_0,{},{}>>"
+    );
+}
+
+#[test]
+fn embedded_hex_item() {
+    assert_eq!(
+        utils::DisplayDebug(annotated_with_embedded_cbor(
+            "[h'a201020304', 2(h'a201020304')]"
+        )),
+        utils::DisplayDebug(indoc!(
+            r#"
+            82                  # array(2)
+               45               #   bytes(5)
+                  a201020304    #     "\xa2\x01\x02\x03\x04"
+                                #     embedded cbor data item
+                                #       a2    # map(2)
+                                #          01 #   unsigned(1)
+                                #          02 #   unsigned(2)
+                                #          03 #   unsigned(3)
+                                #          04 #   unsigned(4)
+               c2               #   positive bignum, tag(2)
+                  45            #     bytes(5)
+                     a201020304 #       "\xa2\x01\x02\x03\x04"
+                                #     bignum(695801611012)
+            "#
+        ))
+    );
+}
+
+#[test]
+fn embedded_hex_sequence() {
+    assert_eq!(
+        utils::DisplayDebug(annotated_with_embedded_cbor("h'8301020304'")),
+        utils::DisplayDebug(indoc!(
+            r#"
+            45            # bytes(5)
+               8301020304 #   "\x83\x01\x02\x03\x04"
+                          #   embedded cbor sequence
+                          #     83    # array(3)
+                          #        01 #   unsigned(1)
+                          #        02 #   unsigned(2)
+                          #        03 #   unsigned(3)
+                          #     04    # unsigned(4)
+            "#
+        ))
+    );
+}
+
+#[test]
+fn embedded_hex_separate_references() {
+    assert_eq!(
+        utils::DisplayDebug(annotated_with_embedded_cbor(
+            r#"256([h'63616263', "xyz", 25(1)])"#
+        )),
+        utils::DisplayDebug(indoc!(
+            r#"
+            d9 0100           # stringref namespace, tag(256)
+               83             #   array(3)
+                  44          #     bytes(4)
+                     63616263 #       "cabc"
+                              #       embedded cbor data item
+                              #         63        # text(3)
+                              #            616263 #   "abc"
+                  63          #     text(3)
+                     78797a   #       "xyz"
+                  d8 19       #     reference to shared string, tag(25)
+                     01       #       unsigned(1)
+                              #       stringref(1): "xyz"
+            "#
+        ))
+    );
+}
+
+fn with_byte_strings(diag: &str, byte_strings: ByteStringStyle) -> String {
+    utils::parse_diag(diag)
+        .unwrap()
+        .to_hex_with(&cbor_diag::HexOptions {
+            byte_strings,
+            ..cbor_diag::HexOptions::default()
+        })
+}
+
+/// Checks that the annotations of the contents of a byte string are
+/// diagnostic notation literals of the same bytes.
+fn byte_strings_round_trip(data: &[u8], byte_strings: ByteStringStyle) {
+    let value = DataItem::ByteString(ByteString {
+        data: data.to_vec(),
+        bitwidth: IntegerWidth::Unknown,
+    });
+    let hex = value.to_hex_with(&cbor_diag::HexOptions {
+        byte_strings,
+        ..cbor_diag::HexOptions::default()
+    });
+    let literals: Vec<&str> = hex
+        .lines()
+        .skip(1)
+        .map(|line| line.split_once("#   ").unwrap().1)
+        .filter(|literal| !literal.starts_with("hint: "))
+        .collect();
+    let parsed = utils::parse_diag(literals.join(" ")).unwrap();
+    assert_eq!(parsed.to_bytes(), value.to_bytes(), "{hex}");
+}
+
+#[test]
+fn byte_strings_detect_text() {
+    assert_eq!(
+        utils::DisplayDebug(with_byte_strings(
+            r#"['it\'s a \\ test', 'some longer text with ünïcode in it']"#,
+            ByteStringStyle::Detect
+        )),
+        utils::DisplayDebug(indoc!(
+            r#"
+            82                                     # array(2)
+               4d                                  #   bytes(13)
+                  697427732061205c2074657374       #     'it\'s a \\ test'
+               58 25                               #   bytes(37)
+                  736f6d65206c6f6e6765722074657874 #     'some longer text'
+                  207769746820c3bc6ec3af636f646520 #     ' with ünïcode '
+                  696e206974                       #     'in it'
+            "#
+        ))
+    );
+}
+
+#[test]
+fn byte_strings_detect_binary() {
+    assert_eq!(
+        utils::DisplayDebug(with_byte_strings(
+            "[h'', h'01ff', h'c0000201', h'a0b1c2d3e4f5465788990011aabbccdd', h'20010db8000000000000000000000001']",
+            ByteStringStyle::Detect
+        )),
+        utils::DisplayDebug(indoc!(
+            r#"
+            85                                     # array(5)
+               40                                  #   bytes(0)
+                                                   #     ''
+               42                                  #   bytes(2)
+                  01ff                             #     h'01ff'
+               44                                  #   bytes(4)
+                  c0000201                         #     h'c0000201'
+                                                   #     hint: ipv4 address(192.0.2.1)
+               50                                  #   bytes(16)
+                  a0b1c2d3e4f5465788990011aabbccdd #     h'a0b1c2d3e4f5465788990011aabbccdd'
+                                                   #     hint: uuid(a0b1c2d3-e4f5-4657-8899-0011aabbccdd)
+               50                                  #   bytes(16)
+                  20010db8000000000000000000000001 #     h'20010db8000000000000000000000001'
+                                                   #     hint: ipv6 address(2001:db8::1)
+            "#
+        ))
+    );
+}
+
+#[test]
+fn byte_strings_detect_digest() {
+    assert_eq!(
+        utils::DisplayDebug(with_byte_strings(
+            "h'00112233445566778899aabbccddeeff00112233445566778899aabbccddeeff'",
+            ByteStringStyle::Detect
+        )),
+        utils::DisplayDebug(indoc!(
+            r#"
+            58 20                               # bytes(32)
+               00112233445566778899aabbccddeeff #   h'00112233445566778899aabbccddeeff'
+               00112233445566778899aabbccddeeff #   h'00112233445566778899aabbccddeeff'
+                                                #   hint: 32 byte digest, e.g. sha-256
+            "#
+        ))
+    );
+}
+
+#[test]
+fn byte_strings_base64() {
+    assert_eq!(
+        utils::DisplayDebug(with_byte_strings(
+            "[h'', h'01ff', 23(h'01ff')]",
+            ByteStringStyle::Base64
+        )),
+        utils::DisplayDebug(indoc!(
+            r#"
+            83            # array(3)
+               40         #   bytes(0)
+                          #     b64''
+               42         #   bytes(2)
+                  01ff    #     b64'Af8'
+               d7         #   suggested base16 encoding, tag(23)
+                  42      #     bytes(2)
+                     01ff #       h'01ff'
+            "#
+        ))
+    );
+}
+
+#[test]
+fn byte_strings_base32() {
+    assert_eq!(
+        utils::DisplayDebug(with_byte_strings("h'01ff'", ByteStringStyle::Base32)),
+        utils::DisplayDebug(indoc!(
+            r#"
+            42      # bytes(2)
+               01ff #   b32'AH7Q===='
+            "#
+        ))
+    );
+}
+
+#[test]
+fn byte_strings_round_trips() {
+    let data: Vec<u8> = (0..=255).collect();
+    for byte_strings in [
+        ByteStringStyle::Detect,
+        ByteStringStyle::Base64,
+        ByteStringStyle::Base32,
+    ] {
+        byte_strings_round_trip(&data, byte_strings);
+        byte_strings_round_trip(b"", byte_strings);
+        byte_strings_round_trip(b"it's a \\ test with \xc3\xbcn\xc3\xafcode", byte_strings);
+        byte_strings_round_trip(&[0xc0, 0, 2, 1], byte_strings);
+    }
+}
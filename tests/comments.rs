@@ -77,4 +77,42 @@ testcases! {
             "
         }
     }
+
+    end_of_line(diag2value) {
+        DataItem::Array {
+            data: vec![
+                DataItem::Integer {
+                    value: 1,
+                    bitwidth: IntegerWidth::Zero,
+                },
+                DataItem::TextString(TextString {
+                    data: "# not a comment // either".into(),
+                    bitwidth: IntegerWidth::Unknown,
+                }),
+            ],
+            bitwidth: Some(IntegerWidth::Unknown),
+        },
+        {
+            r##"
+            # a comment
+            [
+                1, // another comment
+                "# not a comment // either" # the last / comment
+            ] //
+            "##
+        }
+    }
+
+    end_of_line_bytestring(diag2value) {
+        DataItem::ByteString(ByteString {
+            data: hex!("68656c6c6f").into(),
+            bitwidth: IntegerWidth::Unknown,
+        }),
+        {
+            "
+                h'68 65 6c # doubled l!
+                  6c 6f' // hello
+            "
+        }
+    }
 }
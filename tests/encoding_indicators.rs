@@ -0,0 +1,135 @@
+use cbor_diag::{parse_diag, parse_hex, DiagOptions};
+use data_encoding_macro::hexlower as hex;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+#[macro_use]
+mod utils;
+
+use utils::DisplayDebug;
+
+fn explicit(value: &cbor_diag::DataItem, pretty: bool) -> String {
+    value.to_diag_with(&DiagOptions {
+        pretty,
+        explicit_encoding: true,
+        ..DiagOptions::default()
+    })
+}
+
+#[track_caller]
+fn assert_encodes_as(diag: &str, bytes: &[u8]) {
+    assert_eq!(parse_diag(diag).unwrap().to_bytes(), bytes, "{diag}");
+}
+
+#[test]
+fn immediate() {
+    assert_encodes_as("1_i", &hex!("01"));
+    assert_encodes_as("-24_i", &hex!("37"));
+    assert_encodes_as("23_i(1)", &hex!("d701"));
+    assert_encodes_as("1_0", &hex!("1801"));
+}
+
+#[test]
+fn lengths() {
+    assert_encodes_as("h'01'_0", &hex!("580101"));
+    assert_encodes_as("'a' 'b'_1", &hex!("5900026162"));
+    assert_encodes_as(r#""a"_i"#, &hex!("6161"));
+    assert_encodes_as(r#"(_ "a"_0, "b")"#, &hex!("7f7801616162ff"));
+    assert_encodes_as("[_0 1, 2]", &hex!("98020102"));
+    assert_encodes_as("[_i ]", &hex!("80"));
+    assert_encodes_as("{_1 1: 2}", &hex!("b900010102"));
+}
+
+#[test]
+fn indefinite() {
+    assert_encodes_as("[_7 1]", &hex!("9f01ff"));
+    assert_encodes_as("{_7 1: 2}", &hex!("bf0102ff"));
+    assert_encodes_as(r#"(_7 "a")"#, &hex!("7f6161ff"));
+    assert_encodes_as(r#"""_"#, &hex!("7fff"));
+    assert_encodes_as("''_", &hex!("5fff"));
+    // Without whitespace following it is the first item
+    assert_encodes_as("[_7]", &hex!("9f07ff"));
+    assert_encodes_as("[_1,2]", &hex!("9f0102ff"));
+}
+
+#[test]
+fn simple() {
+    assert_encodes_as("false_i", &hex!("f4"));
+    assert_encodes_as("simple(16)_i", &hex!("f0"));
+    assert_encodes_as("simple(40)_0", &hex!("f828"));
+}
+
+#[test]
+fn invalid() {
+    for diag in [
+        "24_i",
+        "256_0",
+        "65536_1",
+        "-25_i",
+        "24_i(1)",
+        "1.5_i",
+        "1.5_0",
+        "1_7",
+        r#""a"_7"#,
+        r#""abcdefghijklmnopqrstuvwxyz"_i"#,
+        "[_i 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24]",
+        r#""a" ..._0"#,
+        "false_0",
+        "simple(40)_i",
+        "true_1",
+    ] {
+        assert!(parse_diag(diag).is_err(), "{diag}");
+    }
+}
+
+#[test]
+fn explicit_output() {
+    let value = parse_hex("a2 01 98 02 18 18 f4 78 01 61 5f 41 00 ff").unwrap();
+    assert_eq!(
+        explicit(&value, false),
+        r#"{_i 1_i:[_0 24_0,false],"a"_0:(_h'00'_i)}"#
+    );
+    assert_eq!(
+        explicit(&value, true),
+        r#"{_i 1_i: [_0 24_0, false], "a"_0: (_ h'00'_i)}"#
+    );
+    for pretty in [false, true] {
+        assert_eq!(
+            parse_diag(explicit(&value, pretty)).unwrap().to_bytes(),
+            value.to_bytes()
+        );
+    }
+}
+
+#[test]
+fn explicit_output_multiline() {
+    let value =
+        parse_diag(r#"{_0 "first key": "a longer text string"_0, "second": [_i 1, 2]}"#).unwrap();
+    assert_eq!(
+        DisplayDebug(explicit(&value, true)),
+        DisplayDebug(indoc!(
+            r#"
+            {_0
+                "first key": "a longer text string"_0,
+                "second": [_i 1_i, 2_i],
+            }"#
+        ))
+    );
+    assert_eq!(
+        parse_diag(explicit(&value, true)).unwrap().to_bytes(),
+        value.to_bytes()
+    );
+}
+
+#[test]
+fn explicit_empty_indefinite_text() {
+    let value = parse_hex("7fff").unwrap();
+    assert_eq!(explicit(&value, false), r#"""_"#);
+    assert_eq!(value.to_diag(), "(_)");
+}
+
+#[test]
+fn not_explicit_by_default() {
+    let value = parse_hex("98 01 01").unwrap();
+    assert_eq!(value.to_diag(), "[1]");
+}
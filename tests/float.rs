@@ -1,6 +1,5 @@
-use cbor_diag::{DataItem, FloatWidth};
+use cbor_diag::{parse_diag, parse_hex, DataItem, Float, FloatWidth};
 use indoc::indoc;
-use std::f64::{INFINITY, NAN, NEG_INFINITY};
 
 #[macro_use]
 mod utils;
@@ -8,10 +7,7 @@ mod utils;
 testcases! {
     mod unknown {
         zero(diag2value, value2diag) {
-            DataItem::Float {
-                value: 0.0,
-                bitwidth: FloatWidth::Unknown,
-            },
+            DataItem::Float(Float::new(0.0, FloatWidth::Unknown)),
             {
                 "0.0",
                 "0.0",
@@ -19,10 +15,7 @@ testcases! {
         }
 
         one(diag2value, value2diag) {
-            DataItem::Float {
-                value: 1.0,
-                bitwidth: FloatWidth::Unknown,
-            },
+            DataItem::Float(Float::new(1.0, FloatWidth::Unknown)),
             {
                 "1.0",
                 "1.0",
@@ -30,10 +23,7 @@ testcases! {
         }
 
         half(diag2value, value2diag) {
-            DataItem::Float {
-                value: 0.5,
-                bitwidth: FloatWidth::Unknown,
-            },
+            DataItem::Float(Float::new(0.5, FloatWidth::Unknown)),
             {
                 "0.5",
                 "0.5",
@@ -41,10 +31,7 @@ testcases! {
         }
 
         infinity(diag2value, value2diag) {
-            DataItem::Float {
-                value: INFINITY,
-                bitwidth: FloatWidth::Unknown,
-            },
+            DataItem::Float(Float::new(f64::INFINITY, FloatWidth::Unknown)),
             {
                 "Infinity",
                 "Infinity",
@@ -52,10 +39,7 @@ testcases! {
         }
 
         neg_infinity(diag2value, value2diag) {
-            DataItem::Float {
-                value: NEG_INFINITY,
-                bitwidth: FloatWidth::Unknown,
-            },
+            DataItem::Float(Float::new(f64::NEG_INFINITY, FloatWidth::Unknown)),
             {
                 "-Infinity",
                 "-Infinity",
@@ -63,10 +47,7 @@ testcases! {
         }
 
         nan(value2diag) {
-            DataItem::Float {
-                value: NAN,
-                bitwidth: FloatWidth::Unknown,
-            },
+            DataItem::Float(Float::new(f64::NAN, FloatWidth::Unknown)),
             {
                 "NaN",
                 "NaN",
@@ -76,10 +57,7 @@ testcases! {
 
     mod f16 {
         zero {
-            DataItem::Float {
-                value: 0.0,
-                bitwidth: FloatWidth::Sixteen,
-            },
+            DataItem::Float(Float::new(0.0, FloatWidth::Sixteen)),
             {
                 "0.0_1",
                 "0.0_1",
@@ -90,10 +68,7 @@ testcases! {
         }
 
         one {
-            DataItem::Float {
-                value: 1.0,
-                bitwidth: FloatWidth::Sixteen,
-            },
+            DataItem::Float(Float::new(1.0, FloatWidth::Sixteen)),
             {
                 "1.0_1",
                 "1.0_1",
@@ -104,10 +79,7 @@ testcases! {
         }
 
         half {
-            DataItem::Float {
-                value: 0.5,
-                bitwidth: FloatWidth::Sixteen,
-            },
+            DataItem::Float(Float::new(0.5, FloatWidth::Sixteen)),
             {
                 "0.5_1",
                 "0.5_1",
@@ -118,10 +90,7 @@ testcases! {
         }
 
         infinity {
-            DataItem::Float {
-                value: INFINITY,
-                bitwidth: FloatWidth::Sixteen,
-            },
+            DataItem::Float(Float::new(f64::INFINITY, FloatWidth::Sixteen)),
             {
                 "Infinity_1",
                 "Infinity_1",
@@ -132,10 +101,7 @@ testcases! {
         }
 
         neg_infinity {
-            DataItem::Float {
-                value: NEG_INFINITY,
-                bitwidth: FloatWidth::Sixteen,
-            },
+            DataItem::Float(Float::new(f64::NEG_INFINITY, FloatWidth::Sixteen)),
             {
                 "-Infinity_1",
                 "-Infinity_1",
@@ -146,10 +112,7 @@ testcases! {
         }
 
         nan(value2diag, value2hex) {
-            DataItem::Float {
-                value: NAN,
-                bitwidth: FloatWidth::Sixteen,
-            },
+            DataItem::Float(Float::new(f64::NAN, FloatWidth::Sixteen)),
             {
                 "NaN_1",
                 "NaN_1",
@@ -162,10 +125,7 @@ testcases! {
 
     mod f32 {
         zero {
-            DataItem::Float {
-                value: 0.0,
-                bitwidth: FloatWidth::ThirtyTwo,
-            },
+            DataItem::Float(Float::new(0.0, FloatWidth::ThirtyTwo)),
             {
                 "0.0_2",
                 "0.0_2",
@@ -176,10 +136,7 @@ testcases! {
         }
 
         one {
-            DataItem::Float {
-                value: 1.0,
-                bitwidth: FloatWidth::ThirtyTwo,
-            },
+            DataItem::Float(Float::new(1.0, FloatWidth::ThirtyTwo)),
             {
                 "1.0_2",
                 "1.0_2",
@@ -190,10 +147,7 @@ testcases! {
         }
 
         half {
-            DataItem::Float {
-                value: 0.5,
-                bitwidth: FloatWidth::ThirtyTwo,
-            },
+            DataItem::Float(Float::new(0.5, FloatWidth::ThirtyTwo)),
             {
                 "0.5_2",
                 "0.5_2",
@@ -204,10 +158,7 @@ testcases! {
         }
 
         infinity {
-            DataItem::Float {
-                value: INFINITY,
-                bitwidth: FloatWidth::ThirtyTwo,
-            },
+            DataItem::Float(Float::new(f64::INFINITY, FloatWidth::ThirtyTwo)),
             {
                 "Infinity_2",
                 "Infinity_2",
@@ -218,10 +169,7 @@ testcases! {
         }
 
         neg_infinity {
-            DataItem::Float {
-                value: NEG_INFINITY,
-                bitwidth: FloatWidth::ThirtyTwo,
-            },
+            DataItem::Float(Float::new(f64::NEG_INFINITY, FloatWidth::ThirtyTwo)),
             {
                 "-Infinity_2",
                 "-Infinity_2",
@@ -232,10 +180,7 @@ testcases! {
         }
 
         nan(value2diag, value2hex) {
-            DataItem::Float {
-                value: NAN,
-                bitwidth: FloatWidth::ThirtyTwo,
-            },
+            DataItem::Float(Float::new(f64::NAN, FloatWidth::ThirtyTwo)),
             {
                 "NaN_2",
                 "NaN_2",
@@ -248,10 +193,7 @@ testcases! {
 
     mod f64 {
         zero {
-            DataItem::Float {
-                value: 0.0,
-                bitwidth: FloatWidth::SixtyFour,
-            },
+            DataItem::Float(Float::new(0.0, FloatWidth::SixtyFour)),
             {
                 "0.0_3",
                 "0.0_3",
@@ -262,10 +204,7 @@ testcases! {
         }
 
         one {
-            DataItem::Float {
-                value: 1.0,
-                bitwidth: FloatWidth::SixtyFour,
-            },
+            DataItem::Float(Float::new(1.0, FloatWidth::SixtyFour)),
             {
                 "1.0_3",
                 "1.0_3",
@@ -276,10 +215,7 @@ testcases! {
         }
 
         half {
-            DataItem::Float {
-                value: 0.5,
-                bitwidth: FloatWidth::SixtyFour,
-            },
+            DataItem::Float(Float::new(0.5, FloatWidth::SixtyFour)),
             {
                 "0.5_3",
                 "0.5_3",
@@ -290,10 +226,7 @@ testcases! {
         }
 
         infinity {
-            DataItem::Float {
-                value: f64::INFINITY,
-                bitwidth: FloatWidth::SixtyFour,
-            },
+            DataItem::Float(Float::new(f64::INFINITY, FloatWidth::SixtyFour)),
             {
                 "Infinity_3",
                 "Infinity_3",
@@ -304,10 +237,7 @@ testcases! {
         }
 
         neg_infinity {
-            DataItem::Float {
-                value: f64::NEG_INFINITY,
-                bitwidth: FloatWidth::SixtyFour,
-            },
+            DataItem::Float(Float::new(f64::NEG_INFINITY, FloatWidth::SixtyFour)),
             {
                 "-Infinity_3",
                 "-Infinity_3",
@@ -318,10 +248,7 @@ testcases! {
         }
 
         nan(value2diag, value2hex) {
-            DataItem::Float {
-                value: f64::NAN,
-                bitwidth: FloatWidth::SixtyFour,
-            },
+            DataItem::Float(Float::new(f64::NAN, FloatWidth::SixtyFour)),
             {
                 "NaN_3",
                 "NaN_3",
@@ -335,27 +262,128 @@ testcases! {
     // RFC 8610 Appendix G.5
     mod formats {
         decimal(diag2value) {
-            DataItem::Float {
-                value: 1.5,
-                bitwidth: FloatWidth::Unknown,
-            },
+            DataItem::Float(Float::new(1.5, FloatWidth::Unknown)),
             { "1.5" }
         }
 
         hex1(diag2value) {
-            DataItem::Float {
-                value: 1.5,
-                bitwidth: FloatWidth::Unknown,
-            },
+            DataItem::Float(Float::new(1.5, FloatWidth::Unknown)),
             { "0x1.8p0" }
         }
 
         hex2(diag2value) {
-            DataItem::Float {
-                value: 1.5,
-                bitwidth: FloatWidth::Unknown,
-            },
+            DataItem::Float(Float::new(1.5, FloatWidth::Unknown)),
             { "0x18p-4" }
         }
     }
 }
+
+fn encoded(value: &DataItem) -> String {
+    data_encoding::HEXLOWER.encode(&value.to_bytes())
+}
+
+/// NaNs other than `NaN` keep their sign and payload, including whether they
+/// are signaling, through hex and diagnostic notation
+#[test]
+fn nan_payloads() {
+    for (hex, diag) in [
+        ("f97e00", "NaN_1"),
+        ("f97e01", "float'7e01'"),
+        ("f97c01", "float'7c01'"),
+        ("f9fe00", "float'fe00'"),
+        ("fa7fc00000", "NaN_2"),
+        ("fa7fc00001", "float'7fc00001'"),
+        ("fa7f800001", "float'7f800001'"),
+        ("fb7ff8000000000000", "NaN_3"),
+        ("fb7ff8000000000001", "float'7ff8000000000001'"),
+        ("fb7ff0000000000001", "float'7ff0000000000001'"),
+        ("fbfff8000000000000", "float'fff8000000000000'"),
+    ] {
+        let value = parse_hex(hex).unwrap();
+        assert_eq!(encoded(&value), hex);
+        assert_eq!(value.to_diag(), diag);
+        assert_eq!(parse_diag(diag).unwrap().to_bytes(), value.to_bytes());
+    }
+}
+
+#[test]
+fn negative_zero() {
+    for (hex, diag) in [
+        ("f98000", "-0.0_1"),
+        ("fa80000000", "-0.0_2"),
+        ("fb8000000000000000", "-0.0_3"),
+    ] {
+        let value = parse_hex(hex).unwrap();
+        assert_eq!(value.to_diag(), diag);
+        assert_eq!(encoded(&parse_diag(diag).unwrap()), hex);
+    }
+    assert_eq!(encoded(&parse_diag("-0.0").unwrap()), "fb8000000000000000");
+}
+
+#[test]
+fn float_literal() {
+    assert_eq!(encoded(&parse_diag("float'3e00'").unwrap()), "f93e00");
+    assert_eq!(parse_diag("float'3e00'").unwrap().to_diag(), "1.5_1");
+    for diag in ["float'7e0'", "float'+7e0'", "float'7e0g'", "float''"] {
+        assert!(parse_diag(diag).is_err(), "{diag}");
+    }
+}
+
+#[test]
+fn exact_bits() {
+    assert_eq!(
+        Float::from_f16_bits(0x3e00),
+        Float {
+            bits: 0x3e00,
+            bitwidth: FloatWidth::Sixteen,
+        }
+    );
+    assert_eq!(Float::from_f32(1.5).bits, 0x3fc0_0000);
+    assert_eq!(Float::from_f64(1.5).bits, 0x3ff8_0000_0000_0000);
+    assert_eq!(Float::new(1.1, FloatWidth::Sixteen).bits, 0x3c66);
+    assert_eq!(Float::new(1.1, FloatWidth::Sixteen).as_f64(), 1.099_609_375);
+
+    // A signaling NaN stays signaling at every width
+    let nan = Float {
+        bits: 0x7c01,
+        bitwidth: FloatWidth::Sixteen,
+    };
+    assert_eq!(nan.as_f64().to_bits(), 0x7ff0_0400_0000_0000);
+    assert_eq!(
+        Float::new(nan.as_f64(), FloatWidth::ThirtyTwo).bits,
+        0x7f80_2000
+    );
+}
+
+#[test]
+fn bits_above_width() {
+    let float = Float {
+        bits: 0xffff_3e00,
+        bitwidth: FloatWidth::Sixteen,
+    };
+    assert_eq!(float.encoded_bits(), 0x3e00);
+    assert_eq!(float.as_f64(), 1.5);
+    let item = DataItem::Float(float);
+    assert_eq!(item.to_bytes(), [0xf9, 0x3e, 0x00]);
+    assert_eq!(item.to_diag(), "1.5_1");
+    assert_eq!(item.to_hex(), "f9 3e00 # float(1.5)\n");
+}
+
+#[test]
+fn exactly_representable() {
+    let float = Float::from_f64(1.5);
+    assert!(float.is_exactly_representable_in(FloatWidth::Sixteen));
+    assert!(float.is_exactly_representable_in(FloatWidth::ThirtyTwo));
+    assert!(!Float::from_f64(1.1).is_exactly_representable_in(FloatWidth::ThirtyTwo));
+    assert!(Float::from_f32(1.1).is_exactly_representable_in(FloatWidth::SixtyFour));
+    assert!(!Float::from_f32(1.1).is_exactly_representable_in(FloatWidth::Sixteen));
+    assert!(Float::from_f64(65504.0).is_exactly_representable_in(FloatWidth::Sixteen));
+    assert!(!Float::from_f64(65536.0).is_exactly_representable_in(FloatWidth::Sixteen));
+    assert!(Float::from_f64(f64::INFINITY).is_exactly_representable_in(FloatWidth::Sixteen));
+
+    // Only NaNs with no payload bits below those of the narrower width
+    let nan = |bits| Float::from_f64(f64::from_bits(bits));
+    assert!(nan(0x7ff8_0000_0000_0000).is_exactly_representable_in(FloatWidth::Sixteen));
+    assert!(nan(0x7ff0_0400_0000_0000).is_exactly_representable_in(FloatWidth::Sixteen));
+    assert!(!nan(0x7ff8_0000_0000_0001).is_exactly_representable_in(FloatWidth::ThirtyTwo));
+}
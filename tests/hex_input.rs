@@ -0,0 +1,175 @@
+use cbor_diag::{parse_hex, parse_hex_with, HexInputFormat};
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+const DIAG: &str = r#"{1:"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",2:h''}"#;
+
+#[track_caller]
+fn assert_parses(hex: &str, format: HexInputFormat) {
+    assert_eq!(parse_hex(hex).unwrap().to_diag(), DIAG);
+    assert_eq!(parse_hex_with(hex, format).unwrap().to_diag(), DIAG);
+}
+
+#[test]
+fn plain() {
+    assert_parses(
+        indoc! {r#"
+            a2                      # map(2)
+               01                   #   unsigned(1), "0x01"
+               78 20                #   text(32)
+            	  61616161616161616161616161616161
+            	  61616161616161616161616161616161
+               02                   #   unsigned(2)
+               40                   #   bytes(0)
+        "#},
+        HexInputFormat::Plain,
+    );
+    assert_parses(
+        "a2017820616161616161616161616161616161616161616161616161616161616161616102\r\n40",
+        HexInputFormat::Plain,
+    );
+}
+
+#[test]
+fn plain_starting_with_offset() {
+    // A leading run of hex digits is only an offset when the text looks like
+    // the rest of a dump
+    assert_eq!(
+        parse_hex("d9d9f7 a1 01 02").unwrap().to_diag(),
+        "55799_1({1:2})"
+    );
+    assert_eq!(parse_hex("a101 02").unwrap().to_diag(), "{1:2}");
+}
+
+#[test]
+fn xxd() {
+    assert_parses(
+        indoc! {"
+            00000000: a201 7820 6161 6161 6161 6161 6161 6161  ..x aaaaaaaaaaaa
+            00000010: 6161 6161 6161 6161 6161 6161 6161 6161  aaaaaaaaaaaaaaaa
+            00000020: 6161 6161 0240                           aaaa.@
+        "},
+        HexInputFormat::Xxd,
+    );
+}
+
+#[test]
+fn hexdump() {
+    assert_parses(
+        indoc! {"
+            00000000  a2 01 78 20 61 61 61 61  61 61 61 61 61 61 61 61  |..x aaaaaaaaaaaa|
+            00000010  61 61 61 61 61 61 61 61  61 61 61 61 61 61 61 61  |aaaaaaaaaaaaaaaa|
+            00000020  61 61 61 61 02 40                                 |aaaa.@|
+            00000026
+        "},
+        HexInputFormat::Dump,
+    );
+}
+
+#[test]
+fn od() {
+    assert_parses(
+        indoc! {"
+            000000 a2 01 78 20 61 61 61 61 61 61 61 61 61 61 61 61  >..x aaaaaaaaaaaa<
+            000010 61 61 61 61 61 61 61 61 61 61 61 61 61 61 61 61  >aaaaaaaaaaaaaaaa<
+            000020 61 61 61 61 02 40                                >aaaa.@<
+            000026
+        "},
+        HexInputFormat::Dump,
+    );
+}
+
+#[test]
+fn wireshark() {
+    assert_parses(
+        indoc! {"
+            0000   a2 01 78 20 61 61 61 61 61 61 61 61 61 61 61 61   ..x aaaaaaaaaaaa
+            0010   61 61 61 61 61 61 61 61 61 61 61 61 61 61 61 61   aaaaaaaaaaaaaaaa
+            0020   61 61 61 61 02 40                                 aaaa.@
+        "},
+        HexInputFormat::Dump,
+    );
+}
+
+#[test]
+fn repeated_lines() {
+    let expected = format!("h'{}',1", "00".repeat(64));
+    for dump in [
+        // hexdump -C
+        indoc! {"
+            00000000  58 40 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |X@..............|
+            00000010  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |................|
+            *
+            00000040  00 00 01                                          |...|
+            00000043
+        "},
+        // od -t x1, with octal offsets
+        indoc! {"
+            0000000 58 40 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+            0000020 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00 00
+            *
+            0000100 00 00 01
+            0000103
+        "},
+        // xxd -a
+        indoc! {"
+            00000000: 5840 0000 0000 0000 0000 0000 0000 0000  X@..............
+            00000010: 0000 0000 0000 0000 0000 0000 0000 0000  ................
+            *
+            00000040: 0000 01                                  ...
+        "},
+    ] {
+        let items = cbor_diag::parse_hex_seq(dump).unwrap();
+        assert_eq!(
+            cbor_diag::to_diag_seq(&items, &Default::default()),
+            expected
+        );
+    }
+}
+
+#[test]
+fn repeated_lines_too_long() {
+    let dump = indoc! {"
+        00000000: 5840 0000 0000 0000 0000 0000 0000 0000  X@..............
+        *
+        fffffff0: 0000 01                                  ...
+    "};
+    let err = parse_hex_with(dump, HexInputFormat::Xxd).unwrap_err();
+    assert!(
+        err.to_string().contains("Repeated lines expand beyond"),
+        "{err}"
+    );
+}
+
+#[test]
+fn prefixed() {
+    assert_parses(
+        indoc! {"
+            #include <stdint.h>
+
+            /* {1: \"a...\", 2: h''} */
+            const uint8_t data[] = {
+                0xa2, 0x01, 0x78, 0x20, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61,
+                0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61,
+                0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61, 0x61,
+                0x2, 0X40, // h''
+            };
+            const size_t data_len = 38;
+        "},
+        HexInputFormat::Prefixed,
+    );
+    assert_parses(
+        "0xa201782061616161616161616161616161616161616161616161616161616161616161610240",
+        HexInputFormat::Prefixed,
+    );
+}
+
+#[test]
+fn invalid() {
+    assert!(parse_hex("a1 01 0").is_err());
+    assert!(parse_hex("{0xa1, 0x01, 2}").is_err());
+    assert!(parse_hex("0xa1, 0x01, 0x123").is_err());
+    assert!(parse_hex_with("a1 01 02", HexInputFormat::Prefixed).is_err());
+    assert!(parse_hex_with("0000 a1 01 0g", HexInputFormat::Dump).is_err());
+    assert!(parse_hex_with("0000 a1 01 02", HexInputFormat::Plain).is_err());
+}
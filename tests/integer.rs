@@ -1,4 +1,5 @@
-use cbor_diag::{DataItem, IntegerWidth};
+use cbor_diag::{ByteString, DataItem, IntegerWidth, Tag};
+use data_encoding_macro::hexlower as hex;
 use indoc::indoc;
 
 #[macro_use]
@@ -523,4 +524,104 @@ testcases! {
             { "0b1001001100111" }
         }
     }
+
+    mod bignum {
+        positive(diag2value) {
+            DataItem::Tag {
+                tag: Tag::POSITIVE_BIGNUM,
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("010000000000000000").into(),
+                    bitwidth: IntegerWidth::Unknown,
+                })),
+            },
+            { "18446744073709551616" }
+        }
+
+        negative(diag2value) {
+            DataItem::Tag {
+                tag: Tag::NEGATIVE_BIGNUM,
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("010000000000000000").into(),
+                    bitwidth: IntegerWidth::Unknown,
+                })),
+            },
+            { "-18446744073709551617" }
+        }
+
+        large(diag2value) {
+            DataItem::Tag {
+                tag: Tag::POSITIVE_BIGNUM,
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("0100000000000000000000000000000000000000").into(),
+                    bitwidth: IntegerWidth::Unknown,
+                })),
+            },
+            { "0x100000000000000000000000000000000000000" }
+        }
+
+        max_integer(diag2value) {
+            DataItem::Integer {
+                value: u64::MAX,
+                bitwidth: IntegerWidth::Unknown,
+            },
+            { "18446744073709551615" }
+        }
+
+        min_negative(diag2value) {
+            DataItem::Negative {
+                value: u64::MAX,
+                bitwidth: IntegerWidth::Unknown,
+            },
+            { "-18446744073709551616" }
+        }
+    }
+}
+
+#[test]
+fn bignum_with_encoding_indicator() {
+    assert!(utils::parse_diag("18446744073709551616_3").is_err());
+    assert!(utils::parse_diag("-18446744073709551617_3").is_err());
+}
+
+fn with_bignum_literals(diag: &str) -> String {
+    let value = utils::parse_diag(diag).unwrap();
+    let output = value.to_diag_with(&cbor_diag::DiagOptions {
+        bignum_literals: true,
+        ..cbor_diag::DiagOptions::default()
+    });
+    assert_eq!(
+        utils::parse_diag(&output).unwrap().to_bytes(),
+        value.to_bytes()
+    );
+    output
+}
+
+#[test]
+fn bignum_literals() {
+    assert_eq!(
+        with_bignum_literals("[2(h'010000000000000000'), 3(h'010000000000000000')]"),
+        "[18446744073709551616,-18446744073709551617]"
+    );
+    assert_eq!(
+        with_bignum_literals("4([-2, 2(h'0100000000000000000000')])"),
+        "4([-2,1208925819614629174706176])"
+    );
+}
+
+#[test]
+fn bignum_literals_fallback() {
+    // Small enough for a plain integer, leading zeros, non-preferred widths
+    // and invalid contents
+    for diag in [
+        "2(h'01')",
+        "3(h'')",
+        "2(h'00010000000000000000')",
+        "2_0(h'010000000000000000')",
+        "2(\"text\")",
+    ] {
+        assert_eq!(with_bignum_literals(diag), diag);
+    }
 }
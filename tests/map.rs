@@ -674,3 +674,103 @@ testcases! {
         }
     }
 }
+
+fn with_cose_profile(diag: &str) -> String {
+    utils::parse_diag(diag)
+        .unwrap()
+        .to_hex_with(&cbor_diag::HexOptions {
+            profile: Some(cbor_diag::AnnotationProfile::Cose),
+            ..cbor_diag::HexOptions::default()
+        })
+}
+
+#[test]
+fn cose_ec2_private_key() {
+    // RFC 9052 Appendix C.7.2, truncated coordinates
+    assert_eq!(
+        utils::DisplayDebug(with_cose_profile(
+            "{1: 2, 2: 'meriadoc', -1: 1, -2: h'65eda5a1', -3: h'1e52ed75', -4: h'aff907c9'}"
+        )),
+        utils::DisplayDebug(indoc!(
+            r#"
+            a6                     # COSE_Key, map(6)
+               01                  #   kty, unsigned(1)
+               02                  #   EC2, unsigned(2)
+               02                  #   kid, unsigned(2)
+               48                  #   bytes(8)
+                  6d65726961646f63 #     "meriadoc"
+               20                  #   crv, negative(-1)
+               01                  #   P-256, unsigned(1)
+               21                  #   x, negative(-2)
+               44                  #   bytes(4)
+                  65eda5a1         #     "e\xed\xa5\xa1"
+               22                  #   y, negative(-3)
+               44                  #   bytes(4)
+                  1e52ed75         #     "\x1eR\xedu"
+               23                  #   d, negative(-4)
+               44                  #   bytes(4)
+                  aff907c9         #     "\xaf\xf9\x07\xc9"
+                                   #     warning: private key material
+            "#
+        ))
+    );
+}
+
+#[test]
+fn cose_key_set() {
+    assert_eq!(
+        utils::DisplayDebug(with_cose_profile(
+            "[{1: 1, -1: 6, 3: -8, 4: [2]}, {1: 4, -1: h'00', 3: 5}]"
+        )),
+        utils::DisplayDebug(indoc!(
+            r#"
+            82          # COSE_KeySet, array(2)
+               a4       #   COSE_Key, map(4)
+                  01    #     kty, unsigned(1)
+                  01    #     OKP, unsigned(1)
+                  20    #     crv, negative(-1)
+                  06    #     Ed25519, unsigned(6)
+                  03    #     alg, unsigned(3)
+                  27    #     EdDSA, negative(-8)
+                  04    #     key_ops, unsigned(4)
+                  81    #     array(1)
+                     02 #       verify, unsigned(2)
+               a3       #   COSE_Key, map(3)
+                  01    #     kty, unsigned(1)
+                  04    #     Symmetric, unsigned(4)
+                  20    #     k, negative(-1)
+                  41    #     bytes(1)
+                     00 #       "\x00"
+                        #       warning: private key material
+                  03    #     alg, unsigned(3)
+                  05    #     HMAC 256/256, unsigned(5)
+            "#
+        ))
+    );
+}
+
+#[test]
+fn cose_not_a_key() {
+    let diag = "[{2: 1, -1: 6}, {1: [1]}]";
+    assert_eq!(
+        utils::DisplayDebug(with_cose_profile(diag)),
+        utils::DisplayDebug(utils::parse_diag(diag).unwrap().to_hex())
+    );
+}
+
+#[test]
+fn cose_header_map() {
+    // A protected header with an algorithm (label 1) and key id (label 4)
+    let diag = "{1: -7, 4: h'11'}";
+    assert_eq!(
+        utils::DisplayDebug(with_cose_profile(diag)),
+        utils::DisplayDebug(utils::parse_diag(diag).unwrap().to_hex())
+    );
+
+    // A128GCM with an IV (label 5), both also valid key labels
+    let diag = "{1: 1, 5: h'89f52f65a1c580933b5261a7'}";
+    assert_eq!(
+        utils::DisplayDebug(with_cose_profile(diag)),
+        utils::DisplayDebug(utils::parse_diag(diag).unwrap().to_hex())
+    );
+}
@@ -0,0 +1,153 @@
+use cbor_diag::{parse_diag, Error};
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+#[track_caller]
+fn assert_error(diag: &str, expected: &str) {
+    let Error::Todo(message) = parse_diag(diag).unwrap_err();
+    assert_eq!(message, expected.trim_end_matches('\n'));
+}
+
+#[test]
+fn missing_colon() {
+    assert_error(
+        r#"{"a" 1}"#,
+        indoc! {r#"
+            line 1, column 6: expected ':' after map key, found '1'
+            {"a" 1}
+                 ^
+        "#},
+    );
+}
+
+#[test]
+fn missing_comma() {
+    assert_error(
+        "[1, 2 3]",
+        indoc! {"
+            line 1, column 7: expected ',' or ']', found '3'
+            [1, 2 3]
+                  ^
+        "},
+    );
+}
+
+#[test]
+fn unclosed() {
+    assert_error(
+        "[1,",
+        indoc! {"
+            line 1, column 4: expected a data item, found end of input
+            [1,
+               ^
+        "},
+    );
+    assert_error(
+        r#""abc"#,
+        indoc! {r#"
+            line 1, column 5: expected '"', found end of input
+            "abc
+                ^
+        "#},
+    );
+}
+
+#[test]
+fn later_line() {
+    assert_error(
+        "{\n  1: [1, 2],\n\t\"b\": 32(1 2)\n}",
+        indoc! {"
+            line 3, column 12: expected ')', found '2'
+            \t\"b\": 32(1 2)
+            \t          ^
+        "},
+    );
+}
+
+#[test]
+fn invalid_contents() {
+    assert_error(
+        "h'0g'",
+        indoc! {r#"
+            line 1, column 4: expected "'", found 'g'
+            h'0g'
+               ^
+        "#},
+    );
+    assert_error(
+        r#""a\x""#,
+        indoc! {r#"
+            line 1, column 4: expected an escape sequence, found 'x'
+            "a\x"
+               ^
+        "#},
+    );
+}
+
+#[test]
+fn invalid_encoding_indicator() {
+    assert_error(
+        "24_i",
+        indoc! {"
+            line 1, column 1: expected an integer below 24 for _i, found '2'
+            24_i
+            ^
+        "},
+    );
+    assert_error(
+        "[-257_0]",
+        indoc! {"
+            line 1, column 2: expected an integer from -256 for _0, found '-'
+            [-257_0]
+             ^
+        "},
+    );
+    assert_error(
+        "simple(24)_0",
+        indoc! {"
+            line 1, column 1: expected a simple value from 32 for _0, found 's'
+            simple(24)_0
+            ^
+        "},
+    );
+}
+
+#[test]
+fn invalid_app_literal() {
+    assert_error(
+        "dt'bogus'",
+        indoc! {"
+            line 1, column 1: expected an RFC 3339 date-time in dt'', found 'd'
+            dt'bogus'
+            ^
+        "},
+    );
+    assert_error(
+        "{1: ip'192.0.2'}",
+        indoc! {"
+            line 1, column 5: expected an IP address or prefix in ip'', found 'i'
+            {1: ip'192.0.2'}
+                ^
+        "},
+    );
+    assert_error(
+        "hash'abc'",
+        indoc! {"
+            line 1, column 1: expected a supported application literal, hash'' is not supported, found 'h'
+            hash'abc'
+            ^
+        "},
+    );
+}
+
+#[test]
+fn remaining_text() {
+    assert_error(
+        "[1] 2",
+        indoc! {"
+            line 1, column 5: expected end of input, found '2'
+            [1] 2
+                ^
+        "},
+    );
+}
@@ -0,0 +1,107 @@
+use cbor_diag::{
+    parse_bytes_seq, parse_diag_seq, parse_hex_seq, to_diag_seq, to_hex_seq, DataItem, DiagOptions,
+    HexOptions, IntegerWidth, TextString,
+};
+use data_encoding_macro::hexlower as hex;
+use indoc::indoc;
+use pretty_assertions::assert_eq;
+
+fn items() -> Vec<DataItem> {
+    vec![
+        DataItem::Integer {
+            value: 1,
+            bitwidth: IntegerWidth::Zero,
+        },
+        DataItem::Array {
+            data: vec![DataItem::Integer {
+                value: 2,
+                bitwidth: IntegerWidth::Zero,
+            }],
+            bitwidth: Some(IntegerWidth::Unknown),
+        },
+        DataItem::TextString(TextString {
+            data: "a".into(),
+            bitwidth: IntegerWidth::Unknown,
+        }),
+    ]
+}
+
+#[test]
+fn parse_diag() {
+    assert_eq!(parse_diag_seq(r#"1, [2], "a""#).unwrap(), items());
+    assert_eq!(
+        parse_diag_seq("# the items\n1, / one / [2],\n\"a\" # done").unwrap(),
+        items()
+    );
+    assert_eq!(parse_diag_seq("").unwrap(), vec![]);
+    assert_eq!(parse_diag_seq("  # nothing\n").unwrap(), vec![]);
+    assert!(parse_diag_seq("1 2").is_err());
+    assert!(parse_diag_seq("1,").is_err());
+    assert!(parse_diag_seq(",").is_err());
+}
+
+#[test]
+fn to_diag() {
+    assert_eq!(diag(&items()), r#"1,[2],"a""#);
+    assert_eq!(
+        to_diag_seq(
+            &items(),
+            &DiagOptions {
+                pretty: true,
+                ..DiagOptions::default()
+            }
+        ),
+        indoc! {r#"
+            1,
+            [2],
+            "a""#}
+    );
+    assert_eq!(diag(&[]), "");
+    assert_eq!(parse_diag_seq(diag(&items())).unwrap(), items());
+}
+
+/// Items parsed from binary have known widths, so are compared by their
+/// diagnostic notation
+fn diag(items: &[DataItem]) -> String {
+    to_diag_seq(items, &DiagOptions::default())
+}
+
+#[test]
+fn bytes() {
+    assert_eq!(
+        diag(&parse_bytes_seq(hex!("0181026161")).unwrap()),
+        r#"1,[2],"a""#
+    );
+    assert_eq!(parse_bytes_seq([]).unwrap(), vec![]);
+    assert!(parse_bytes_seq(hex!("01810262")).is_err());
+    assert!(parse_bytes_seq(hex!("01ff")).is_err());
+}
+
+#[test]
+fn hex() {
+    assert_eq!(
+        diag(&parse_hex_seq("01 8102 6161").unwrap()),
+        r#"1,[2],"a""#
+    );
+    assert_eq!(parse_hex_seq("").unwrap(), vec![]);
+    assert!(parse_hex_seq("01 8102 62").is_err());
+}
+
+#[test]
+fn to_hex() {
+    let annotated = to_hex_seq(&items(), &HexOptions::default());
+    assert_eq!(
+        annotated,
+        indoc! {r#"
+            01 # unsigned(1)
+
+            81    # array(1)
+               02 #   unsigned(2)
+
+            61    # text(1)
+               61 #   "a"
+        "#}
+    );
+    assert_eq!(diag(&parse_hex_seq(annotated).unwrap()), r#"1,[2],"a""#);
+    assert_eq!(to_hex_seq(&[], &HexOptions::default()), "");
+}
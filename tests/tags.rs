@@ -1,4 +1,9 @@
-use cbor_diag::{ByteString, DataItem, FloatWidth, IntegerWidth, Simple, Tag, TextString};
+#![recursion_limit = "256"]
+
+use cbor_diag::{
+    parse_bytes, parse_bytes_with, ByteString, DataItem, Float, FloatWidth, IntegerWidth, Magic,
+    ParseOptions, Simple, Tag, TextString, TypedArrayStyle,
+};
 use data_encoding_macro::hexlower as hex;
 use indoc::indoc;
 
@@ -212,10 +217,7 @@ testcases! {
                     DataItem::Tag {
                         tag: Tag::EPOCH_DATETIME,
                         bitwidth: IntegerWidth::Zero,
-                        value: Box::new(DataItem::Float {
-                            value: 1_533_233_978.125,
-                            bitwidth: FloatWidth::Unknown,
-                        })
+                        value: Box::new(DataItem::Float(Float::new(1_533_233_978.125, FloatWidth::Unknown)))
                     },
                 ],
                 bitwidth: Some(IntegerWidth::Unknown),
@@ -738,6 +740,160 @@ testcases! {
             }
         }
 
+        language_tagged_string(diag2value, value2diag) {
+            DataItem::Tag {
+                tag: Tag::LANGUAGE_TAGGED_STRING,
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::TextString(TextString {
+                            data: "en-US".into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                        DataItem::TextString(TextString {
+                            data: "Hello".into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                    ],
+                    bitwidth: Some(IntegerWidth::Unknown),
+                }),
+            },
+            {
+                r#"38(["en-US","Hello"])/language(en-US)/"#,
+                r#"38(["en-US", "Hello"]) / language(en-US) /"#,
+            }
+        }
+
+        language_tagged_string_direction(diag2value, value2diag) {
+            DataItem::Tag {
+                tag: Tag::LANGUAGE_TAGGED_STRING,
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::TextString(TextString {
+                            data: "ar".into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                        DataItem::TextString(TextString {
+                            data: "مرحبا".into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                        DataItem::Simple(Simple::TRUE),
+                    ],
+                    bitwidth: Some(IntegerWidth::Unknown),
+                }),
+            },
+            {
+                r#"38(["ar","مرحبا",true])/language(ar), right-to-left/"#,
+                r#"38(["ar", "مرحبا", true]) / language(ar), right-to-left /"#,
+            }
+        }
+
+        language_tagged_string_invalid(diag2value, value2diag) {
+            DataItem::Tag {
+                tag: Tag::LANGUAGE_TAGGED_STRING,
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::TextString(TextString {
+                            data: "en-".into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                        DataItem::TextString(TextString {
+                            data: "Hello".into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                    ],
+                    bitwidth: Some(IntegerWidth::Unknown),
+                }),
+            },
+            {
+                r#"38(["en-","Hello"])"#,
+                r#"38(["en-", "Hello"])"#,
+            }
+        }
+
+        encoded_cbor_json(diag2value, value2diag) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR,
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("7b2261223a20317d").into(),
+                    bitwidth: IntegerWidth::Unknown,
+                })),
+            },
+            {
+                r#"24(h'7b2261223a20317d')/{"a":1}/"#,
+                r#"
+                24(h'7b2261223a20317d') / {
+                    "a": 1
+                } /
+                "#,
+            }
+        }
+
+        embedded_json(diag2value, value2diag) {
+            DataItem::Tag {
+                tag: Tag::EMBEDDED_JSON,
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("7b2261223a205b312c2022782f79225d7d").into(),
+                    bitwidth: IntegerWidth::Unknown,
+                })),
+            },
+            {
+                r#"262(h'7b2261223a205b312c2022782f79225d7d')/{"a":[1,"x\u002fy"]}/"#,
+                r#"
+                262(h'7b2261223a205b312c2022782f79225d7d') / {
+                    "a": [
+                        1,
+                        "x\u002fy"
+                    ]
+                } /
+                "#,
+            }
+        }
+
+        embedded_json_nested(diag2value, value2diag) {
+            DataItem::Array {
+                data: vec![
+                    DataItem::Tag {
+                        tag: Tag::EMBEDDED_JSON,
+                        bitwidth: IntegerWidth::Unknown,
+                        value: Box::new(DataItem::ByteString(ByteString {
+                            data: hex!("5b747275652c207b7d5d").into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        })),
+                    },
+                ],
+                bitwidth: Some(IntegerWidth::Unknown),
+            },
+            {
+                r#"[262(h'5b747275652c207b7d5d')/[true,{}]/]"#,
+                r#"
+                [262(h'5b747275652c207b7d5d') / [
+                    true,
+                    {}
+                ] /]
+                "#,
+            }
+        }
+
+        embedded_json_invalid(diag2value, value2diag) {
+            DataItem::Tag {
+                tag: Tag::EMBEDDED_JSON,
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("5b312c5d").into(),
+                    bitwidth: IntegerWidth::Unknown,
+                })),
+            },
+            {
+                r#"262(h'5b312c5d')/invalid embedded json, expected value at byte 3/"#,
+                r#"262(h'5b312c5d') / invalid embedded json, expected value at byte 3 /"#,
+            }
+        }
+
         self_describe_cbor(diag2value, value2diag) {
             DataItem::Tag {
                 tag: Tag::SELF_DESCRIBE_CBOR,
@@ -784,389 +940,1094 @@ testcases! {
         }
     }
 
-    mod hex_tests {
-        date_time(hex2value, value2hex) {
-            DataItem::Array {
-                data: vec![
-                    DataItem::Tag {
-                        tag: Tag::DATETIME,
-                        bitwidth: IntegerWidth::Zero,
-                        value: Box::new(DataItem::TextString(TextString {
-                            data: "2018-08-02T18:19:38Z".into(),
-                            bitwidth: IntegerWidth::Zero,
-                        }))
-                    },
-                    DataItem::Tag {
-                        tag: Tag::DATETIME,
-                        bitwidth: IntegerWidth::Zero,
-                        value: Box::new(DataItem::TextString(TextString {
-                            data: "1921-06-01T05:40:21Z".into(),
-                            bitwidth: IntegerWidth::Zero,
-                        }))
-                    },
-                    DataItem::Tag {
-                        tag: Tag::DATETIME,
-                        bitwidth: IntegerWidth::Zero,
-                        value: Box::new(DataItem::TextString(TextString {
-                            data: "2018-08-02T18:19:38.125Z".into(),
-                            bitwidth: IntegerWidth::Eight,
-                        }))
-                    },
-                ],
-                bitwidth: Some(IntegerWidth::Zero),
+    // EDN application-extension literals
+    mod app_literals {
+        epoch_datetime(diag2value) {
+            DataItem::Tag {
+                tag: Tag::EPOCH_DATETIME,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::Integer {
+                    value: 1672531200,
+                    bitwidth: IntegerWidth::Unknown,
+                }),
             },
-            indoc!(r#"
-               83                                                        # array(3)
-                  c0                                                     #   standard datetime string, tag(0)
-                     74                                                  #     text(20)
-                        323031382d30382d30325431383a31393a33385a         #       "2018-08-02T18:19:38Z"
-                                                                         #     epoch(1533233978)
-                  c0                                                     #   standard datetime string, tag(0)
-                     74                                                  #     text(20)
-                        313932312d30362d30315430353a34303a32315a         #       "1921-06-01T05:40:21Z"
-                                                                         #     epoch(-1533233979)
-                  c0                                                     #   standard datetime string, tag(0)
-                     78 18                                               #     text(24)
-                        323031382d30382d30325431383a31393a33382e3132355a #       "2018-08-02T18:19:38.125Z"
-                                                                         #     epoch(1533233978.125)
-            "#),
+            { "dt'2023-01-01T00:00:00Z'" }
         }
 
-        epoch_date_time(hex2value, value2hex) {
-            DataItem::Array {
-                data: vec![
-                    DataItem::Tag {
-                        tag: Tag::EPOCH_DATETIME,
-                        bitwidth: IntegerWidth::Zero,
-                        value: Box::new(DataItem::Integer {
-                            value: 1_533_233_978,
-                            bitwidth: IntegerWidth::ThirtyTwo,
-                        })
-                    },
-                    DataItem::Tag {
-                        tag: Tag::EPOCH_DATETIME,
-                        bitwidth: IntegerWidth::Zero,
-                        value: Box::new(DataItem::Negative {
-                            value: 1_533_233_978,
-                            bitwidth: IntegerWidth::ThirtyTwo,
-                        })
-                    },
-                    DataItem::Tag {
-                        tag: Tag::EPOCH_DATETIME,
-                        bitwidth: IntegerWidth::Zero,
-                        value: Box::new(DataItem::Float {
-                            value: 1_533_233_978.125,
-                            bitwidth: FloatWidth::SixtyFour,
-                        })
-                    },
-                ],
-                bitwidth: Some(IntegerWidth::Zero),
+        epoch_datetime_offset(diag2value) {
+            DataItem::Tag {
+                tag: Tag::EPOCH_DATETIME,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::Integer {
+                    value: 1672531200,
+                    bitwidth: IntegerWidth::Unknown,
+                }),
             },
-            indoc!(r#"
-                83                        # array(3)
-                   c1                     #   epoch datetime value, tag(1)
-                      1a 5b634b3a         #     unsigned(1,533,233,978)
-                                          #     datetime(2018-08-02T18:19:38Z)
-                   c1                     #   epoch datetime value, tag(1)
-                      3a 5b634b3a         #     negative(-1,533,233,979)
-                                          #     datetime(1921-06-01T05:40:21Z)
-                   c1                     #   epoch datetime value, tag(1)
-                      fb 41d6d8d2ce880000 #     float(1,533,233,978.125)
-                                          #     datetime(2018-08-02T18:19:38.125Z)
-            "#),
+            { "dt'2023-01-01T01:00:00+01:00'" }
         }
 
-        positive_bignum(hex2value, value2hex) {
+        epoch_datetime_negative(diag2value) {
             DataItem::Tag {
-                tag: Tag::POSITIVE_BIGNUM,
+                tag: Tag::EPOCH_DATETIME,
                 bitwidth: IntegerWidth::Zero,
-                value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("000001ffffffffffffffffffffff0000000000000000000000").into(),
-                    bitwidth: IntegerWidth::Eight,
-                }))
+                value: Box::new(DataItem::Negative {
+                    value: 14159023,
+                    bitwidth: IntegerWidth::Unknown,
+                }),
             },
-            indoc!(r#"
-                c2                                     # positive bignum, tag(2)
-                   58 19                               #   bytes(25)
-                      000001ffffffffffffffffffffff0000 #     "\x00\x00\x01\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00\x00"
-                      000000000000000000               #     "\x00\x00\x00\x00\x00\x00\x00\x00\x00"
-                                                       #   bignum(191561942608236107294793378084303638130997321548169216)
-            "#),
+            { "dt'1969-07-21T02:56:16Z'" }
         }
 
-        negative_bignum(hex2value, value2hex) {
+        epoch_datetime_fractional(diag2value) {
             DataItem::Tag {
-                tag: Tag::NEGATIVE_BIGNUM,
+                tag: Tag::EPOCH_DATETIME,
                 bitwidth: IntegerWidth::Zero,
-                value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("123456789abcdeffedcba987654321").into(),
-                    bitwidth: IntegerWidth::Eight,
-                }))
+                value: Box::new(DataItem::Float(Float::new(1672531200.5, FloatWidth::Unknown))),
             },
-            indoc!(r#"
-                c3                                   # negative bignum, tag(3)
-                   58 0f                             #   bytes(15)
-                      123456789abcdeffedcba987654321 #     "\x124Vx\x9a\xbc\xde\xff\xed\xcb\xa9\x87eC!"
-                                                     #   bignum(-94522879700260684208272210605196066)
-            "#),
+            { "dt'2023-01-01T00:00:00.5Z'" }
         }
 
-        decimal_fraction(hex2value, value2hex) {
+        datetime(diag2value) {
             DataItem::Tag {
-                tag: Tag::DECIMAL_FRACTION,
+                tag: Tag::DATETIME,
                 bitwidth: IntegerWidth::Zero,
-                value: Box::new(DataItem::Array {
-                    data: vec![
-                        DataItem::Negative {
-                            value: 1,
-                            bitwidth: IntegerWidth::Zero,
-                        },
-                        DataItem::Integer {
-                            value: 27315,
-                            bitwidth: IntegerWidth::Sixteen,
-                        },
-                    ],
-                    bitwidth: Some(IntegerWidth::Zero),
-                })
+                value: Box::new(DataItem::TextString(TextString {
+                    data: "2023-01-01T00:00:00Z".into(),
+                    bitwidth: IntegerWidth::Unknown,
+                })),
             },
-            indoc!(r#"
-                c4            # decimal fraction, tag(4)
-                   82         #   array(2)
-                      21      #     negative(-2)
-                      19 6ab3 #     unsigned(27,315)
-                              #   decimal fraction(5463/20)
-            "#),
+            { "DT'2023-01-01T00:00:00Z'" }
         }
 
-        bigfloat(hex2value, value2hex) {
+        ipv4_address(diag2value) {
             DataItem::Tag {
-                tag: Tag::BIGFLOAT,
-                bitwidth: IntegerWidth::Zero,
+                tag: Tag::IPV4,
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("c0000201").into(),
+                    bitwidth: IntegerWidth::Unknown,
+                })),
+            },
+            { "ip'192.0.2.1'" }
+        }
+
+        ipv4_prefix(diag2value) {
+            DataItem::Tag {
+                tag: Tag::IPV4,
+                bitwidth: IntegerWidth::Unknown,
                 value: Box::new(DataItem::Array {
                     data: vec![
-                        DataItem::Negative {
-                            value: 0,
-                            bitwidth: IntegerWidth::Zero,
-                        },
                         DataItem::Integer {
-                            value: 3,
-                            bitwidth: IntegerWidth::Zero,
+                            value: 24,
+                            bitwidth: IntegerWidth::Unknown,
                         },
+                        DataItem::ByteString(ByteString {
+                            data: hex!("c00002").into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
                     ],
-                    bitwidth: Some(IntegerWidth::Zero),
-                })
+                    bitwidth: Some(IntegerWidth::Unknown),
+                }),
             },
-            indoc!(r#"
-                c5       # bigfloat, tag(5)
-                   82    #   array(2)
-                      20 #     negative(-1)
-                      03 #     unsigned(3)
-                         #   bigfloat(3/2)
-            "#),
+            { "ip'192.0.2.0/24'" }
         }
 
-        decimal_fraction_bignum(hex2value, value2hex) {
+        ipv4_address_with_prefix(diag2value) {
             DataItem::Tag {
-                tag: Tag::DECIMAL_FRACTION,
-                bitwidth: IntegerWidth::Zero,
+                tag: Tag::IPV4,
+                bitwidth: IntegerWidth::Unknown,
                 value: Box::new(DataItem::Array {
                     data: vec![
-                        DataItem::Negative {
-                            value: 52,
-                            bitwidth: IntegerWidth::Eight,
-                        },
-                        DataItem::Tag {
-                            tag: Tag::POSITIVE_BIGNUM,
-                            bitwidth: IntegerWidth::Zero,
-                            value: Box::new(DataItem::ByteString(ByteString {
-                                data: hex!("000001ffffffffffffffffffffff0000000000000000000000").into(),
-                                bitwidth: IntegerWidth::Eight,
-                            }))
+                        DataItem::ByteString(ByteString {
+                            data: hex!("c0000201").into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                        DataItem::Integer {
+                            value: 24,
+                            bitwidth: IntegerWidth::Unknown,
                         },
                     ],
-                    bitwidth: Some(IntegerWidth::Zero),
-                })
+                    bitwidth: Some(IntegerWidth::Unknown),
+                }),
             },
-            indoc!(r#"
-                c4                                           # decimal fraction, tag(4)
-                   82                                        #   array(2)
-                      38 34                                  #     negative(-53)
-                      c2                                     #     positive bignum, tag(2)
-                         58 19                               #       bytes(25)
-                            000001ffffffffffffffffffffff0000 #         "\x00\x00\x01\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00\x00"
-                            000000000000000000               #         "\x00\x00\x00\x00\x00\x00\x00\x00\x00"
-                                                             #       bignum(191561942608236107294793378084303638130997321548169216)
-                                                             #   decimal fraction(21267647932558653966460912930125774848/11102230246251565404236316680908203125)
-            "#),
+            { "ip'192.0.2.1/24'" }
         }
 
-        bigfloat_bignum(hex2value, value2hex) {
+        ipv4_empty_prefix(diag2value) {
             DataItem::Tag {
-                tag: Tag::BIGFLOAT,
-                bitwidth: IntegerWidth::Zero,
+                tag: Tag::IPV4,
+                bitwidth: IntegerWidth::Unknown,
                 value: Box::new(DataItem::Array {
                     data: vec![
-                        DataItem::Negative {
-                            value: 175,
-                            bitwidth: IntegerWidth::Eight,
-                        },
-                        DataItem::Tag {
-                            tag: Tag::POSITIVE_BIGNUM,
+                        DataItem::Integer {
+                            value: 0,
                             bitwidth: IntegerWidth::Zero,
-                            value: Box::new(DataItem::ByteString(ByteString {
-                                data: hex!("000001ffffffffffffffffffffff0000000000000000000000").into(),
-                                bitwidth: IntegerWidth::Eight,
-                            }))
                         },
+                        DataItem::ByteString(ByteString {
+                            data: vec![],
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
                     ],
-                    bitwidth: Some(IntegerWidth::Zero),
-                })
+                    bitwidth: Some(IntegerWidth::Unknown),
+                }),
             },
-            indoc!(r#"
-                c5                                           # bigfloat, tag(5)
-                   82                                        #   array(2)
-                      38 af                                  #     negative(-176)
-                      c2                                     #     positive bignum, tag(2)
-                         58 19                               #       bytes(25)
-                            000001ffffffffffffffffffffff0000 #         "\x00\x00\x01\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00\x00"
-                            000000000000000000               #         "\x00\x00\x00\x00\x00\x00\x00\x00\x00"
-                                                             #       bignum(191561942608236107294793378084303638130997321548169216)
-                                                             #   bigfloat(618970019642690137449562111/309485009821345068724781056)
-            "#),
+            { "ip'0.0.0.0/0'" }
         }
 
-        base64url_encoding(hex2value, value2hex) {
+        ipv6_address(diag2value) {
             DataItem::Tag {
-                tag: Tag::ENCODED_BASE64URL,
-                bitwidth: IntegerWidth::Zero,
+                tag: Tag::IPV6,
+                bitwidth: IntegerWidth::Unknown,
                 value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("123456789abcdeffedcba9876543").into(),
-                    bitwidth: IntegerWidth::Zero,
+                    data: hex!("20010db8000000000000000000000001").into(),
+                    bitwidth: IntegerWidth::Unknown,
                 })),
             },
-            indoc!(r#"
-                d5                                 # suggested base64url encoding, tag(21)
-                   4e                              #   bytes(14)
-                      123456789abcdeffedcba9876543 #     b64'EjRWeJq83v_ty6mHZUM'
-            "#),
+            { "ip'2001:db8::1'" }
         }
 
-        base64url_encoding_nested(hex2value, value2hex) {
+        ipv6_prefix(diag2value) {
             DataItem::Tag {
-                tag: Tag::ENCODED_BASE64URL,
-                bitwidth: IntegerWidth::Zero,
+                tag: Tag::IPV6,
+                bitwidth: IntegerWidth::Unknown,
                 value: Box::new(DataItem::Array {
                     data: vec![
+                        DataItem::Integer {
+                            value: 32,
+                            bitwidth: IntegerWidth::Unknown,
+                        },
                         DataItem::ByteString(ByteString {
-                            data: hex!("123456789abcdeffedcba9876543").into(),
-                            bitwidth: IntegerWidth::Zero,
+                            data: hex!("20010db8").into(),
+                            bitwidth: IntegerWidth::Unknown,
                         }),
                     ],
-                    bitwidth: None,
-                })
+                    bitwidth: Some(IntegerWidth::Unknown),
+                }),
             },
-            indoc!(r#"
-                d5                                    # suggested base64url encoding, tag(21)
-                   9f                                 #   array(*)
-                      4e                              #     bytes(14)
-                         123456789abcdeffedcba9876543 #       b64'EjRWeJq83v_ty6mHZUM'
-                      ff                              #     break
-            "#),
+            { "ip'2001:db8::/32'" }
         }
 
-        base64_encoding(hex2value, value2hex) {
+        uuid(diag2value) {
             DataItem::Tag {
-                tag: Tag::ENCODED_BASE64,
-                bitwidth: IntegerWidth::Zero,
+                tag: Tag::UUID,
+                bitwidth: IntegerWidth::Unknown,
                 value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("123456789abcdeffedcba9876543").into(),
-                    bitwidth: IntegerWidth::Zero,
+                    data: hex!("8c8a8d48d5ac4e038f6754bd67da3a86").into(),
+                    bitwidth: IntegerWidth::Unknown,
                 })),
             },
-            indoc!(r#"
-                d6                                 # suggested base64 encoding, tag(22)
-                   4e                              #   bytes(14)
-                      123456789abcdeffedcba9876543 #     b64'EjRWeJq83v/ty6mHZUM='
-            "#),
+            { "uuid'8c8a8d48-d5ac-4e03-8f67-54bd67da3a86'" }
         }
 
-        base64_encoding_nested(hex2value, value2hex) {
-            DataItem::Tag {
-                tag: Tag::ENCODED_BASE64,
-                bitwidth: IntegerWidth::Zero,
-                value: Box::new(DataItem::Array {
-                    data: vec![
-                        DataItem::ByteString(ByteString {
-                            data: hex!("123456789abcdeffedcba9876543").into(),
-                            bitwidth: IntegerWidth::Zero,
-                        }),
-                    ],
-                    bitwidth: None,
-                })
+        within_items(diag2value) {
+            DataItem::Map {
+                data: vec![(
+                    DataItem::Integer {
+                        value: 1,
+                        bitwidth: IntegerWidth::Zero,
+                    },
+                    DataItem::Array {
+                        data: vec![
+                            DataItem::Tag {
+                                tag: Tag::EPOCH_DATETIME,
+                                bitwidth: IntegerWidth::Zero,
+                                value: Box::new(DataItem::Integer {
+                                    value: 0,
+                                    bitwidth: IntegerWidth::Zero,
+                                }),
+                            },
+                            DataItem::Tag {
+                                tag: Tag::IPV4,
+                                bitwidth: IntegerWidth::Unknown,
+                                value: Box::new(DataItem::ByteString(ByteString {
+                                    data: hex!("c0000201").into(),
+                                    bitwidth: IntegerWidth::Unknown,
+                                })),
+                            },
+                        ],
+                        bitwidth: Some(IntegerWidth::Unknown),
+                    },
+                )],
+                bitwidth: Some(IntegerWidth::Unknown),
             },
-            indoc!(r#"
-                d6                                    # suggested base64 encoding, tag(22)
-                   9f                                 #   array(*)
-                      4e                              #     bytes(14)
-                         123456789abcdeffedcba9876543 #       b64'EjRWeJq83v/ty6mHZUM='
-                      ff                              #     break
-            "#),
+            { "{1: [dt'1970-01-01T00:00:00Z', ip'192.0.2.1']}" }
         }
+    }
 
-        base16_encoding(hex2value, value2hex) {
+    // EDN elisions, tag 888
+    mod elision {
+        item(diag2value, value2diag) {
             DataItem::Tag {
-                tag: Tag::ENCODED_BASE16,
-                bitwidth: IntegerWidth::Zero,
-                value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("123456789abcdeffedcba9876543").into(),
-                    bitwidth: IntegerWidth::Zero,
-                })),
+                tag: Tag(888),
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::Simple(Simple::NULL)),
             },
-            indoc!(r#"
-                d7                                 # suggested base16 encoding, tag(23)
-                   4e                              #   bytes(14)
-                      123456789abcdeffedcba9876543 #     h'123456789abcdeffedcba9876543'
-            "#),
+            { "..." }
         }
 
-        base16_encoding_nested(hex2value, value2hex) {
-            DataItem::Tag {
-                tag: Tag::ENCODED_BASE16,
-                bitwidth: IntegerWidth::Zero,
-                value: Box::new(DataItem::Array {
-                    data: vec![
-                        DataItem::ByteString(ByteString {
-                            data: hex!("123456789abcdeffedcba9876543").into(),
-                            bitwidth: IntegerWidth::Zero,
-                        }),
-                    ],
-                    bitwidth: None,
-                })
+        array_item(diag2value, value2diag) {
+            DataItem::Array {
+                data: vec![
+                    DataItem::Integer {
+                        value: 1,
+                        bitwidth: IntegerWidth::Zero,
+                    },
+                    DataItem::Tag {
+                        tag: Tag(888),
+                        bitwidth: IntegerWidth::Unknown,
+                        value: Box::new(DataItem::Simple(Simple::NULL)),
+                    },
+                ],
+                bitwidth: Some(IntegerWidth::Unknown),
             },
-            indoc!(r#"
-                d7                                    # suggested base16 encoding, tag(23)
-                   9f                                 #   array(*)
-                      4e                              #     bytes(14)
-                         123456789abcdeffedcba9876543 #       h'123456789abcdeffedcba9876543'
-                      ff                              #     break
-            "#),
+            { "[1,...]" }
         }
 
-        multiple_encodings(hex2value, value2hex) {
-            DataItem::Tag {
-                tag: Tag::ENCODED_BASE64URL,
-                bitwidth: IntegerWidth::Zero,
-                value: Box::new(DataItem::Array {
-                    data: vec![
-                        DataItem::ByteString(ByteString {
-                            data: hex!("123456789abcdeffedcba9876543").into(),
+        indefinite_array_item(diag2value, value2diag) {
+            DataItem::Array {
+                data: vec![
+                    DataItem::Tag {
+                        tag: Tag(888),
+                        bitwidth: IntegerWidth::Unknown,
+                        value: Box::new(DataItem::Simple(Simple::NULL)),
+                    },
+                    DataItem::Integer {
+                        value: 2,
+                        bitwidth: IntegerWidth::Zero,
+                    },
+                ],
+                bitwidth: None,
+            },
+            { "[_...,2]" }
+        }
+
+        map_items(diag2value, value2diag) {
+            DataItem::Map {
+                data: vec![
+                    (
+                        DataItem::Integer {
+                            value: 1,
                             bitwidth: IntegerWidth::Zero,
-                        }),
+                        },
                         DataItem::Tag {
-                            tag: Tag::ENCODED_BASE64,
+                            tag: Tag(888),
+                            bitwidth: IntegerWidth::Unknown,
+                            value: Box::new(DataItem::Simple(Simple::NULL)),
+                        },
+                    ),
+                    (
+                        DataItem::Tag {
+                            tag: Tag(888),
+                            bitwidth: IntegerWidth::Unknown,
+                            value: Box::new(DataItem::Simple(Simple::NULL)),
+                        },
+                        DataItem::Integer {
+                            value: 2,
                             bitwidth: IntegerWidth::Zero,
-                            value: Box::new(DataItem::Array {
-                                data: vec![
+                        },
+                    ),
+                ],
+                bitwidth: Some(IntegerWidth::Unknown),
+            },
+            { "{1:...,...:2}" }
+        }
+
+        tagged_item(diag2value, value2diag) {
+            DataItem::Tag {
+                tag: Tag::EPOCH_DATETIME,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::Tag {
+                    tag: Tag(888),
+                    bitwidth: IntegerWidth::Unknown,
+                    value: Box::new(DataItem::Simple(Simple::NULL)),
+                }),
+            },
+            { "1(...)" }
+        }
+
+        map_entries(diag2value, value2diag) {
+            DataItem::Map {
+                data: vec![
+                    (
+                        DataItem::Integer {
+                            value: 1,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::Integer {
+                            value: 2,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                    ),
+                    (
+                        DataItem::Tag {
+                            tag: Tag(888),
+                            bitwidth: IntegerWidth::Unknown,
+                            value: Box::new(DataItem::Simple(Simple::NULL)),
+                        },
+                        DataItem::Tag {
+                            tag: Tag(888),
+                            bitwidth: IntegerWidth::Unknown,
+                            value: Box::new(DataItem::Simple(Simple::NULL)),
+                        },
+                    ),
+                ],
+                bitwidth: Some(IntegerWidth::Unknown),
+            },
+            { "{1:2,...}" }
+        }
+
+        indefinite_map_entries(diag2value, value2diag) {
+            DataItem::Map {
+                data: vec![
+                    (
+                        DataItem::Tag {
+                            tag: Tag(888),
+                            bitwidth: IntegerWidth::Unknown,
+                            value: Box::new(DataItem::Simple(Simple::NULL)),
+                        },
+                        DataItem::Tag {
+                            tag: Tag(888),
+                            bitwidth: IntegerWidth::Unknown,
+                            value: Box::new(DataItem::Simple(Simple::NULL)),
+                        },
+                    ),
+                    (
+                        DataItem::Integer {
+                            value: 1,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::Integer {
+                            value: 2,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                    ),
+                ],
+                bitwidth: None,
+            },
+            { "{_...,1:2}" }
+        }
+
+        text_after(diag2value, value2diag) {
+            DataItem::Tag {
+                tag: Tag(888),
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::TextString(TextString {
+                            data: "abc".into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                        DataItem::Simple(Simple::NULL),
+                    ],
+                    bitwidth: Some(IntegerWidth::Unknown),
+                }),
+            },
+            { r#""abc" ..."# }
+        }
+
+        text_between(diag2value, value2diag) {
+            DataItem::Tag {
+                tag: Tag(888),
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Simple(Simple::NULL),
+                        DataItem::TextString(TextString {
+                            data: "abc".into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                        DataItem::Simple(Simple::NULL),
+                        DataItem::TextString(TextString {
+                            data: "xyz".into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                    ],
+                    bitwidth: Some(IntegerWidth::Unknown),
+                }),
+            },
+            { r#"... "abc" ... "xyz""# }
+        }
+
+        map_value_and_entry(diag2value, value2diag) {
+            DataItem::Map {
+                data: vec![
+                    (
+                        DataItem::Integer {
+                            value: 1,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::Tag {
+                            tag: Tag(888),
+                            bitwidth: IntegerWidth::Unknown,
+                            value: Box::new(DataItem::Simple(Simple::NULL)),
+                        },
+                    ),
+                    (
+                        DataItem::Tag {
+                            tag: Tag(888),
+                            bitwidth: IntegerWidth::Unknown,
+                            value: Box::new(DataItem::Simple(Simple::NULL)),
+                        },
+                        DataItem::Tag {
+                            tag: Tag(888),
+                            bitwidth: IntegerWidth::Unknown,
+                            value: Box::new(DataItem::Simple(Simple::NULL)),
+                        },
+                    ),
+                ],
+                bitwidth: Some(IntegerWidth::Unknown),
+            },
+            { "{1:...,...}" }
+        }
+
+        text_around(diag2value, value2diag) {
+            DataItem::Tag {
+                tag: Tag(888),
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Simple(Simple::NULL),
+                        DataItem::TextString(TextString {
+                            data: "abc".into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                        DataItem::Simple(Simple::NULL),
+                    ],
+                    bitwidth: Some(IntegerWidth::Unknown),
+                }),
+            },
+            { r#"... "abc" ..."# }
+        }
+
+        concatenated(diag2value) {
+            DataItem::Tag {
+                tag: Tag(888),
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::TextString(TextString {
+                            data: "ab".into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                        DataItem::Simple(Simple::NULL),
+                        DataItem::TextString(TextString {
+                            data: "c".into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                    ],
+                    bitwidth: Some(IntegerWidth::Unknown),
+                }),
+            },
+            { r#""a" h'62' ... "c""# }
+        }
+
+        bytes_between(diag2value, value2diag) {
+            DataItem::Tag {
+                tag: Tag(888),
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::ByteString(ByteString {
+                            data: hex!("0102").into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                        DataItem::Simple(Simple::NULL),
+                        DataItem::ByteString(ByteString {
+                            data: hex!("0304").into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                    ],
+                    bitwidth: Some(IntegerWidth::Unknown),
+                }),
+            },
+            { "h'0102' ... h'0304'" }
+        }
+
+        quoted_bytes_before(diag2value) {
+            DataItem::Tag {
+                tag: Tag(888),
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::ByteString(ByteString {
+                            data: hex!("616263").into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                        DataItem::Simple(Simple::NULL),
+                    ],
+                    bitwidth: Some(IntegerWidth::Unknown),
+                }),
+            },
+            { "'abc' ..." }
+        }
+
+        quoted_bytes_after(diag2value) {
+            DataItem::Tag {
+                tag: Tag(888),
+                bitwidth: IntegerWidth::Unknown,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Simple(Simple::NULL),
+                        DataItem::ByteString(ByteString {
+                            data: hex!("616263").into(),
+                            bitwidth: IntegerWidth::Unknown,
+                        }),
+                    ],
+                    bitwidth: Some(IntegerWidth::Unknown),
+                }),
+            },
+            { "... 'abc'" }
+        }
+    }
+
+    mod hex_tests {
+        elision(hex2value, value2hex) {
+            DataItem::Array {
+                data: vec![
+                    DataItem::Integer {
+                        value: 1,
+                        bitwidth: IntegerWidth::Zero,
+                    },
+                    DataItem::Tag {
+                        tag: Tag(888),
+                        bitwidth: IntegerWidth::Sixteen,
+                        value: Box::new(DataItem::Simple(Simple::NULL)),
+                    },
+                ],
+                bitwidth: Some(IntegerWidth::Zero),
+            },
+            indoc!(r#"
+                82         # array(2)
+                   01      #   unsigned(1)
+                   d9 0378 #   elided data, tag(888)
+                      f6   #     null, simple(22)
+            "#),
+        }
+
+        date_time(hex2value, value2hex) {
+            DataItem::Array {
+                data: vec![
+                    DataItem::Tag {
+                        tag: Tag::DATETIME,
+                        bitwidth: IntegerWidth::Zero,
+                        value: Box::new(DataItem::TextString(TextString {
+                            data: "2018-08-02T18:19:38Z".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }))
+                    },
+                    DataItem::Tag {
+                        tag: Tag::DATETIME,
+                        bitwidth: IntegerWidth::Zero,
+                        value: Box::new(DataItem::TextString(TextString {
+                            data: "1921-06-01T05:40:21Z".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }))
+                    },
+                    DataItem::Tag {
+                        tag: Tag::DATETIME,
+                        bitwidth: IntegerWidth::Zero,
+                        value: Box::new(DataItem::TextString(TextString {
+                            data: "2018-08-02T18:19:38.125Z".into(),
+                            bitwidth: IntegerWidth::Eight,
+                        }))
+                    },
+                ],
+                bitwidth: Some(IntegerWidth::Zero),
+            },
+            indoc!(r#"
+               83                                                        # array(3)
+                  c0                                                     #   standard datetime string, tag(0)
+                     74                                                  #     text(20)
+                        323031382d30382d30325431383a31393a33385a         #       "2018-08-02T18:19:38Z"
+                                                                         #     epoch(1533233978)
+                  c0                                                     #   standard datetime string, tag(0)
+                     74                                                  #     text(20)
+                        313932312d30362d30315430353a34303a32315a         #       "1921-06-01T05:40:21Z"
+                                                                         #     epoch(-1533233979)
+                  c0                                                     #   standard datetime string, tag(0)
+                     78 18                                               #     text(24)
+                        323031382d30382d30325431383a31393a33382e3132355a #       "2018-08-02T18:19:38.125Z"
+                                                                         #     epoch(1533233978.125)
+            "#),
+        }
+
+        epoch_date_time(hex2value, value2hex) {
+            DataItem::Array {
+                data: vec![
+                    DataItem::Tag {
+                        tag: Tag::EPOCH_DATETIME,
+                        bitwidth: IntegerWidth::Zero,
+                        value: Box::new(DataItem::Integer {
+                            value: 1_533_233_978,
+                            bitwidth: IntegerWidth::ThirtyTwo,
+                        })
+                    },
+                    DataItem::Tag {
+                        tag: Tag::EPOCH_DATETIME,
+                        bitwidth: IntegerWidth::Zero,
+                        value: Box::new(DataItem::Negative {
+                            value: 1_533_233_978,
+                            bitwidth: IntegerWidth::ThirtyTwo,
+                        })
+                    },
+                    DataItem::Tag {
+                        tag: Tag::EPOCH_DATETIME,
+                        bitwidth: IntegerWidth::Zero,
+                        value: Box::new(DataItem::Float(Float::new(1_533_233_978.125, FloatWidth::SixtyFour)))
+                    },
+                ],
+                bitwidth: Some(IntegerWidth::Zero),
+            },
+            indoc!(r#"
+                83                        # array(3)
+                   c1                     #   epoch datetime value, tag(1)
+                      1a 5b634b3a         #     unsigned(1,533,233,978)
+                                          #     datetime(2018-08-02T18:19:38Z)
+                   c1                     #   epoch datetime value, tag(1)
+                      3a 5b634b3a         #     negative(-1,533,233,979)
+                                          #     datetime(1921-06-01T05:40:21Z)
+                   c1                     #   epoch datetime value, tag(1)
+                      fb 41d6d8d2ce880000 #     float(1,533,233,978.125)
+                                          #     datetime(2018-08-02T18:19:38.125Z)
+            "#),
+        }
+
+        positive_bignum(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::POSITIVE_BIGNUM,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("000001ffffffffffffffffffffff0000000000000000000000").into(),
+                    bitwidth: IntegerWidth::Eight,
+                }))
+            },
+            indoc!(r#"
+                c2                                     # positive bignum, tag(2)
+                   58 19                               #   bytes(25)
+                      000001ffffffffffffffffffffff0000 #     "\x00\x00\x01\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00\x00"
+                      000000000000000000               #     "\x00\x00\x00\x00\x00\x00\x00\x00\x00"
+                                                       #   bignum(191561942608236107294793378084303638130997321548169216)
+            "#),
+        }
+
+        negative_bignum(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::NEGATIVE_BIGNUM,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("123456789abcdeffedcba987654321").into(),
+                    bitwidth: IntegerWidth::Eight,
+                }))
+            },
+            indoc!(r#"
+                c3                                   # negative bignum, tag(3)
+                   58 0f                             #   bytes(15)
+                      123456789abcdeffedcba987654321 #     "\x124Vx\x9a\xbc\xde\xff\xed\xcb\xa9\x87eC!"
+                                                     #   bignum(-94522879700260684208272210605196066)
+            "#),
+        }
+
+        decimal_fraction(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::DECIMAL_FRACTION,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Negative {
+                            value: 1,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::Integer {
+                            value: 27315,
+                            bitwidth: IntegerWidth::Sixteen,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                })
+            },
+            indoc!(r#"
+                c4            # decimal fraction, tag(4)
+                   82         #   array(2)
+                      21      #     negative(-2)
+                      19 6ab3 #     unsigned(27,315)
+                              #   decimal fraction(5463/20)
+            "#),
+        }
+
+        bigfloat(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::BIGFLOAT,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Negative {
+                            value: 0,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::Integer {
+                            value: 3,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                })
+            },
+            indoc!(r#"
+                c5       # bigfloat, tag(5)
+                   82    #   array(2)
+                      20 #     negative(-1)
+                      03 #     unsigned(3)
+                         #   bigfloat(3/2)
+            "#),
+        }
+
+        decimal_fraction_bignum(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::DECIMAL_FRACTION,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Negative {
+                            value: 52,
+                            bitwidth: IntegerWidth::Eight,
+                        },
+                        DataItem::Tag {
+                            tag: Tag::POSITIVE_BIGNUM,
+                            bitwidth: IntegerWidth::Zero,
+                            value: Box::new(DataItem::ByteString(ByteString {
+                                data: hex!("000001ffffffffffffffffffffff0000000000000000000000").into(),
+                                bitwidth: IntegerWidth::Eight,
+                            }))
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                })
+            },
+            indoc!(r#"
+                c4                                           # decimal fraction, tag(4)
+                   82                                        #   array(2)
+                      38 34                                  #     negative(-53)
+                      c2                                     #     positive bignum, tag(2)
+                         58 19                               #       bytes(25)
+                            000001ffffffffffffffffffffff0000 #         "\x00\x00\x01\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00\x00"
+                            000000000000000000               #         "\x00\x00\x00\x00\x00\x00\x00\x00\x00"
+                                                             #       bignum(191561942608236107294793378084303638130997321548169216)
+                                                             #   decimal fraction(21267647932558653966460912930125774848/11102230246251565404236316680908203125)
+            "#),
+        }
+
+        bigfloat_bignum(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::BIGFLOAT,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Negative {
+                            value: 175,
+                            bitwidth: IntegerWidth::Eight,
+                        },
+                        DataItem::Tag {
+                            tag: Tag::POSITIVE_BIGNUM,
+                            bitwidth: IntegerWidth::Zero,
+                            value: Box::new(DataItem::ByteString(ByteString {
+                                data: hex!("000001ffffffffffffffffffffff0000000000000000000000").into(),
+                                bitwidth: IntegerWidth::Eight,
+                            }))
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                })
+            },
+            indoc!(r#"
+                c5                                           # bigfloat, tag(5)
+                   82                                        #   array(2)
+                      38 af                                  #     negative(-176)
+                      c2                                     #     positive bignum, tag(2)
+                         58 19                               #       bytes(25)
+                            000001ffffffffffffffffffffff0000 #         "\x00\x00\x01\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\xff\x00\x00"
+                            000000000000000000               #         "\x00\x00\x00\x00\x00\x00\x00\x00\x00"
+                                                             #       bignum(191561942608236107294793378084303638130997321548169216)
+                                                             #   bigfloat(618970019642690137449562111/309485009821345068724781056)
+            "#),
+        }
+
+        rational(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::RATIONAL,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Negative {
+                            value: 5,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::Integer {
+                            value: 4,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                })
+            },
+            indoc!(r#"
+                d8 1e    # rational number, tag(30)
+                   82    #   array(2)
+                      25 #     negative(-6)
+                      04 #     unsigned(4)
+                         #   rational(-3/2)
+                         #   decimal(-1.5)
+            "#),
+        }
+
+        rational_recurring(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::RATIONAL,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Integer {
+                            value: 1,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::Integer {
+                            value: 3,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                })
+            },
+            indoc!(r#"
+                d8 1e    # rational number, tag(30)
+                   82    #   array(2)
+                      01 #     unsigned(1)
+                      03 #     unsigned(3)
+                         #   rational(1/3)
+                         #   decimal(≈0.33333333333333333333…)
+            "#),
+        }
+
+        rational_bignum(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::RATIONAL,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Integer {
+                            value: 3,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::Tag {
+                            tag: Tag::POSITIVE_BIGNUM,
+                            bitwidth: IntegerWidth::Zero,
+                            value: Box::new(DataItem::ByteString(ByteString {
+                                data: hex!("0100000000000000000000").into(),
+                                bitwidth: IntegerWidth::Zero,
+                            }))
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                })
+            },
+            indoc!(r#"
+                d8 1e                              # rational number, tag(30)
+                   82                              #   array(2)
+                      03                           #     unsigned(3)
+                      c2                           #     positive bignum, tag(2)
+                         4b                        #       bytes(11)
+                            0100000000000000000000 #         "\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00\x00"
+                                                   #       bignum(1208925819614629174706176)
+                                                   #   rational(3/1208925819614629174706176)
+                                                   #   decimal(≈0.0000000000000000000000024815418376590830246…)
+            "#),
+        }
+
+        rational_zero_denominator(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::RATIONAL,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Integer {
+                            value: 1,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::Integer {
+                            value: 0,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                })
+            },
+            indoc!(r#"
+                d8 1e    # rational number, tag(30)
+                   82    #   array(2)
+                      01 #     unsigned(1)
+                      00 #     unsigned(0)
+                         #   invalid rational number, zero denominator
+            "#),
+        }
+
+        rational_negative_denominator(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::RATIONAL,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Integer {
+                            value: 1,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::Negative {
+                            value: 2,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                })
+            },
+            indoc!(r#"
+                d8 1e    # rational number, tag(30)
+                   82    #   array(2)
+                      01 #     unsigned(1)
+                      22 #     negative(-3)
+                         #   invalid rational number, negative denominator
+            "#),
+        }
+
+        base64url_encoding(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_BASE64URL,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("123456789abcdeffedcba9876543").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d5                                 # suggested base64url encoding, tag(21)
+                   4e                              #   bytes(14)
+                      123456789abcdeffedcba9876543 #     b64'EjRWeJq83v_ty6mHZUM'
+            "#),
+        }
+
+        base64url_encoding_nested(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_BASE64URL,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::ByteString(ByteString {
+                            data: hex!("123456789abcdeffedcba9876543").into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                    ],
+                    bitwidth: None,
+                })
+            },
+            indoc!(r#"
+                d5                                    # suggested base64url encoding, tag(21)
+                   9f                                 #   array(*)
+                      4e                              #     bytes(14)
+                         123456789abcdeffedcba9876543 #       b64'EjRWeJq83v_ty6mHZUM'
+                      ff                              #     break
+            "#),
+        }
+
+        base64_encoding(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_BASE64,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("123456789abcdeffedcba9876543").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d6                                 # suggested base64 encoding, tag(22)
+                   4e                              #   bytes(14)
+                      123456789abcdeffedcba9876543 #     b64'EjRWeJq83v/ty6mHZUM='
+            "#),
+        }
+
+        base64_encoding_nested(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_BASE64,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::ByteString(ByteString {
+                            data: hex!("123456789abcdeffedcba9876543").into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                    ],
+                    bitwidth: None,
+                })
+            },
+            indoc!(r#"
+                d6                                    # suggested base64 encoding, tag(22)
+                   9f                                 #   array(*)
+                      4e                              #     bytes(14)
+                         123456789abcdeffedcba9876543 #       b64'EjRWeJq83v/ty6mHZUM='
+                      ff                              #     break
+            "#),
+        }
+
+        base16_encoding(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_BASE16,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("123456789abcdeffedcba9876543").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d7                                 # suggested base16 encoding, tag(23)
+                   4e                              #   bytes(14)
+                      123456789abcdeffedcba9876543 #     h'123456789abcdeffedcba9876543'
+            "#),
+        }
+
+        base16_encoding_nested(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_BASE16,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::ByteString(ByteString {
+                            data: hex!("123456789abcdeffedcba9876543").into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                    ],
+                    bitwidth: None,
+                })
+            },
+            indoc!(r#"
+                d7                                    # suggested base16 encoding, tag(23)
+                   9f                                 #   array(*)
+                      4e                              #     bytes(14)
+                         123456789abcdeffedcba9876543 #       h'123456789abcdeffedcba9876543'
+                      ff                              #     break
+            "#),
+        }
+
+        multiple_encodings(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_BASE64URL,
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::ByteString(ByteString {
+                            data: hex!("123456789abcdeffedcba9876543").into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::Tag {
+                            tag: Tag::ENCODED_BASE64,
+                            bitwidth: IntegerWidth::Zero,
+                            value: Box::new(DataItem::Array {
+                                data: vec![
                                     DataItem::ByteString(ByteString {
                                         data: hex!("123456789abcdeffedcba9876543").into(),
                                         bitwidth: IntegerWidth::Zero,
@@ -1176,606 +2037,2303 @@ testcases! {
                             })
                         },
                         DataItem::Tag {
-                            tag: Tag::ENCODED_BASE16,
+                            tag: Tag::ENCODED_BASE16,
+                            bitwidth: IntegerWidth::Zero,
+                            value: Box::new(DataItem::ByteString(ByteString {
+                                data: hex!("123456789abcdeffedcba9876543").into(),
+                                bitwidth: IntegerWidth::Zero,
+                            })),
+                        },
+                    ],
+                    bitwidth: None,
+                })
+            },
+            indoc!(r#"
+                d5                                          # suggested base64url encoding, tag(21)
+                   9f                                       #   array(*)
+                      4e                                    #     bytes(14)
+                         123456789abcdeffedcba9876543       #       b64'EjRWeJq83v_ty6mHZUM'
+                      d6                                    #     suggested base64 encoding, tag(22)
+                         9f                                 #       array(*)
+                            4e                              #         bytes(14)
+                               123456789abcdeffedcba9876543 #           b64'EjRWeJq83v/ty6mHZUM='
+                            ff                              #         break
+                      d7                                    #     suggested base16 encoding, tag(23)
+                         4e                                 #       bytes(14)
+                            123456789abcdeffedcba9876543    #         h'123456789abcdeffedcba9876543'
+                      ff                                    #     break
+            "#),
+        }
+
+        encoded_cbor(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("9f64f09f87b317ff").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!("
+                d8 18                  # encoded cbor data item, tag(24)
+                   48                  #   bytes(8)
+                      9f64f09f87b317ff #     \"\\x9fd\\xf0\\x9f\\x87\\xb3\\x17\\xff\"
+                                       #   encoded cbor data item
+                                       #     9f             # array(*)
+                                       #        64          #   text(4)
+                                       #           f09f87b3 #     \"\u{1f1f3}\"
+                                       #        17          #   unsigned(23)
+                                       #        ff          #   break
+            "),
+        }
+
+        encoded_cbor_invalid(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("ff").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 18    # encoded cbor data item, tag(24)
+                   41    #   bytes(1)
+                      ff #     "\xff"
+                         #   failed to parse encoded cbor data item, unexpected break at byte 0
+                         #     ff # unparsed "\xff"
+            "#),
+        }
+
+        encoded_cbor_empty(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: vec![],
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 18 # encoded cbor data item, tag(24)
+                   40 #   bytes(0)
+                      #     ""
+                      #   failed to parse encoded cbor data item, unexpected end of data at byte 0
+            "#),
+        }
+
+        encoded_cbor_seq(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR_SEQ,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("9f64f09f87b317ff1615").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 3f                      # encoded cbor sequence, tag(63)
+                   4a                      #   bytes(10)
+                      9f64f09f87b317ff1615 #     "\x9fd\xf0\x9f\x87\xb3\x17\xff\x16\x15"
+                                           #   encoded cbor data item
+                                           #     9f             # array(*)
+                                           #        64          #   text(4)
+                                           #           f09f87b3 #     "🇳"
+                                           #        17          #   unsigned(23)
+                                           #        ff          #   break
+                                           #   encoded cbor data item
+                                           #     16 # unsigned(22)
+                                           #   encoded cbor data item
+                                           #     15 # unsigned(21)
+            "#),
+        }
+
+        encoded_cbor_seq_invalid(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR_SEQ,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("1617ff").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 3f        # encoded cbor sequence, tag(63)
+                   43        #   bytes(3)
+                      1617ff #     "\x16\x17\xff"
+                             #   encoded cbor data item
+                             #     16 # unsigned(22)
+                             #   encoded cbor data item
+                             #     17 # unsigned(23)
+                             #   failed to parse remaining encoded cbor sequence, unexpected break at byte 2
+                             #     ff # unparsed "\xff"
+            "#),
+        }
+
+        encoded_cbor_truncated(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("830102").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 18        # encoded cbor data item, tag(24)
+                   43        #   bytes(3)
+                      830102 #     "\x83\x01\x02"
+                             #   failed to parse encoded cbor data item, unexpected end of data at byte 3
+                             #     83    # array(3)
+                             #        01 #   unsigned(1)
+                             #        02 #   unsigned(2)
+            "#),
+        }
+
+        encoded_cbor_nested_failure(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("a1616181ff").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 18            # encoded cbor data item, tag(24)
+                   45            #   bytes(5)
+                      a1616181ff #     "\xa1aa\x81\xff"
+                                 #   failed to parse encoded cbor data item, unexpected break at byte 4
+                                 #     a1       # map(1)
+                                 #        61    #   text(1)
+                                 #           61 #     "a"
+                                 #        81    #   array(1)
+                                 #     ff       # unparsed "\xff"
+            "#),
+        }
+
+        encoded_cbor_invalid_utf8(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("62c328").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 18        # encoded cbor data item, tag(24)
+                   43        #   bytes(3)
+                      62c328 #     "b\xc3("
+                             #   failed to parse encoded cbor data item, invalid utf-8 at byte 1
+                             #     62      # text(2)
+                             #        c328 #   "\xc3("
+            "#),
+        }
+
+        encoded_cbor_trailing_bytes(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("0102").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 18      # encoded cbor data item, tag(24)
+                   42      #   bytes(2)
+                      0102 #     "\x01\x02"
+                           #   failed to parse encoded cbor data item, unexpected trailing bytes at byte 1
+                           #     01 # unsigned(1)
+                           #     02 # unparsed "\x02"
+            "#),
+        }
+
+        encoded_cbor_reserved(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("9f1c").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 18      # encoded cbor data item, tag(24)
+                   42      #   bytes(2)
+                      9f1c #     "\x9f\x1c"
+                           #   failed to parse encoded cbor data item, reserved additional information 28 at byte 1
+                           #     9f # array(*)
+                           #     1c # unparsed "\x1c"
+            "#),
+        }
+
+        encoded_cbor_with_encoding(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag(22),
+                bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::Tag {
+                    tag: Tag::ENCODED_CBOR,
+                    bitwidth: IntegerWidth::Eight,
+                    value: Box::new(DataItem::ByteString(ByteString {
+                        data: hex!("4101").into(),
+                        bitwidth: IntegerWidth::Zero,
+                    })),
+                }),
+            },
+            indoc!(r#"
+                d6            # suggested base64 encoding, tag(22)
+                   d8 18      #   encoded cbor data item, tag(24)
+                      42      #     bytes(2)
+                         4101 #       b64'QQE='
+                              #     encoded cbor data item
+                              #       41    # bytes(1)
+                              #          01 #   b64'AQ=='
+            "#),
+        }
+
+        encoded_cbor_seq_truncated(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR_SEQ,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("01830203").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 3f          # encoded cbor sequence, tag(63)
+                   44          #   bytes(4)
+                      01830203 #     "\x01\x83\x02\x03"
+                               #   encoded cbor data item
+                               #     01 # unsigned(1)
+                               #   failed to parse remaining encoded cbor sequence, unexpected end of data at byte 4
+                               #     83    # array(3)
+                               #        02 #   unsigned(2)
+                               #        03 #   unsigned(3)
+            "#),
+        }
+
+        encoded_cbor_seq_empty(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR_SEQ,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: vec![],
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 3f # encoded cbor sequence, tag(63)
+                   40 #   bytes(0)
+                      #     ""
+            "#),
+        }
+
+        uri(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::URI,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::TextString(TextString {
+                    data: "https://example.com".into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 20                                        # uri, tag(32)
+                   73                                        #   text(19)
+                      68747470733a2f2f6578616d706c652e636f6d #     "https://example.com"
+                                                             #   valid URL (checked against URL Standard, not RFC 3986)
+            "#),
+        }
+
+        uri_non_http(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::URI,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::TextString(TextString {
+                    data: "urn:oasis:names:specification:docbook:dtd:xml:4.1.2".into(),
+                    bitwidth: IntegerWidth::Eight,
+                })),
+            },
+            indoc!(r#"
+                d8 20                                  # uri, tag(32)
+                   78 33                               #   text(51)
+                      75726e3a6f617369733a6e616d65733a #     "urn:oasis:names:"
+                      73706563696669636174696f6e3a646f #     "specification:do"
+                      63626f6f6b3a6474643a786d6c3a342e #     "cbook:dtd:xml:4."
+                      312e32                           #     "1.2"
+                                                       #   valid URL (checked against URL Standard, not RFC 3986)
+            "#),
+        }
+
+        uri_invalid(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::URI,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::TextString(TextString {
+                    data: "foo".into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 20        # uri, tag(32)
+                   63        #   text(3)
+                      666f6f #     "foo"
+                             #   invalid URL (checked against URL Standard, not RFC 3986)
+            "#),
+        }
+
+        base64url(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::BASE64URL,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::TextString(TextString {
+                    data: "aHR0cHM6Ly9leGFtcGxlLmNvbS_wn5C2".into(),
+                    bitwidth: IntegerWidth::Eight,
+                })),
+            },
+            indoc!(r#"
+                d8 21                                  # base64url encoded text, tag(33)
+                   78 20                               #   text(32)
+                      6148523063484d364c79396c65474674 #     "aHR0cHM6Ly9leGFt"
+                      6347786c4c6d4e7662535f776e354332 #     "cGxlLmNvbS_wn5C2"
+                                                       #   base64url decoded
+                                                       #     68747470733a2f2f6578616d706c652e # "https://example."
+                                                       #     636f6d2ff09f90b6                 # "com/\xf0\x9f\x90\xb6"
+            "#),
+        }
+
+        base64(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::BASE64,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::TextString(TextString {
+                    data: "aHR0cHM6Ly9leGFtcGxlLmNvbS/wn5C2".into(),
+                    bitwidth: IntegerWidth::Eight,
+                })),
+            },
+            indoc!(r#"
+                d8 22                                  # base64 encoded text, tag(34)
+                   78 20                               #   text(32)
+                      6148523063484d364c79396c65474674 #     "aHR0cHM6Ly9leGFt"
+                      6347786c4c6d4e7662532f776e354332 #     "cGxlLmNvbS/wn5C2"
+                                                       #   base64 decoded
+                                                       #     68747470733a2f2f6578616d706c652e # "https://example."
+                                                       #     636f6d2ff09f90b6                 # "com/\xf0\x9f\x90\xb6"
+            "#),
+        }
+
+        encoded_cbor_nested(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("d818489f64f09f87b317ff").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!("
+                d8 18                        # encoded cbor data item, tag(24)
+                   4b                        #   bytes(11)
+                      d818489f64f09f87b317ff #     \"\\xd8\\x18H\\x9fd\\xf0\\x9f\\x87\\xb3\\x17\\xff\"
+                                             #   encoded cbor data item
+                                             #     d8 18                  # encoded cbor data item, tag(24)
+                                             #        48                  #   bytes(8)
+                                             #           9f64f09f87b317ff #     \"\\x9fd\\xf0\\x9f\\x87\\xb3\\x17\\xff\"
+                                             #                            #   encoded cbor data item
+                                             #                            #     9f             # array(*)
+                                             #                            #        64          #   text(4)
+                                             #                            #           f09f87b3 #     \"\u{1f1f3}\"
+                                             #                            #        17          #   unsigned(23)
+                                             #                            #        ff          #   break
+            "),
+        }
+
+        encoded_cbor_json_hint(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::ENCODED_CBOR,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("7b2261223a20317d").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 18                  # encoded cbor data item, tag(24)
+                   48                  #   bytes(8)
+                      7b2261223a20317d #     "{\"a\": 1}"
+                                       #   failed to parse encoded cbor data item, unexpected end of data at byte 8
+                                       #     7b2261223a20317d # unparsed "{\"a\": 1}"
+                                       #     hint: content is json
+                                       #       {
+                                       #           "a": 1
+                                       #       }
+            "#),
+        }
+
+        uuid(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::UUID,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("8c8a8d48c00f42209cf8b75a882bf586").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 25                                  # uuid, tag(37)
+                   50                                  #   bytes(16)
+                      8c8a8d48c00f42209cf8b75a882bf586 #     h'8c8a8d48c00f42209cf8b75a882bf586'
+                                                       #   uuid(variant(RFC4122), version(4, Random))
+                                                       #     base16(8c8a8d48-c00f-4220-9cf8-b75a882bf586)
+                                                       #     base58(JMZyLNqHizfirvWvE2EXBK)
+                                                       #     base64(jIqNSMAPQiCc+LdaiCv1hg)
+            "#),
+        }
+
+        uuid_invalid_length(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::UUID,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("0123456789").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 25            # uuid, tag(37)
+                   45            #   bytes(5)
+                      0123456789 #     h'0123456789'
+                                 #   invalid data length for uuid
+            "#),
+        }
+
+        uuid_invalid_type(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::UUID,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::TextString(TextString {
+                    data: "0123456789ab".into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 25                          # uuid, tag(37)
+                   6c                          #   text(12)
+                      303132333435363738396162 #     "0123456789ab"
+                                               #   invalid type for uuid
+            "#),
+        }
+
+        language_tagged_string(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::LANGUAGE_TAGGED_STRING,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::TextString(TextString {
+                            data: "en-US".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::TextString(TextString {
+                            data: "Hello".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d8 26               # language-tagged string, tag(38)
+                   82               #   array(2)
+                      65            #     text(5)
+                         656e2d5553 #       "en-US"
+                      65            #     text(5)
+                         48656c6c6f #       "Hello"
+                                    #   language(en-US)
+            "#),
+        }
+
+        language_tagged_string_direction(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::LANGUAGE_TAGGED_STRING,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::TextString(TextString {
+                            data: "ar".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::TextString(TextString {
+                            data: "مرحبا".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::Simple(Simple::TRUE),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d8 26                         # language-tagged string, tag(38)
+                   83                         #   array(3)
+                      62                      #     text(2)
+                         6172                 #       "ar"
+                      6a                      #     text(10)
+                         d985d8b1d8add8a8d8a7 #       "مرحبا"
+                      f5                      #     true, simple(21)
+                                              #   language(ar), right-to-left
+            "#),
+        }
+
+        language_tagged_string_no_language(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::LANGUAGE_TAGGED_STRING,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::TextString(TextString {
+                            data: "".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::TextString(TextString {
+                            data: "abc".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::Simple(Simple::FALSE),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d8 26           # language-tagged string, tag(38)
+                   83           #   array(3)
+                      60        #     text(0)
+                                #       ""
+                      63        #     text(3)
+                         616263 #       "abc"
+                      f4        #     false, simple(20)
+                                #   no language, left-to-right
+            "#),
+        }
+
+        language_tagged_string_invalid_language(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::LANGUAGE_TAGGED_STRING,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::TextString(TextString {
+                            data: "en-US-a".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::TextString(TextString {
+                            data: "Hello".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d8 26                   # language-tagged string, tag(38)
+                   82                   #   array(2)
+                      67                #     text(7)
+                         656e2d55532d61 #       "en-US-a"
+                      65                #     text(5)
+                         48656c6c6f     #       "Hello"
+                                        #   invalid language tag, extension "a" has no subtags
+            "#),
+        }
+
+        language_tagged_string_invalid_direction(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::LANGUAGE_TAGGED_STRING,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::TextString(TextString {
+                            data: "en".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::TextString(TextString {
+                            data: "Hello".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::Integer {
+                            value: 1,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d8 26               # language-tagged string, tag(38)
+                   83               #   array(3)
+                      62            #     text(2)
+                         656e       #       "en"
+                      65            #     text(5)
+                         48656c6c6f #       "Hello"
+                      01            #     unsigned(1)
+                                    #   invalid type for language-tagged string direction
+            "#),
+        }
+
+        language_tagged_string_invalid_type(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::LANGUAGE_TAGGED_STRING,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::TextString(TextString {
+                    data: "en".into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 26      # language-tagged string, tag(38)
+                   62      #   text(2)
+                      656e #     "en"
+                           #   invalid type for language-tagged string
+            "#),
+        }
+
+        cid_v1(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::CID,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("00017112202cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824").into(),
+                    bitwidth: IntegerWidth::Eight,
+                })),
+            },
+            indoc!(r#"
+                d8 2a                                  # ipld content identifier, tag(42)
+                   58 25                               #   bytes(37)
+                      00017112202cf24dba5fb0a30e26e83b #     h'00017112202cf24dba5fb0a30e26e83b'
+                      2ac5b9e29e1b161e5c1fa7425e730433 #     h'2ac5b9e29e1b161e5c1fa7425e730433'
+                      62938b9824                       #     h'62938b9824'
+                                                       #   cid(version(1), codec(dag-cbor), hash(sha2-256))
+                                                       #     base32(bafyreibm6jg3ux5qumhcn2b3flc3tyu6dmlb4xa7u5bf44yegnrjhc4yeq)
+            "#),
+        }
+
+        cid_v0(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::CID,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("0012202cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824").into(),
+                    bitwidth: IntegerWidth::Eight,
+                })),
+            },
+            indoc!(r#"
+                d8 2a                                  # ipld content identifier, tag(42)
+                   58 23                               #   bytes(35)
+                      0012202cf24dba5fb0a30e26e83b2ac5 #     h'0012202cf24dba5fb0a30e26e83b2ac5'
+                      b9e29e1b161e5c1fa7425e7304336293 #     h'b9e29e1b161e5c1fa7425e7304336293'
+                      8b9824                           #     h'8b9824'
+                                                       #   cid(version(0), codec(dag-pb), hash(sha2-256))
+                                                       #     base58(QmRN6wdp1S2A5EtjW9A3M1vKSBuQQGcgvuhoMUoEz4iiT5)
+            "#),
+        }
+
+        cid_unknown_codec(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::CID,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("0001808001b2200400000000").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 2a                          # ipld content identifier, tag(42)
+                   4c                          #   bytes(12)
+                      0001808001b2200400000000 #     h'0001808001b2200400000000'
+                                               #   cid(version(1), codec(0x4000), hash(0x1032))
+                                               #     base32(bagaiaanseacaaaaaaa)
+            "#),
+        }
+
+        cid_missing_prefix(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::CID,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("01711220").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 2a          # ipld content identifier, tag(42)
+                   44          #   bytes(4)
+                      01711220 #     h'01711220'
+                               #   invalid cid, missing multibase identity prefix
+            "#),
+        }
+
+        cid_digest_length(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::CID,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("0001711220").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 2a            # ipld content identifier, tag(42)
+                   45            #   bytes(5)
+                      0001711220 #     h'0001711220'
+                                 #   invalid cid, digest length 32 does not match remaining 0 bytes
+            "#),
+        }
+
+        cid_invalid_type(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::CID,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::TextString(TextString {
+                    data: "bafy".into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d8 2a          # ipld content identifier, tag(42)
+                   64          #   text(4)
+                      62616679 #     "bafy"
+                               #   invalid type for cid
+            "#),
+        }
+
+        geographic_coordinates(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::GEOGRAPHIC_COORDINATES,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Float(Float::new(51.5034, FloatWidth::SixtyFour)),
+                        DataItem::Float(Float::new(-0.1276, FloatWidth::SixtyFour)),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d8 67                     # geographic coordinates, tag(103)
+                   82                     #   array(2)
+                      fb 4049c06f69446738 #     float(51.5034)
+                      fb bfc05532617c1bda #     float(-0.1276)
+                                          #   latitude(51.5034), longitude(-0.1276)
+            "#),
+        }
+
+        geographic_coordinates_altitude(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::GEOGRAPHIC_COORDINATES,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Float(Float::new(51.5, FloatWidth::SixtyFour)),
+                        DataItem::Float(Float::new(-0.12, FloatWidth::SixtyFour)),
+                        DataItem::Integer {
+                            value: 11,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d8 67                     # geographic coordinates, tag(103)
+                   83                     #   array(3)
+                      fb 4049c00000000000 #     float(51.5)
+                      fb bfbeb851eb851eb8 #     float(-0.12)
+                      0b                  #     unsigned(11)
+                                          #   latitude(51.5), longitude(-0.12), altitude(11)
+            "#),
+        }
+
+        geographic_coordinates_out_of_range(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::GEOGRAPHIC_COORDINATES,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Integer {
+                            value: 91,
+                            bitwidth: IntegerWidth::Eight,
+                        },
+                        DataItem::Integer {
+                            value: 0,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d8 67       # geographic coordinates, tag(103)
+                   82       #   array(2)
+                      18 5b #     unsigned(91)
+                      00    #     unsigned(0)
+                            #   invalid geographic coordinates, latitude 91 out of range
+            "#),
+        }
+
+        geographic_coordinates_invalid_type(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::GEOGRAPHIC_COORDINATES,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::TextString(TextString {
+                            data: "51.5".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::TextString(TextString {
+                            data: "-0.12".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d8 67               # geographic coordinates, tag(103)
+                   82               #   array(2)
+                      64            #     text(4)
+                         35312e35   #       "51.5"
+                      65            #     text(5)
+                         2d302e3132 #       "-0.12"
+                                    #   invalid type for geographic coordinates
+            "#),
+        }
+
+        geographic_coordinate_reference_system(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::GEOGRAPHIC_COORDINATE_REFERENCE_SYSTEM,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Integer {
+                            value: 4326,
+                            bitwidth: IntegerWidth::Sixteen,
+                        },
+                        DataItem::Tag {
+                            tag: Tag::GEOGRAPHIC_COORDINATES,
+                            bitwidth: IntegerWidth::Eight,
+                            value: Box::new(DataItem::Array {
+                                data: vec![
+                                    DataItem::Float(Float::new(1.5, FloatWidth::SixtyFour)),
+                                    DataItem::Float(Float::new(2.5, FloatWidth::SixtyFour)),
+                                ],
+                                bitwidth: Some(IntegerWidth::Zero),
+                            }),
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d8 68                           # geographic coordinates with reference system, tag(104)
+                   82                           #   array(2)
+                      19 10e6                   #     unsigned(4,326)
+                      d8 67                     #     geographic coordinates, tag(103)
+                         82                     #       array(2)
+                            fb 3ff8000000000000 #         float(1.5)
+                            fb 4004000000000000 #         float(2.5)
+                                                #       latitude(1.5), longitude(2.5)
+                                                #   crs(EPSG:4326)
+            "#),
+        }
+
+        geographic_coordinate_reference_system_wkt(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::GEOGRAPHIC_COORDINATE_REFERENCE_SYSTEM,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::TextString(TextString {
+                            data: "GEOGCS[...]".into(),
                             bitwidth: IntegerWidth::Zero,
-                            value: Box::new(DataItem::ByteString(ByteString {
-                                data: hex!("123456789abcdeffedcba9876543").into(),
-                                bitwidth: IntegerWidth::Zero,
-                            })),
+                        }),
+                        DataItem::Array {
+                            data: vec![
+                                DataItem::Float(Float::new(1.5, FloatWidth::SixtyFour)),
+                                DataItem::Float(Float::new(2.5, FloatWidth::SixtyFour)),
+                                DataItem::Negative {
+                                    value: 2,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                            ],
+                            bitwidth: Some(IntegerWidth::Zero),
                         },
                     ],
-                    bitwidth: None,
-                })
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d8 68                           # geographic coordinates with reference system, tag(104)
+                   82                           #   array(2)
+                      6b                        #     text(11)
+                         47454f4743535b2e2e2e5d #       "GEOGCS[...]"
+                      83                        #     array(3)
+                         fb 3ff8000000000000    #       float(1.5)
+                         fb 4004000000000000    #       float(2.5)
+                         22                     #       negative(-3)
+                                                #   crs(well-known text)
+                                                #   latitude(1.5), longitude(2.5), altitude(-3)
+            "#),
+        }
+
+        geographic_coordinate_reference_system_invalid_type(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::GEOGRAPHIC_COORDINATE_REFERENCE_SYSTEM,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Integer {
+                            value: 4326,
+                            bitwidth: IntegerWidth::Sixteen,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d8 68         # geographic coordinates with reference system, tag(104)
+                   81         #   array(1)
+                      19 10e6 #     unsigned(4,326)
+                              #   invalid type for geographic coordinate reference system
+            "#),
+        }
+
+        network_address_ipv4(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::NETWORK_ADDRESS,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("c00a0a01").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d9 0104        # network address, tag(260)
+                   44          #   bytes(4)
+                      c00a0a01 #     h'c00a0a01'
+                               #   IPv4 address(192.10.10.1)
+            "#),
+        }
+
+        network_address_mac(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::NETWORK_ADDRESS,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("0123456789ab").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d9 0104            # network address, tag(260)
+                   46              #   bytes(6)
+                      0123456789ab #     h'0123456789ab'
+                                   #   MAC address(01:23:45:67:89:ab)
+            "#),
+        }
+
+        network_address_ipv6(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::NETWORK_ADDRESS,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("20010db885a3000000008a2e03707334").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d9 0104                                # network address, tag(260)
+                   50                                  #   bytes(16)
+                      20010db885a3000000008a2e03707334 #     h'20010db885a3000000008a2e03707334'
+                                                       #   IPv6 address(2001:db8:85a3::8a2e:370:7334)
+            "#),
+        }
+
+        network_address_invalid_length(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::NETWORK_ADDRESS,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("0123456789").into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d9 0104          # network address, tag(260)
+                   45            #   bytes(5)
+                      0123456789 #     h'0123456789'
+                                 #   invalid data length for network address
+            "#),
+        }
+
+        network_address_invalid_type(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::NETWORK_ADDRESS,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::TextString(TextString {
+                    data: "0123456789ab".into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
             },
             indoc!(r#"
-                d5                                          # suggested base64url encoding, tag(21)
-                   9f                                       #   array(*)
-                      4e                                    #     bytes(14)
-                         123456789abcdeffedcba9876543       #       b64'EjRWeJq83v_ty6mHZUM'
-                      d6                                    #     suggested base64 encoding, tag(22)
-                         9f                                 #       array(*)
-                            4e                              #         bytes(14)
-                               123456789abcdeffedcba9876543 #           b64'EjRWeJq83v/ty6mHZUM='
-                            ff                              #         break
-                      d7                                    #     suggested base16 encoding, tag(23)
-                         4e                                 #       bytes(14)
-                            123456789abcdeffedcba9876543    #         h'123456789abcdeffedcba9876543'
-                      ff                                    #     break
+                d9 0104                        # network address, tag(260)
+                   6c                          #   text(12)
+                      303132333435363738396162 #     "0123456789ab"
+                                               #   invalid type for network address
             "#),
         }
 
-        encoded_cbor(hex2value, value2hex) {
+        embedded_json(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::ENCODED_CBOR,
-                bitwidth: IntegerWidth::Eight,
+                tag: Tag::EMBEDDED_JSON,
+                bitwidth: IntegerWidth::Sixteen,
                 value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("9f64f09f87b317ff").into(),
+                    data: hex!("7b2261223a205b312c20322e3565332c2022782f79225d2c202262223a207b7d2c202263223a206e756c6c7d").into(),
+                    bitwidth: IntegerWidth::Eight,
+                })),
+            },
+            indoc!(r#"
+                d9 0106                                # embedded json, tag(262)
+                   58 2c                               #   bytes(44)
+                      7b2261223a205b312c20322e3565332c #     "{\"a\": [1, 2.5e3,"
+                      2022782f79225d2c202262223a207b7d #     " \"x/y\"], \"b\": {}"
+                      2c202263223a206e756c6c7d         #     ", \"c\": null}"
+                                                       #   json
+                                                       #     {
+                                                       #         "a": [
+                                                       #             1,
+                                                       #             2.5e3,
+                                                       #             "x/y"
+                                                       #         ],
+                                                       #         "b": {},
+                                                       #         "c": null
+                                                       #     }
+            "#),
+        }
+
+        embedded_json_invalid(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::EMBEDDED_JSON,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::ByteString(ByteString {
+                    data: hex!("7b2261223a207d").into(),
                     bitwidth: IntegerWidth::Zero,
                 })),
             },
-            indoc!("
-                d8 18                  # encoded cbor data item, tag(24)
-                   48                  #   bytes(8)
-                      9f64f09f87b317ff #     \"\\x9fd\\xf0\\x9f\\x87\\xb3\\x17\\xff\"
-                                       #   encoded cbor data item
-                                       #     9f             # array(*)
-                                       #        64          #   text(4)
-                                       #           f09f87b3 #     \"\u{1f1f3}\"
-                                       #        17          #   unsigned(23)
-                                       #        ff          #   break
-            "),
+            indoc!(r#"
+                d9 0106              # embedded json, tag(262)
+                   47                #   bytes(7)
+                      7b2261223a207d #     "{\"a\": }"
+                                     #   invalid embedded json, expected value at byte 6
+            "#),
         }
 
-        encoded_cbor_invalid(hex2value, value2hex) {
+        embedded_json_invalid_utf8(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::ENCODED_CBOR,
-                bitwidth: IntegerWidth::Eight,
+                tag: Tag::EMBEDDED_JSON,
+                bitwidth: IntegerWidth::Sixteen,
                 value: Box::new(DataItem::ByteString(ByteString {
                     data: hex!("ff").into(),
                     bitwidth: IntegerWidth::Zero,
                 })),
             },
             indoc!(r#"
-                d8 18    # encoded cbor data item, tag(24)
+                d9 0106  # embedded json, tag(262)
                    41    #   bytes(1)
                       ff #     "\xff"
-                         #   failed to parse encoded cbor data item
-                         #     Todo("Parsing error (Error(Error { input: [255], code: TagBits }))")
+                         #   invalid embedded json, invalid utf-8 at byte 0
             "#),
         }
 
-        encoded_cbor_empty(hex2value, value2hex) {
+        embedded_json_invalid_type(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::ENCODED_CBOR,
-                bitwidth: IntegerWidth::Eight,
+                tag: Tag::EMBEDDED_JSON,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::TextString(TextString {
+                    data: "{}".into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+                d9 0106    # embedded json, tag(262)
+                   62      #   text(2)
+                      7b7d #     "{}"
+                           #   invalid type for embedded json
+            "#),
+        }
+
+        self_describe_cbor_sequence(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::SELF_DESCRIBE_CBOR_SEQUENCE,
+                bitwidth: IntegerWidth::Sixteen,
                 value: Box::new(DataItem::ByteString(ByteString {
-                    data: vec![],
+                    data: hex!("424f52").into(),
                     bitwidth: IntegerWidth::Zero,
                 })),
             },
             indoc!(r#"
-                d8 18 # encoded cbor data item, tag(24)
-                   40 #   bytes(0)
-                      #     ""
-                      #   failed to parse encoded cbor data item
-                      #     Todo("Parsing error (Incomplete(Size(1)))")
+                d9 d9f8      # self describe cbor sequence, tag(55800)
+                   43        #   bytes(3)
+                      424f52 #     "BOR"
             "#),
         }
 
-        encoded_cbor_seq(hex2value, value2hex) {
+        self_describe_cbor_sequence_invalid(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::ENCODED_CBOR_SEQ,
-                bitwidth: IntegerWidth::Eight,
+                tag: Tag::SELF_DESCRIBE_CBOR_SEQUENCE,
+                bitwidth: IntegerWidth::Sixteen,
                 value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("9f64f09f87b317ff1615").into(),
+                    data: hex!("0102").into(),
                     bitwidth: IntegerWidth::Zero,
                 })),
             },
             indoc!(r#"
-                d8 3f                      # encoded cbor sequence, tag(63)
-                   4a                      #   bytes(10)
-                      9f64f09f87b317ff1615 #     "\x9fd\xf0\x9f\x87\xb3\x17\xff\x16\x15"
-                                           #   encoded cbor data item
-                                           #     9f             # array(*)
-                                           #        64          #   text(4)
-                                           #           f09f87b3 #     "🇳"
-                                           #        17          #   unsigned(23)
-                                           #        ff          #   break
-                                           #   encoded cbor data item
-                                           #     16 # unsigned(22)
-                                           #   encoded cbor data item
-                                           #     15 # unsigned(21)
+                d9 d9f8    # self describe cbor sequence, tag(55800)
+                   42      #   bytes(2)
+                      0102 #     "\x01\x02"
+                           #   invalid magic number, expected 'BOR'
             "#),
         }
 
-        encoded_cbor_seq_invalid(hex2value, value2hex) {
+        cbor_labeled_non_cbor_data(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::ENCODED_CBOR_SEQ,
-                bitwidth: IntegerWidth::Eight,
+                tag: Tag::CBOR_LABELED_NON_CBOR_DATA,
+                bitwidth: IntegerWidth::Sixteen,
                 value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("1617ff").into(),
+                    data: hex!("424f52").into(),
                     bitwidth: IntegerWidth::Zero,
                 })),
             },
             indoc!(r#"
-                d8 3f        # encoded cbor sequence, tag(63)
+                d9 d9f9      # cbor-labeled non-cbor data, tag(55801)
                    43        #   bytes(3)
-                      1617ff #     "\x16\x17\xff"
-                             #   encoded cbor data item
-                             #     16 # unsigned(22)
-                             #   encoded cbor data item
-                             #     17 # unsigned(23)
-                             #   failed to parse remaining encoded cbor sequence
-                             #     Todo("Parsing error (Error(Error { input: [255], code: TagBits }))")
+                      424f52 #     "BOR"
             "#),
         }
 
-        encoded_cbor_seq_empty(hex2value, value2hex) {
+        content_format(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::ENCODED_CBOR_SEQ,
-                bitwidth: IntegerWidth::Eight,
+                tag: Tag::from_content_format(60),
+                bitwidth: IntegerWidth::ThirtyTwo,
+                value: Box::new(DataItem::Integer {
+                    value: 1,
+                    bitwidth: IntegerWidth::Zero,
+                }),
+            },
+            indoc!(r#"
+                da 6374003c # content-format labeled cbor, tag(1668546620)
+                   01       #   unsigned(1)
+                            #   content-format(60, application/cbor)
+            "#),
+        }
+
+        content_format_sequence(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::from_content_format(50),
+                bitwidth: IntegerWidth::ThirtyTwo,
                 value: Box::new(DataItem::ByteString(ByteString {
-                    data: vec![],
+                    data: hex!("424f52").into(),
                     bitwidth: IntegerWidth::Zero,
                 })),
             },
             indoc!(r#"
-                d8 3f # encoded cbor sequence, tag(63)
-                   40 #   bytes(0)
-                      #     ""
+                da 63740032  # content-format labeled cbor, tag(1668546610)
+                   43        #   bytes(3)
+                      424f52 #     "BOR"
+                             #   content-format(50, application/json)
             "#),
         }
 
-        uri(hex2value, value2hex) {
+        content_format_unknown(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::URI,
-                bitwidth: IntegerWidth::Eight,
+                tag: Tag::from_content_format(65535),
+                bitwidth: IntegerWidth::ThirtyTwo,
+                value: Box::new(DataItem::Simple(Simple::NULL)),
+            },
+            indoc!(r#"
+                da 6374ffff # content-format labeled cbor, tag(1668612095)
+                   f6       #   null, simple(22)
+                            #   content-format(65535)
+            "#),
+        }
+
+        date_birth(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::DATE,
+                bitwidth: IntegerWidth::Sixteen,
                 value: Box::new(DataItem::TextString(TextString {
-                    data: "https://example.com".into(),
+                    data: "1940-10-09".into(),
                     bitwidth: IntegerWidth::Zero,
                 })),
             },
             indoc!(r#"
-                d8 20                                        # uri, tag(32)
-                   73                                        #   text(19)
-                      68747470733a2f2f6578616d706c652e636f6d #     "https://example.com"
-                                                             #   valid URL (checked against URL Standard, not RFC 3986)
+                d9 03ec                    # standard date string, tag(1004)
+                   6a                      #   text(10)
+                      313934302d31302d3039 #     "1940-10-09"
+                                           #   epoch(-10,676)
             "#),
         }
 
-        uri_non_http(hex2value, value2hex) {
+        date_death(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::URI,
-                bitwidth: IntegerWidth::Eight,
+                tag: Tag::DATE,
+                bitwidth: IntegerWidth::Sixteen,
                 value: Box::new(DataItem::TextString(TextString {
-                    data: "urn:oasis:names:specification:docbook:dtd:xml:4.1.2".into(),
-                    bitwidth: IntegerWidth::Eight,
+                    data: "1980-12-08".into(),
+                    bitwidth: IntegerWidth::Zero,
                 })),
             },
             indoc!(r#"
-                d8 20                                  # uri, tag(32)
-                   78 33                               #   text(51)
-                      75726e3a6f617369733a6e616d65733a #     "urn:oasis:names:"
-                      73706563696669636174696f6e3a646f #     "specification:do"
-                      63626f6f6b3a6474643a786d6c3a342e #     "cbook:dtd:xml:4."
-                      312e32                           #     "1.2"
-                                                       #   valid URL (checked against URL Standard, not RFC 3986)
+                d9 03ec                    # standard date string, tag(1004)
+                   6a                      #   text(10)
+                      313938302d31322d3038 #     "1980-12-08"
+                                           #   epoch(3,994)
             "#),
         }
 
-        uri_invalid(hex2value, value2hex) {
+        date_min_repr(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::URI,
-                bitwidth: IntegerWidth::Eight,
+                tag: Tag::DATE,
+                bitwidth: IntegerWidth::Sixteen,
                 value: Box::new(DataItem::TextString(TextString {
-                    data: "foo".into(),
+                    data: "-262144-01-01".into(),
                     bitwidth: IntegerWidth::Zero,
                 })),
             },
             indoc!(r#"
-                d8 20        # uri, tag(32)
-                   63        #   text(3)
-                      666f6f #     "foo"
-                             #   invalid URL (checked against URL Standard, not RFC 3986)
+               d9 03ec                          # standard date string, tag(1004)
+                  6d                            #   text(13)
+                     2d3236323134342d30312d3031 #     "-262144-01-01"
+                                                #   epoch(-96,465,658)
             "#),
         }
 
-        base64url(hex2value, value2hex) {
+        date_min_repr_minus_one(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::BASE64URL,
-                bitwidth: IntegerWidth::Eight,
+                tag: Tag::DATE,
+                bitwidth: IntegerWidth::Sixteen,
                 value: Box::new(DataItem::TextString(TextString {
-                    data: "aHR0cHM6Ly9leGFtcGxlLmNvbS_wn5C2".into(),
-                    bitwidth: IntegerWidth::Eight,
+                    data: "-262145-12-31".into(),
+                    bitwidth: IntegerWidth::Zero,
                 })),
             },
             indoc!(r#"
-                d8 21                                  # base64url encoded text, tag(33)
-                   78 20                               #   text(32)
-                      6148523063484d364c79396c65474674 #     "aHR0cHM6Ly9leGFt"
-                      6347786c4c6d4e7662535f776e354332 #     "cGxlLmNvbS_wn5C2"
-                                                       #   base64url decoded
-                                                       #     68747470733a2f2f6578616d706c652e # "https://example."
-                                                       #     636f6d2ff09f90b6                 # "com/\xf0\x9f\x90\xb6"
+               d9 03ec                          # standard date string, tag(1004)
+                  6d                            #   text(13)
+                     2d3236323134352d31322d3331 #     "-262145-12-31"
+                                                #   error parsing date: input is out of range
             "#),
         }
 
-        base64(hex2value, value2hex) {
+        date_max_repr(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::BASE64,
-                bitwidth: IntegerWidth::Eight,
+                tag: Tag::DATE,
+                bitwidth: IntegerWidth::Sixteen,
                 value: Box::new(DataItem::TextString(TextString {
-                    data: "aHR0cHM6Ly9leGFtcGxlLmNvbS/wn5C2".into(),
-                    bitwidth: IntegerWidth::Eight,
+                    data: "+262143-12-31".into(),
+                    bitwidth: IntegerWidth::Zero,
                 })),
             },
             indoc!(r#"
-                d8 22                                  # base64 encoded text, tag(34)
-                   78 20                               #   text(32)
-                      6148523063484d364c79396c65474674 #     "aHR0cHM6Ly9leGFt"
-                      6347786c4c6d4e7662532f776e354332 #     "cGxlLmNvbS/wn5C2"
-                                                       #   base64 decoded
-                                                       #     68747470733a2f2f6578616d706c652e # "https://example."
-                                                       #     636f6d2ff09f90b6                 # "com/\xf0\x9f\x90\xb6"
+               d9 03ec                          # standard date string, tag(1004)
+                  6d                            #   text(13)
+                     2b3236323134332d31322d3331 #     "+262143-12-31"
+                                                #   epoch(95,026,601)
+            "#),
+        }
+
+        date_max_repr_plus_one(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::DATE,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::TextString(TextString {
+                    data: "+262144-01-01".into(),
+                    bitwidth: IntegerWidth::Zero,
+                })),
+            },
+            indoc!(r#"
+               d9 03ec                          # standard date string, tag(1004)
+                  6d                            #   text(13)
+                     2b3236323134342d30312d3031 #     "+262144-01-01"
+                                                #   error parsing date: input is out of range
             "#),
         }
 
-        encoded_cbor_nested(hex2value, value2hex) {
+        shared_ref_cyclic(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::SHAREABLE,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Tag {
+                            tag: Tag::SHARED_REF,
+                            bitwidth: IntegerWidth::Eight,
+                            value: Box::new(DataItem::Integer {
+                                value: 0,
+                                bitwidth: IntegerWidth::Zero,
+                            }),
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!("
+               d8 1c       # shareable value, tag(28)
+                  81       #   array(1)
+                     d8 1d #     reference to shared value, tag(29)
+                        00 #       unsigned(0)
+                           #       reference-to(0), cyclic reference
+                           #   reference(0)
+            "),
+        }
+
+        missing_shared_ref(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::ENCODED_CBOR,
+                tag: Tag::SHARED_REF,
                 bitwidth: IntegerWidth::Eight,
-                value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("d818489f64f09f87b317ff").into(),
+                value: Box::new(DataItem::Integer {
+                    value: 0,
                     bitwidth: IntegerWidth::Zero,
-                })),
+                }),
             },
             indoc!("
-                d8 18                        # encoded cbor data item, tag(24)
-                   4b                        #   bytes(11)
-                      d818489f64f09f87b317ff #     \"\\xd8\\x18H\\x9fd\\xf0\\x9f\\x87\\xb3\\x17\\xff\"
-                                             #   encoded cbor data item
-                                             #     d8 18                  # encoded cbor data item, tag(24)
-                                             #        48                  #   bytes(8)
-                                             #           9f64f09f87b317ff #     \"\\x9fd\\xf0\\x9f\\x87\\xb3\\x17\\xff\"
-                                             #                            #   encoded cbor data item
-                                             #                            #     9f             # array(*)
-                                             #                            #        64          #   text(4)
-                                             #                            #           f09f87b3 #     \"\u{1f1f3}\"
-                                             #                            #        17          #   unsigned(23)
-                                             #                            #        ff          #   break
+               d8 1d # reference to shared value, tag(29)
+                  00 #   unsigned(0)
+                     #   reference-to(0), not previously shared
             "),
         }
 
-        uuid(hex2value, value2hex) {
-            DataItem::Tag {
-                tag: Tag::UUID,
-                bitwidth: IntegerWidth::Eight,
-                value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("8c8a8d48c00f42209cf8b75a882bf586").into(),
-                    bitwidth: IntegerWidth::Zero,
-                })),
+        encoded_cbor_with_shared_refs(hex2value, value2hex) {
+            DataItem::Array {
+                data: vec![
+                    DataItem::Tag {
+                        tag: Tag::SHAREABLE,
+                        bitwidth: IntegerWidth::Eight,
+                        value: Box::new(DataItem::Integer {
+                            value: 0,
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                    },
+                    DataItem::Tag {
+                        tag: Tag::ENCODED_CBOR,
+                        bitwidth: IntegerWidth::Eight,
+                        value: Box::new(DataItem::ByteString(ByteString {
+                            data: hex!("d81c00").into(),
+                            bitwidth: IntegerWidth::Zero,
+                        })),
+                    },
+                    DataItem::Tag {
+                        tag: Tag::SHAREABLE,
+                        bitwidth: IntegerWidth::Eight,
+                        value: Box::new(DataItem::Integer {
+                            value: 0,
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                    },
+                ],
+                bitwidth: Some(IntegerWidth::Zero),
             },
             indoc!(r#"
-                d8 25                                  # uuid, tag(37)
-                   50                                  #   bytes(16)
-                      8c8a8d48c00f42209cf8b75a882bf586 #     h'8c8a8d48c00f42209cf8b75a882bf586'
-                                                       #   uuid(variant(RFC4122), version(4, Random))
-                                                       #     base16(8c8a8d48-c00f-4220-9cf8-b75a882bf586)
-                                                       #     base58(JMZyLNqHizfirvWvE2EXBK)
-                                                       #     base64(jIqNSMAPQiCc+LdaiCv1hg)
+                83              # array(3)
+                   d8 1c        #   shareable value, tag(28)
+                      00        #     unsigned(0)
+                                #     reference(0)
+                   d8 18        #   encoded cbor data item, tag(24)
+                      43        #     bytes(3)
+                         d81c00 #       "\xd8\x1c\x00"
+                                #     encoded cbor data item
+                                #       d8 1c # shareable value, tag(28)
+                                #          00 #   unsigned(0)
+                                #             #   reference(1)
+                   d8 1c        #   shareable value, tag(28)
+                      00        #     unsigned(0)
+                                #     reference(2)
             "#),
         }
 
-        uuid_invalid_length(hex2value, value2hex) {
+        stringref(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::UUID,
-                bitwidth: IntegerWidth::Eight,
-                value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("0123456789").into(),
-                    bitwidth: IntegerWidth::Zero,
-                })),
+                tag: Tag::STRINGREF_NAMESPACE,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::TextString(TextString {
+                            data: "aaa".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::Tag {
+                            tag: Tag::STRINGREF,
+                            bitwidth: IntegerWidth::Eight,
+                            value: Box::new(DataItem::Integer {
+                                value: 0,
+                                bitwidth: IntegerWidth::Zero,
+                            }),
+                        },
+                        DataItem::ByteString(ByteString {
+                            data: hex!("010203").into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::Tag {
+                            tag: Tag::STRINGREF,
+                            bitwidth: IntegerWidth::Eight,
+                            value: Box::new(DataItem::Integer {
+                                value: 1,
+                                bitwidth: IntegerWidth::Zero,
+                            }),
+                        },
+                        DataItem::TextString(TextString {
+                            data: "bb".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::Tag {
+                            tag: Tag::STRINGREF_NAMESPACE,
+                            bitwidth: IntegerWidth::Sixteen,
+                            value: Box::new(DataItem::Array {
+                                data: vec![
+                                    DataItem::Tag {
+                                        tag: Tag::STRINGREF,
+                                        bitwidth: IntegerWidth::Eight,
+                                        value: Box::new(DataItem::Integer {
+                                            value: 0,
+                                            bitwidth: IntegerWidth::Zero,
+                                        }),
+                                    },
+                                    DataItem::TextString(TextString {
+                                        data: "ccc".into(),
+                                        bitwidth: IntegerWidth::Zero,
+                                    }),
+                                ],
+                                bitwidth: Some(IntegerWidth::Zero),
+                            }),
+                        },
+                        DataItem::Tag {
+                            tag: Tag::STRINGREF,
+                            bitwidth: IntegerWidth::Eight,
+                            value: Box::new(DataItem::Integer {
+                                value: 2,
+                                bitwidth: IntegerWidth::Zero,
+                            }),
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
             },
             indoc!(r#"
-                d8 25            # uuid, tag(37)
-                   45            #   bytes(5)
-                      0123456789 #     h'0123456789'
-                                 #   invalid data length for uuid
+                d9 0100               # stringref namespace, tag(256)
+                   87                 #   array(7)
+                      63              #     text(3)
+                         616161       #       "aaa"
+                      d8 19           #     reference to shared string, tag(25)
+                         00           #       unsigned(0)
+                                      #       stringref(0): "aaa"
+                      43              #     bytes(3)
+                         010203       #       "\x01\x02\x03"
+                      d8 19           #     reference to shared string, tag(25)
+                         01           #       unsigned(1)
+                                      #       stringref(1): h'010203'
+                      62              #     text(2)
+                         6262         #       "bb"
+                      d9 0100         #     stringref namespace, tag(256)
+                         82           #       array(2)
+                            d8 19     #         reference to shared string, tag(25)
+                               00     #           unsigned(0)
+                                      #           stringref(0), not previously defined
+                            63        #         text(3)
+                               636363 #           "ccc"
+                      d8 19           #     reference to shared string, tag(25)
+                         02           #       unsigned(2)
+                                      #       stringref(2), not previously defined
             "#),
         }
 
-        uuid_invalid_type(hex2value, value2hex) {
+        stringref_outside_namespace(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::UUID,
+                tag: Tag::STRINGREF,
                 bitwidth: IntegerWidth::Eight,
-                value: Box::new(DataItem::TextString(TextString {
-                    data: "0123456789ab".into(),
+                value: Box::new(DataItem::Integer {
+                    value: 0,
                     bitwidth: IntegerWidth::Zero,
-                })),
+                }),
             },
             indoc!(r#"
-                d8 25                          # uuid, tag(37)
-                   6c                          #   text(12)
-                      303132333435363738396162 #     "0123456789ab"
-                                               #   invalid type for uuid
+                d8 19 # reference to shared string, tag(25)
+                   00 #   unsigned(0)
+                      #   stringref(0), outside of stringref namespace
             "#),
         }
 
-        network_address_ipv4(hex2value, value2hex) {
+        shared_ref_resolved(hex2value, value2hex) {
+            DataItem::Array {
+                data: vec![
+                    DataItem::Tag {
+                        tag: Tag::SHAREABLE,
+                        bitwidth: IntegerWidth::Eight,
+                        value: Box::new(DataItem::Array {
+                            data: vec![
+                                DataItem::Integer {
+                                    value: 1,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                                DataItem::TextString(TextString {
+                                    data: "abc".into(),
+                                    bitwidth: IntegerWidth::Zero,
+                                }),
+                            ],
+                            bitwidth: Some(IntegerWidth::Zero),
+                        }),
+                    },
+                    DataItem::Tag {
+                        tag: Tag::SHARED_REF,
+                        bitwidth: IntegerWidth::Eight,
+                        value: Box::new(DataItem::Integer {
+                            value: 0,
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                    },
+                ],
+                bitwidth: Some(IntegerWidth::Zero),
+            },
+            indoc!(r#"
+                82                 # array(2)
+                   d8 1c           #   shareable value, tag(28)
+                      82           #     array(2)
+                         01        #       unsigned(1)
+                         63        #       text(3)
+                            616263 #         "abc"
+                                   #     reference(0)
+                   d8 1d           #   reference to shared value, tag(29)
+                      00           #     unsigned(0)
+                                   #     reference-to(0): [1,"abc"]
+            "#),
+        }
+
+        set(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::NETWORK_ADDRESS,
+                tag: Tag::SET,
                 bitwidth: IntegerWidth::Sixteen,
-                value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("c00a0a01").into(),
-                    bitwidth: IntegerWidth::Zero,
-                })),
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Integer {
+                            value: 1,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::TextString(TextString {
+                            data: "a".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::ByteString(ByteString {
+                            data: hex!("01").into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
             },
             indoc!(r#"
-                d9 0104        # network address, tag(260)
-                   44          #   bytes(4)
-                      c00a0a01 #     h'c00a0a01'
-                               #   IPv4 address(192.10.10.1)
+                d9 0102     # mathematical finite set, tag(258)
+                   83       #   array(3)
+                      01    #     unsigned(1)
+                      61    #     text(1)
+                         61 #       "a"
+                      41    #     bytes(1)
+                         01 #       "\x01"
             "#),
         }
 
-        network_address_mac(hex2value, value2hex) {
+        set_duplicates(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::NETWORK_ADDRESS,
+                tag: Tag::SET,
                 bitwidth: IntegerWidth::Sixteen,
-                value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("0123456789ab").into(),
-                    bitwidth: IntegerWidth::Zero,
-                })),
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Integer {
+                            value: 1,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::Integer {
+                            value: 2,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::Integer {
+                            value: 1,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::IndefiniteByteString(vec![
+                            ByteString {
+                                data: hex!("6162").into(),
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            ByteString {
+                                data: hex!("63").into(),
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ]),
+                        DataItem::ByteString(ByteString {
+                            data: hex!("616263").into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::Integer {
+                            value: 1,
+                            bitwidth: IntegerWidth::Sixteen,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
             },
             indoc!(r#"
-                d9 0104            # network address, tag(260)
-                   46              #   bytes(6)
-                      0123456789ab #     h'0123456789ab'
-                                   #   MAC address(01:23:45:67:89:ab)
+                d9 0102          # mathematical finite set, tag(258)
+                   86            #   array(6)
+                      01         #     unsigned(1)
+                      02         #     unsigned(2)
+                      01         #     unsigned(1)
+                      5f         #     bytes(*)
+                         42      #       bytes(2)
+                            6162 #         "ab"
+                         41      #       bytes(1)
+                            63   #         "c"
+                         ff      #       break
+                      43         #     bytes(3)
+                         616263  #       "abc"
+                      19 0001    #     unsigned(1)
+                                 #   warning: duplicate set element at index 2, same as index 0
+                                 #   warning: duplicate set element at index 4, same as index 3
+                                 #   warning: duplicate set element at index 5, same as index 0
             "#),
         }
 
-        network_address_ipv6(hex2value, value2hex) {
+        set_duplicate_maps(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::NETWORK_ADDRESS,
+                tag: Tag::SET,
                 bitwidth: IntegerWidth::Sixteen,
-                value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("20010db885a3000000008a2e03707334").into(),
-                    bitwidth: IntegerWidth::Zero,
-                })),
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Map {
+                            data: vec![
+                                (
+                                    DataItem::Integer {
+                                        value: 1,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                    DataItem::Integer {
+                                        value: 2,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                ),
+                                (
+                                    DataItem::Integer {
+                                        value: 3,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                    DataItem::Integer {
+                                        value: 4,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                ),
+                            ],
+                            bitwidth: Some(IntegerWidth::Zero),
+                        },
+                        DataItem::Map {
+                            data: vec![
+                                (
+                                    DataItem::Integer {
+                                        value: 3,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                    DataItem::Integer {
+                                        value: 4,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                ),
+                                (
+                                    DataItem::Integer {
+                                        value: 1,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                    DataItem::Integer {
+                                        value: 2,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                ),
+                            ],
+                            bitwidth: Some(IntegerWidth::Zero),
+                        },
+                        DataItem::Map {
+                            data: vec![
+                                (
+                                    DataItem::Integer {
+                                        value: 1,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                    DataItem::Integer {
+                                        value: 2,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                ),
+                                (
+                                    DataItem::Integer {
+                                        value: 3,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                    DataItem::Integer {
+                                        value: 5,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                ),
+                            ],
+                            bitwidth: Some(IntegerWidth::Zero),
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
             },
             indoc!(r#"
-                d9 0104                                # network address, tag(260)
-                   50                                  #   bytes(16)
-                      20010db885a3000000008a2e03707334 #     h'20010db885a3000000008a2e03707334'
-                                                       #   IPv6 address(2001:db8:85a3::8a2e:370:7334)
+                d9 0102     # mathematical finite set, tag(258)
+                   83       #   array(3)
+                      a2    #     map(2)
+                         01 #       unsigned(1)
+                         02 #       unsigned(2)
+                         03 #       unsigned(3)
+                         04 #       unsigned(4)
+                      a2    #     map(2)
+                         03 #       unsigned(3)
+                         04 #       unsigned(4)
+                         01 #       unsigned(1)
+                         02 #       unsigned(2)
+                      a2    #     map(2)
+                         01 #       unsigned(1)
+                         02 #       unsigned(2)
+                         03 #       unsigned(3)
+                         05 #       unsigned(5)
+                            #   warning: duplicate set element at index 1, same as index 0
             "#),
         }
 
-        network_address_invalid_length(hex2value, value2hex) {
+        set_invalid(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::NETWORK_ADDRESS,
+                tag: Tag::SET,
                 bitwidth: IntegerWidth::Sixteen,
-                value: Box::new(DataItem::ByteString(ByteString {
-                    data: hex!("0123456789").into(),
-                    bitwidth: IntegerWidth::Zero,
-                })),
+                value: Box::new(DataItem::Map {
+                    data: vec![
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
             },
             indoc!(r#"
-                d9 0104          # network address, tag(260)
-                   45            #   bytes(5)
-                      0123456789 #     h'0123456789'
-                                 #   invalid data length for network address
+                d9 0102 # mathematical finite set, tag(258)
+                   a0   #   map(0)
+                        #   invalid type for set
             "#),
         }
 
-        network_address_invalid_type(hex2value, value2hex) {
+        map_with_any_keys(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::NETWORK_ADDRESS,
+                tag: Tag::MAP_WITH_ANY_KEYS,
                 bitwidth: IntegerWidth::Sixteen,
-                value: Box::new(DataItem::TextString(TextString {
-                    data: "0123456789ab".into(),
-                    bitwidth: IntegerWidth::Zero,
-                })),
+                value: Box::new(DataItem::Map {
+                    data: vec![
+                        (
+                            DataItem::Array {
+                                data: vec![
+                                    DataItem::Integer {
+                                        value: 1,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                ],
+                                bitwidth: Some(IntegerWidth::Zero),
+                            },
+                            DataItem::Integer {
+                                value: 2,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ),
+                        (
+                            DataItem::Integer {
+                                value: 1,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 3,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
             },
             indoc!(r#"
-                d9 0104                        # network address, tag(260)
-                   6c                          #   text(12)
-                      303132333435363738396162 #     "0123456789ab"
-                                               #   invalid type for network address
+                d9 0103     # map with any keys, tag(259)
+                   a2       #   map(2)
+                      81    #     array(1)
+                         01 #       unsigned(1)
+                      02    #     unsigned(2)
+                      01    #     unsigned(1)
+                      03    #     unsigned(3)
             "#),
         }
 
-        date_birth(hex2value, value2hex) {
+        map_with_any_keys_duplicates(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::DATE,
+                tag: Tag::MAP_WITH_ANY_KEYS,
                 bitwidth: IntegerWidth::Sixteen,
-                value: Box::new(DataItem::TextString(TextString {
-                    data: "1940-10-09".into(),
-                    bitwidth: IntegerWidth::Zero,
-                })),
+                value: Box::new(DataItem::Map {
+                    data: vec![
+                        (
+                            DataItem::Array {
+                                data: vec![
+                                    DataItem::Integer {
+                                        value: 1,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                ],
+                                bitwidth: Some(IntegerWidth::Zero),
+                            },
+                            DataItem::Integer {
+                                value: 2,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ),
+                        (
+                            DataItem::Integer {
+                                value: 1,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 3,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ),
+                        (
+                            DataItem::Array {
+                                data: vec![
+                                    DataItem::Integer {
+                                        value: 1,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                ],
+                                bitwidth: Some(IntegerWidth::Zero),
+                            },
+                            DataItem::Integer {
+                                value: 4,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
             },
             indoc!(r#"
-                d9 03ec                    # standard date string, tag(1004)
-                   6a                      #   text(10)
-                      313934302d31302d3039 #     "1940-10-09"
-                                           #   epoch(-10,676)
+                d9 0103     # map with any keys, tag(259)
+                   a3       #   map(3)
+                      81    #     array(1)
+                         01 #       unsigned(1)
+                      02    #     unsigned(2)
+                      01    #     unsigned(1)
+                      03    #     unsigned(3)
+                      81    #     array(1)
+                         01 #       unsigned(1)
+                      04    #     unsigned(4)
+                            #   warning: duplicate map key at index 2, same as index 0
             "#),
         }
 
-        date_death(hex2value, value2hex) {
+        map_with_any_keys_invalid(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::DATE,
+                tag: Tag::MAP_WITH_ANY_KEYS,
                 bitwidth: IntegerWidth::Sixteen,
-                value: Box::new(DataItem::TextString(TextString {
-                    data: "1980-12-08".into(),
-                    bitwidth: IntegerWidth::Zero,
-                })),
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
             },
             indoc!(r#"
-                d9 03ec                    # standard date string, tag(1004)
-                   6a                      #   text(10)
-                      313938302d31322d3038 #     "1980-12-08"
-                                           #   epoch(3,994)
+                d9 0103 # map with any keys, tag(259)
+                   80   #   array(0)
+                        #   invalid type for map with any keys
             "#),
         }
 
-        date_min_repr(hex2value, value2hex) {
+        multi_dimensional_array(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::DATE,
-                bitwidth: IntegerWidth::Sixteen,
-                value: Box::new(DataItem::TextString(TextString {
-                    data: "-262144-01-01".into(),
-                    bitwidth: IntegerWidth::Zero,
-                })),
+                tag: Tag::MULTI_DIMENSIONAL_ARRAY,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Array {
+                            data: vec![
+                                DataItem::Integer {
+                                    value: 2,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                                DataItem::Integer {
+                                    value: 3,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                            ],
+                            bitwidth: Some(IntegerWidth::Zero),
+                        },
+                        DataItem::Tag {
+                            tag: Tag(64),
+                            bitwidth: IntegerWidth::Eight,
+                            value: Box::new(DataItem::ByteString(ByteString {
+                                data: hex!("010203040506").into(),
+                                bitwidth: IntegerWidth::Zero,
+                            })),
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
             },
             indoc!(r#"
-               d9 03ec                          # standard date string, tag(1004)
-                  6d                            #   text(13)
-                     2d3236323134342d30312d3031 #     "-262144-01-01"
-                                                #   epoch(-96,465,658)
+                d8 28          # multi-dimensional array, row-major, tag(40)
+                   82          #   array(2)
+                      82       #     array(2)
+                         02    #       unsigned(2)
+                         03    #       unsigned(3)
+                      d8 40    #     typed array of u8, tag(64)
+                         46    #       bytes(6)
+                            01 #         unsigned(1)
+                            02 #         unsigned(2)
+                            03 #         unsigned(3)
+                            04 #         unsigned(4)
+                            05 #         unsigned(5)
+                            06 #         unsigned(6)
+                               #   dimensions(2, 3)
+                               #     [1,2,3]
+                               #     [4,5,6]
             "#),
         }
 
-        date_min_repr_minus_one(hex2value, value2hex) {
+        multi_dimensional_array_column_major(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::DATE,
+                tag: Tag::MULTI_DIMENSIONAL_ARRAY_COLUMN_MAJOR,
                 bitwidth: IntegerWidth::Sixteen,
-                value: Box::new(DataItem::TextString(TextString {
-                    data: "-262145-12-31".into(),
-                    bitwidth: IntegerWidth::Zero,
-                })),
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Array {
+                            data: vec![
+                                DataItem::Integer {
+                                    value: 2,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                                DataItem::Integer {
+                                    value: 3,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                            ],
+                            bitwidth: Some(IntegerWidth::Zero),
+                        },
+                        DataItem::Array {
+                            data: vec![
+                                DataItem::Integer {
+                                    value: 1,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                                DataItem::Integer {
+                                    value: 2,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                                DataItem::Integer {
+                                    value: 3,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                                DataItem::Integer {
+                                    value: 4,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                                DataItem::Integer {
+                                    value: 5,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                                DataItem::Integer {
+                                    value: 6,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                            ],
+                            bitwidth: Some(IntegerWidth::Zero),
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
             },
             indoc!(r#"
-               d9 03ec                          # standard date string, tag(1004)
-                  6d                            #   text(13)
-                     2d3236323134352d31322d3331 #     "-262145-12-31"
-                                                #   error parsing date: input is out of range
+                d9 0410     # multi-dimensional array, column-major, tag(1040)
+                   82       #   array(2)
+                      82    #     array(2)
+                         02 #       unsigned(2)
+                         03 #       unsigned(3)
+                      86    #     array(6)
+                         01 #       unsigned(1)
+                         02 #       unsigned(2)
+                         03 #       unsigned(3)
+                         04 #       unsigned(4)
+                         05 #       unsigned(5)
+                         06 #       unsigned(6)
+                            #   dimensions(2, 3)
+                            #     [1,3,5]
+                            #     [2,4,6]
             "#),
         }
 
-        date_max_repr(hex2value, value2hex) {
+        multi_dimensional_array_three_dimensions(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::DATE,
-                bitwidth: IntegerWidth::Sixteen,
-                value: Box::new(DataItem::TextString(TextString {
-                    data: "+262143-12-31".into(),
-                    bitwidth: IntegerWidth::Zero,
-                })),
+                tag: Tag::MULTI_DIMENSIONAL_ARRAY,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Array {
+                            data: vec![
+                                DataItem::Integer {
+                                    value: 2,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                                DataItem::Integer {
+                                    value: 2,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                                DataItem::Integer {
+                                    value: 2,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                            ],
+                            bitwidth: Some(IntegerWidth::Zero),
+                        },
+                        DataItem::Tag {
+                            tag: Tag::HOMOGENEOUS_ARRAY,
+                            bitwidth: IntegerWidth::Eight,
+                            value: Box::new(DataItem::Array {
+                                data: vec![
+                                    DataItem::Integer {
+                                        value: 1,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                    DataItem::Integer {
+                                        value: 2,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                    DataItem::Integer {
+                                        value: 3,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                    DataItem::Integer {
+                                        value: 4,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                    DataItem::Integer {
+                                        value: 5,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                    DataItem::Integer {
+                                        value: 6,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                    DataItem::Integer {
+                                        value: 7,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                    DataItem::Integer {
+                                        value: 8,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                ],
+                                bitwidth: Some(IntegerWidth::Zero),
+                            }),
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
             },
             indoc!(r#"
-               d9 03ec                          # standard date string, tag(1004)
-                  6d                            #   text(13)
-                     2b3236323134332d31322d3331 #     "+262143-12-31"
-                                                #   epoch(95,026,601)
+                d8 28          # multi-dimensional array, row-major, tag(40)
+                   82          #   array(2)
+                      83       #     array(3)
+                         02    #       unsigned(2)
+                         02    #       unsigned(2)
+                         02    #       unsigned(2)
+                      d8 29    #     homogeneous array, tag(41)
+                         88    #       array(8)
+                            01 #         unsigned(1)
+                            02 #         unsigned(2)
+                            03 #         unsigned(3)
+                            04 #         unsigned(4)
+                            05 #         unsigned(5)
+                            06 #         unsigned(6)
+                            07 #         unsigned(7)
+                            08 #         unsigned(8)
+                               #   dimensions(2, 2, 2)
+                               #     [[1,2],[3,4]]
+                               #     [[5,6],[7,8]]
             "#),
         }
 
-        date_max_repr_plus_one(hex2value, value2hex) {
+        multi_dimensional_array_one_dimension(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::DATE,
-                bitwidth: IntegerWidth::Sixteen,
-                value: Box::new(DataItem::TextString(TextString {
-                    data: "+262144-01-01".into(),
-                    bitwidth: IntegerWidth::Zero,
-                })),
+                tag: Tag::MULTI_DIMENSIONAL_ARRAY,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Array {
+                            data: vec![
+                                DataItem::Integer {
+                                    value: 3,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                            ],
+                            bitwidth: Some(IntegerWidth::Zero),
+                        },
+                        DataItem::Tag {
+                            tag: Tag(85),
+                            bitwidth: IntegerWidth::Eight,
+                            value: Box::new(DataItem::ByteString(ByteString {
+                                data: hex!("0000803f00000040cdcccc3d").into(),
+                                bitwidth: IntegerWidth::Zero,
+                            })),
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
             },
             indoc!(r#"
-               d9 03ec                          # standard date string, tag(1004)
-                  6d                            #   text(13)
-                     2b3236323134342d30312d3031 #     "+262144-01-01"
-                                                #   error parsing date: input is out of range
+                d8 28                # multi-dimensional array, row-major, tag(40)
+                   82                #   array(2)
+                      81             #     array(1)
+                         03          #       unsigned(3)
+                      d8 55          #     typed array of f32, little endian, tag(85)
+                         4c          #       bytes(12)
+                            0000803f #         float(1)
+                            00000040 #         float(2)
+                            cdcccc3d #         float(0.1)
+                                     #   dimensions(3)
+                                     #     [1.0,2.0,0.1]
             "#),
         }
 
-        shared_ref_cyclic(hex2value, value2hex) {
+        multi_dimensional_array_wrong_count(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::SHAREABLE,
+                tag: Tag::MULTI_DIMENSIONAL_ARRAY,
                 bitwidth: IntegerWidth::Eight,
                 value: Box::new(DataItem::Array {
                     data: vec![
-                        DataItem::Tag {
-                            tag: Tag::SHARED_REF,
-                            bitwidth: IntegerWidth::Eight,
-                            value: Box::new(DataItem::Integer {
-                                value: 0,
-                                bitwidth: IntegerWidth::Zero,
-                            }),
+                        DataItem::Array {
+                            data: vec![
+                                DataItem::Integer {
+                                    value: 2,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                                DataItem::Integer {
+                                    value: 4,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                            ],
+                            bitwidth: Some(IntegerWidth::Zero),
+                        },
+                        DataItem::Array {
+                            data: vec![
+                                DataItem::Integer {
+                                    value: 1,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                                DataItem::Integer {
+                                    value: 2,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                                DataItem::Integer {
+                                    value: 3,
+                                    bitwidth: IntegerWidth::Zero,
+                                },
+                            ],
+                            bitwidth: Some(IntegerWidth::Zero),
                         },
                     ],
                     bitwidth: Some(IntegerWidth::Zero),
                 }),
             },
-            indoc!("
-               d8 1c       # shareable value, tag(28)
-                  81       #   array(1)
-                     d8 1d #     reference to shared value, tag(29)
-                        00 #       unsigned(0)
-                           #       reference-to(0)
-                           #   reference(0)
-            "),
+            indoc!(r#"
+                d8 28       # multi-dimensional array, row-major, tag(40)
+                   82       #   array(2)
+                      82    #     array(2)
+                         02 #       unsigned(2)
+                         04 #       unsigned(4)
+                      83    #     array(3)
+                         01 #       unsigned(1)
+                         02 #       unsigned(2)
+                         03 #       unsigned(3)
+                            #   invalid multi-dimensional array, dimensions product 8 does not match element count 3
+            "#),
         }
 
-        missing_shared_ref(hex2value, value2hex) {
+        homogeneous_array(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::SHARED_REF,
+                tag: Tag::HOMOGENEOUS_ARRAY,
                 bitwidth: IntegerWidth::Eight,
-                value: Box::new(DataItem::Integer {
-                    value: 0,
-                    bitwidth: IntegerWidth::Zero,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Integer {
+                            value: 1,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::Negative {
+                            value: 0,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::Integer {
+                            value: 2,
+                            bitwidth: IntegerWidth::Zero,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
                 }),
             },
-            indoc!("
-               d8 1d # reference to shared value, tag(29)
-                  00 #   unsigned(0)
-                     #   reference-to(0), not previously shared
-            "),
+            indoc!(r#"
+                d8 29    # homogeneous array, tag(41)
+                   83    #   array(3)
+                      01 #     unsigned(1)
+                      20 #     negative(-1)
+                      02 #     unsigned(2)
+            "#),
         }
 
-        encoded_cbor_with_shared_refs(hex2value, value2hex) {
-            DataItem::Array {
-                data: vec![
-                    DataItem::Tag {
-                        tag: Tag::SHAREABLE,
-                        bitwidth: IntegerWidth::Eight,
-                        value: Box::new(DataItem::Integer {
-                            value: 0,
-                            bitwidth: IntegerWidth::Zero,
-                        }),
-                    },
-                    DataItem::Tag {
-                        tag: Tag::ENCODED_CBOR,
-                        bitwidth: IntegerWidth::Eight,
-                        value: Box::new(DataItem::ByteString(ByteString {
-                            data: hex!("d81c00").into(),
+        heterogeneous_array(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::HOMOGENEOUS_ARRAY,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::Integer {
+                            value: 1,
                             bitwidth: IntegerWidth::Zero,
-                        })),
-                    },
-                    DataItem::Tag {
-                        tag: Tag::SHAREABLE,
-                        bitwidth: IntegerWidth::Eight,
-                        value: Box::new(DataItem::Integer {
+                        },
+                        DataItem::Negative {
                             value: 0,
                             bitwidth: IntegerWidth::Zero,
+                        },
+                        DataItem::TextString(TextString {
+                            data: "a".into(),
+                            bitwidth: IntegerWidth::Zero,
                         }),
-                    },
-                ],
-                bitwidth: Some(IntegerWidth::Zero),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
             },
             indoc!(r#"
-                83              # array(3)
-                   d8 1c        #   shareable value, tag(28)
-                      00        #     unsigned(0)
-                                #     reference(0)
-                   d8 18        #   encoded cbor data item, tag(24)
-                      43        #     bytes(3)
-                         d81c00 #       "\xd8\x1c\x00"
-                                #     encoded cbor data item
-                                #       d8 1c # shareable value, tag(28)
-                                #          00 #   unsigned(0)
-                                #             #   reference(0)
-                   d8 1c        #   shareable value, tag(28)
-                      00        #     unsigned(0)
-                                #     reference(1)
+                d8 29       # homogeneous array, tag(41)
+                   83       #   array(3)
+                      01    #     unsigned(1)
+                      20    #     negative(-1)
+                      61    #     text(1)
+                         61 #       "a"
+                            #   warning: elements are not all of the same type
             "#),
         }
 
@@ -1991,126 +4549,641 @@ testcases! {
             "),
         }
 
-        ipv6_address_and_prefix(hex2value, value2hex) {
+        ipv6_address_and_prefix(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::IPV6,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::ByteString(ByteString {
+                            data: hex!("20010db81234deedbeefcafefacefeed").into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::Integer {
+                            value: 56,
+                            bitwidth: IntegerWidth::Eight,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!("
+                d8 36                                     # ipv6 address and/or prefix, tag(54)
+                   82                                     #   array(2)
+                      50                                  #     bytes(16)
+                         20010db81234deedbeefcafefacefeed #       h'20010db81234deedbeefcafefacefeed'
+                      18 38                               #     unsigned(56)
+                                                          #   IPv6 address-and-prefix(2001:db8:1234:deed:beef:cafe:face:feed/56)
+            "),
+        }
+
+        ipv6_address_and_zone(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::IPV6,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::ByteString(ByteString {
+                            data: hex!("fe8000000000020202fffffffe030303").into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::Simple(Simple::NULL),
+                        DataItem::Integer {
+                            value: 42,
+                            bitwidth: IntegerWidth::Eight,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!("
+                d8 36                                     # ipv6 address and/or prefix, tag(54)
+                   83                                     #   array(3)
+                      50                                  #     bytes(16)
+                         fe8000000000020202fffffffe030303 #       h'fe8000000000020202fffffffe030303'
+                      f6                                  #     null, simple(22)
+                      18 2a                               #     unsigned(42)
+                                                          #   IPv6 address-and-zone(fe80::202:2ff:ffff:fe03:303%42)
+            "),
+        }
+
+        ipv6_address_and_text_zone(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::IPV6,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::ByteString(ByteString {
+                            data: hex!("fe8000000000020202fffffffe030303").into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::Simple(Simple::NULL),
+                        DataItem::TextString(TextString {
+                            data: "eth0".into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d8 36                                     # ipv6 address and/or prefix, tag(54)
+                   83                                     #   array(3)
+                      50                                  #     bytes(16)
+                         fe8000000000020202fffffffe030303 #       h'fe8000000000020202fffffffe030303'
+                      f6                                  #     null, simple(22)
+                      64                                  #     text(4)
+                         65746830                         #       "eth0"
+                                                          #   IPv6 address-and-zone(fe80::202:2ff:ffff:fe03:303%eth0)
+            "#),
+        }
+
+        ipv6_address_and_zone_and_prefix(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::IPV6,
+                bitwidth: IntegerWidth::Eight,
+                value: Box::new(DataItem::Array {
+                    data: vec![
+                        DataItem::ByteString(ByteString {
+                            data: hex!("fe8000000000020202fffffffe030303").into(),
+                            bitwidth: IntegerWidth::Zero,
+                        }),
+                        DataItem::Integer {
+                            value: 64,
+                            bitwidth: IntegerWidth::Eight,
+                        },
+                        DataItem::Integer {
+                            value: 42,
+                            bitwidth: IntegerWidth::Eight,
+                        },
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!("
+                d8 36                                     # ipv6 address and/or prefix, tag(54)
+                   83                                     #   array(3)
+                      50                                  #     bytes(16)
+                         fe8000000000020202fffffffe030303 #       h'fe8000000000020202fffffffe030303'
+                      18 40                               #     unsigned(64)
+                      18 2a                               #     unsigned(42)
+                                                          #   IPv6 address-and-zone-and-prefix(fe80::202:2ff:ffff:fe03:303%42/64)
+            "),
+        }
+
+        extended_time_milliseconds(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::EXTENDED_TIME,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::Map {
+                    data: vec![
+                        (
+                            DataItem::Integer {
+                                value: 1,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 1672531200,
+                                bitwidth: IntegerWidth::ThirtyTwo,
+                            },
+                        ),
+                        (
+                            DataItem::Negative {
+                                value: 2,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 5,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d9 03e9           # extended time, tag(1001)
+                   a2             #   map(2)
+                      01          #     unsigned(1)
+                      1a 63b0cd00 #     unsigned(1,672,531,200)
+                      22          #     negative(-3)
+                      05          #     unsigned(5)
+                                  #   datetime(2023-01-01T00:00:00.005Z)
+            "#),
+        }
+
+        extended_time_nanoseconds_tai(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::EXTENDED_TIME,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::Map {
+                    data: vec![
+                        (
+                            DataItem::Integer {
+                                value: 1,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 1672531237,
+                                bitwidth: IntegerWidth::ThirtyTwo,
+                            },
+                        ),
+                        (
+                            DataItem::Negative {
+                                value: 8,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 123456789,
+                                bitwidth: IntegerWidth::ThirtyTwo,
+                            },
+                        ),
+                        (
+                            DataItem::Negative {
+                                value: 9,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 1,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d9 03e9           # extended time, tag(1001)
+                   a3             #   map(3)
+                      01          #     unsigned(1)
+                      1a 63b0cd25 #     unsigned(1,672,531,237)
+                      28          #     negative(-9)
+                      1a 075bcd15 #     unsigned(123,456,789)
+                      29          #     negative(-10)
+                      01          #     unsigned(1)
+                                  #   datetime(2023-01-01T00:00:37.123456789)
+                                  #   timescale(TAI)
+            "#),
+        }
+
+        extended_time_decimal_fraction(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::EXTENDED_TIME,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::Map {
+                    data: vec![
+                        (
+                            DataItem::Integer {
+                                value: 4,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Array {
+                                data: vec![
+                                    DataItem::Negative {
+                                        value: 0,
+                                        bitwidth: IntegerWidth::Zero,
+                                    },
+                                    DataItem::Integer {
+                                        value: 16725312005,
+                                        bitwidth: IntegerWidth::SixtyFour,
+                                    },
+                                ],
+                                bitwidth: Some(IntegerWidth::Zero),
+                            },
+                        ),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d9 03e9                      # extended time, tag(1001)
+                   a1                        #   map(1)
+                      04                     #     unsigned(4)
+                      82                     #     array(2)
+                         20                  #       negative(-1)
+                         1b 00000003e4e80205 #       unsigned(16,725,312,005)
+                                             #   datetime(2023-01-01T00:00:00.5Z)
+            "#),
+        }
+
+        extended_time_negative_base(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::EXTENDED_TIME,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::Map {
+                    data: vec![
+                        (
+                            DataItem::Integer {
+                                value: 1,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Negative {
+                                value: 0,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ),
+                        (
+                            DataItem::Negative {
+                                value: 5,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 500000,
+                                bitwidth: IntegerWidth::ThirtyTwo,
+                            },
+                        ),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d9 03e9           # extended time, tag(1001)
+                   a2             #   map(2)
+                      01          #     unsigned(1)
+                      20          #     negative(-1)
+                      25          #     negative(-6)
+                      1a 0007a120 #     unsigned(500,000)
+                                  #   datetime(1969-12-31T23:59:59.500000Z)
+            "#),
+        }
+
+        extended_time_float_with_fraction(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::EXTENDED_TIME,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::Map {
+                    data: vec![
+                        (
+                            DataItem::Integer {
+                                value: 1,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Float(Float::new(1.5, FloatWidth::SixtyFour)),
+                        ),
+                        (
+                            DataItem::Negative {
+                                value: 2,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 5,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d9 03e9                   # extended time, tag(1001)
+                   a2                     #   map(2)
+                      01                  #     unsigned(1)
+                      fb 3ff8000000000000 #     float(1.5)
+                      22                  #     negative(-3)
+                      05                  #     unsigned(5)
+                                          #   invalid extended time, fraction requires an integer base value
+            "#),
+        }
+
+        extended_time_multiple_fractions(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::EXTENDED_TIME,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::Map {
+                    data: vec![
+                        (
+                            DataItem::Integer {
+                                value: 1,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 1,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ),
+                        (
+                            DataItem::Negative {
+                                value: 2,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 5,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ),
+                        (
+                            DataItem::Negative {
+                                value: 5,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 5,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d9 03e9  # extended time, tag(1001)
+                   a3    #   map(3)
+                      01 #     unsigned(1)
+                      01 #     unsigned(1)
+                      22 #     negative(-3)
+                      05 #     unsigned(5)
+                      25 #     negative(-6)
+                      05 #     unsigned(5)
+                         #   invalid extended time, multiple fractions
+            "#),
+        }
+
+        extended_time_fraction_out_of_range(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::EXTENDED_TIME,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::Map {
+                    data: vec![
+                        (
+                            DataItem::Integer {
+                                value: 1,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 1,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ),
+                        (
+                            DataItem::Negative {
+                                value: 2,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 1000,
+                                bitwidth: IntegerWidth::Sixteen,
+                            },
+                        ),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d9 03e9       # extended time, tag(1001)
+                   a2         #   map(2)
+                      01      #     unsigned(1)
+                      01      #     unsigned(1)
+                      22      #     negative(-3)
+                      19 03e8 #     unsigned(1,000)
+                              #   invalid extended time, fraction out of range
+            "#),
+        }
+
+        extended_time_missing_base(hex2value, value2hex) {
+            DataItem::Tag {
+                tag: Tag::EXTENDED_TIME,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::Map {
+                    data: vec![
+                        (
+                            DataItem::Negative {
+                                value: 2,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 5,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                        ),
+                    ],
+                    bitwidth: Some(IntegerWidth::Zero),
+                }),
+            },
+            indoc!(r#"
+                d9 03e9  # extended time, tag(1001)
+                   a1    #   map(1)
+                      22 #     negative(-3)
+                      05 #     unsigned(5)
+                         #   invalid extended time, missing base value
+            "#),
+        }
+
+        duration(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::IPV6,
-                bitwidth: IntegerWidth::Eight,
-                value: Box::new(DataItem::Array {
+                tag: Tag::DURATION,
+                bitwidth: IntegerWidth::Sixteen,
+                value: Box::new(DataItem::Map {
                     data: vec![
-                        DataItem::ByteString(ByteString {
-                            data: hex!("20010db81234deedbeefcafefacefeed").into(),
-                            bitwidth: IntegerWidth::Zero,
-                        }),
-                        DataItem::Integer {
-                            value: 56,
-                            bitwidth: IntegerWidth::Eight,
-                        },
+                        (
+                            DataItem::Integer {
+                                value: 1,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 3600,
+                                bitwidth: IntegerWidth::Sixteen,
+                            },
+                        ),
+                        (
+                            DataItem::Negative {
+                                value: 2,
+                                bitwidth: IntegerWidth::Zero,
+                            },
+                            DataItem::Integer {
+                                value: 250,
+                                bitwidth: IntegerWidth::Eight,
+                            },
+                        ),
                     ],
                     bitwidth: Some(IntegerWidth::Zero),
                 }),
             },
-            indoc!("
-                d8 36                                     # ipv6 address and/or prefix, tag(54)
-                   82                                     #   array(2)
-                      50                                  #     bytes(16)
-                         20010db81234deedbeefcafefacefeed #       h'20010db81234deedbeefcafefacefeed'
-                      18 38                               #     unsigned(56)
-                                                          #   IPv6 address-and-prefix(2001:db8:1234:deed:beef:cafe:face:feed/56)
-            "),
+            indoc!(r#"
+                d9 03ea       # duration, tag(1002)
+                   a2         #   map(2)
+                      01      #     unsigned(1)
+                      19 0e10 #     unsigned(3,600)
+                      22      #     negative(-3)
+                      18 fa   #     unsigned(250)
+                              #   duration(3600.250s)
+            "#),
         }
 
-        ipv6_address_and_zone(hex2value, value2hex) {
+        period_start_duration(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::IPV6,
-                bitwidth: IntegerWidth::Eight,
+                tag: Tag::PERIOD,
+                bitwidth: IntegerWidth::Sixteen,
                 value: Box::new(DataItem::Array {
                     data: vec![
-                        DataItem::ByteString(ByteString {
-                            data: hex!("fe8000000000020202fffffffe030303").into(),
-                            bitwidth: IntegerWidth::Zero,
-                        }),
-                        DataItem::Simple(Simple::NULL),
                         DataItem::Integer {
-                            value: 42,
-                            bitwidth: IntegerWidth::Eight,
+                            value: 1672531200,
+                            bitwidth: IntegerWidth::ThirtyTwo,
+                        },
+                        DataItem::Simple(Simple::NULL),
+                        DataItem::Tag {
+                            tag: Tag::DURATION,
+                            bitwidth: IntegerWidth::Sixteen,
+                            value: Box::new(DataItem::Map {
+                                data: vec![
+                                    (
+                                        DataItem::Integer {
+                                            value: 1,
+                                            bitwidth: IntegerWidth::Zero,
+                                        },
+                                        DataItem::Integer {
+                                            value: 3600,
+                                            bitwidth: IntegerWidth::Sixteen,
+                                        },
+                                    ),
+                                ],
+                                bitwidth: Some(IntegerWidth::Zero),
+                            }),
                         },
                     ],
                     bitwidth: Some(IntegerWidth::Zero),
                 }),
             },
-            indoc!("
-                d8 36                                     # ipv6 address and/or prefix, tag(54)
-                   83                                     #   array(3)
-                      50                                  #     bytes(16)
-                         fe8000000000020202fffffffe030303 #       h'fe8000000000020202fffffffe030303'
-                      f6                                  #     null, simple(22)
-                      18 2a                               #     unsigned(42)
-                                                          #   IPv6 address-and-zone(fe80::202:2ff:ffff:fe03:303%42)
-            "),
+            indoc!(r#"
+                d9 03eb             # period, tag(1003)
+                   83               #   array(3)
+                      1a 63b0cd00   #     unsigned(1,672,531,200)
+                      f6            #     null, simple(22)
+                      d9 03ea       #     duration, tag(1002)
+                         a1         #       map(1)
+                            01      #         unsigned(1)
+                            19 0e10 #         unsigned(3,600)
+                                    #       duration(3600s)
+                                    #   start(2023-01-01T00:00:00Z)
+                                    #   duration(3600s)
+            "#),
         }
 
-        ipv6_address_and_text_zone(hex2value, value2hex) {
+        period_start_end(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::IPV6,
-                bitwidth: IntegerWidth::Eight,
+                tag: Tag::PERIOD,
+                bitwidth: IntegerWidth::Sixteen,
                 value: Box::new(DataItem::Array {
                     data: vec![
-                        DataItem::ByteString(ByteString {
-                            data: hex!("fe8000000000020202fffffffe030303").into(),
-                            bitwidth: IntegerWidth::Zero,
-                        }),
+                        DataItem::Integer {
+                            value: 1672531200,
+                            bitwidth: IntegerWidth::ThirtyTwo,
+                        },
+                        DataItem::Tag {
+                            tag: Tag::EXTENDED_TIME,
+                            bitwidth: IntegerWidth::Sixteen,
+                            value: Box::new(DataItem::Map {
+                                data: vec![
+                                    (
+                                        DataItem::Integer {
+                                            value: 1,
+                                            bitwidth: IntegerWidth::Zero,
+                                        },
+                                        DataItem::Integer {
+                                            value: 1672534800,
+                                            bitwidth: IntegerWidth::ThirtyTwo,
+                                        },
+                                    ),
+                                    (
+                                        DataItem::Negative {
+                                            value: 2,
+                                            bitwidth: IntegerWidth::Zero,
+                                        },
+                                        DataItem::Integer {
+                                            value: 5,
+                                            bitwidth: IntegerWidth::Zero,
+                                        },
+                                    ),
+                                ],
+                                bitwidth: Some(IntegerWidth::Zero),
+                            }),
+                        },
                         DataItem::Simple(Simple::NULL),
-                        DataItem::TextString(TextString {
-                            data: "eth0".into(),
-                            bitwidth: IntegerWidth::Zero,
-                        }),
                     ],
                     bitwidth: Some(IntegerWidth::Zero),
                 }),
             },
             indoc!(r#"
-                d8 36                                     # ipv6 address and/or prefix, tag(54)
-                   83                                     #   array(3)
-                      50                                  #     bytes(16)
-                         fe8000000000020202fffffffe030303 #       h'fe8000000000020202fffffffe030303'
-                      f6                                  #     null, simple(22)
-                      64                                  #     text(4)
-                         65746830                         #       "eth0"
-                                                          #   IPv6 address-and-zone(fe80::202:2ff:ffff:fe03:303%eth0)
+                d9 03eb                 # period, tag(1003)
+                   83                   #   array(3)
+                      1a 63b0cd00       #     unsigned(1,672,531,200)
+                      d9 03e9           #     extended time, tag(1001)
+                         a2             #       map(2)
+                            01          #         unsigned(1)
+                            1a 63b0db10 #         unsigned(1,672,534,800)
+                            22          #         negative(-3)
+                            05          #         unsigned(5)
+                                        #       datetime(2023-01-01T01:00:00.005Z)
+                      f6                #     null, simple(22)
+                                        #   start(2023-01-01T00:00:00Z)
+                                        #   end(2023-01-01T01:00:00.005Z)
+                                        #   duration(3600.005s)
             "#),
         }
 
-        ipv6_address_and_zone_and_prefix(hex2value, value2hex) {
+        period_underspecified(hex2value, value2hex) {
             DataItem::Tag {
-                tag: Tag::IPV6,
-                bitwidth: IntegerWidth::Eight,
+                tag: Tag::PERIOD,
+                bitwidth: IntegerWidth::Sixteen,
                 value: Box::new(DataItem::Array {
                     data: vec![
-                        DataItem::ByteString(ByteString {
-                            data: hex!("fe8000000000020202fffffffe030303").into(),
-                            bitwidth: IntegerWidth::Zero,
-                        }),
-                        DataItem::Integer {
-                            value: 64,
-                            bitwidth: IntegerWidth::Eight,
-                        },
                         DataItem::Integer {
-                            value: 42,
-                            bitwidth: IntegerWidth::Eight,
+                            value: 1672531200,
+                            bitwidth: IntegerWidth::ThirtyTwo,
                         },
+                        DataItem::Simple(Simple::NULL),
+                        DataItem::Simple(Simple::NULL),
                     ],
                     bitwidth: Some(IntegerWidth::Zero),
                 }),
             },
-            indoc!("
-                d8 36                                     # ipv6 address and/or prefix, tag(54)
-                   83                                     #   array(3)
-                      50                                  #     bytes(16)
-                         fe8000000000020202fffffffe030303 #       h'fe8000000000020202fffffffe030303'
-                      18 40                               #     unsigned(64)
-                      18 2a                               #     unsigned(42)
-                                                          #   IPv6 address-and-zone-and-prefix(fe80::202:2ff:ffff:fe03:303%42/64)
-            "),
+            indoc!(r#"
+                d9 03eb           # period, tag(1003)
+                   83             #   array(3)
+                      1a 63b0cd00 #     unsigned(1,672,531,200)
+                      f6          #     null, simple(22)
+                      f6          #     null, simple(22)
+                                  #   invalid period, exactly two of start, end and duration must be given
+            "#),
         }
 
         mod typed_array {
@@ -2225,20 +5298,58 @@ testcases! {
                 "),
             }
 
+            f128_be(hex2value, value2hex) {
+                DataItem::Tag {
+                    tag: Tag(83),
+                    bitwidth: IntegerWidth::Eight,
+                    value: Box::new(DataItem::ByteString(ByteString {
+                        data: hex!("3fff80000000000000000000000000003ffb999999999999999999999999999a80000000000000000000000000000000ffff0000000000000000000000000000").into(),
+                        bitwidth: IntegerWidth::Eight,
+                    })),
+                },
+                indoc!(r#"
+                    d8 53                                  # typed array of f128, big endian, tag(83)
+                       58 40                               #   bytes(64)
+                          3fff8000000000000000000000000000 #     float(1.5)
+                          3ffb999999999999999999999999999a #     float(0.1000000000000000000000000000000000048148248609680896326399448564623182963452541205384704880998469889163970947265625)
+                          80000000000000000000000000000000 #     float(-0.0)
+                          ffff0000000000000000000000000000 #     float(-Infinity)
+                "#),
+            }
+
+            f128_be_nan(hex2value, value2hex) {
+                DataItem::Tag {
+                    tag: Tag(83),
+                    bitwidth: IntegerWidth::Eight,
+                    value: Box::new(DataItem::ByteString(ByteString {
+                        data: hex!("7fff80000000000000000000000000007fff800000000000000000000000002affff0000000000000000000000000001").into(),
+                        bitwidth: IntegerWidth::Eight,
+                    })),
+                },
+                indoc!(r#"
+                    d8 53                                  # typed array of f128, big endian, tag(83)
+                       58 30                               #   bytes(48)
+                          7fff8000000000000000000000000000 #     float(NaN)
+                          7fff800000000000000000000000002a #     float(NaN, quiet, payload(0x2a))
+                          ffff0000000000000000000000000001 #     float(-NaN, signaling, payload(0x1))
+                "#),
+            }
+
             f128_le(hex2value, value2hex) {
                 DataItem::Tag {
-                    tag: Tag::TYPED_ARRAY_F128_LITTLE_ENDIAN,
+                    tag: Tag(87),
                     bitwidth: IntegerWidth::Eight,
                     value: Box::new(DataItem::ByteString(ByteString {
-                        data: hex!("3ff2fff4fff8fff43ff2fff4fff8fff4").into(),
-                        bitwidth: IntegerWidth::Zero,
+                        data: hex!("0000000000000000000000000000ff3f9a99999999999999999999999999fbbf").into(),
+                        bitwidth: IntegerWidth::Eight,
                     })),
                 },
-                indoc!("
+                indoc!(r#"
                     d8 57                                  # typed array of f128, little endian, tag(87)
-                       50                                  #   bytes(16)
-                          3ff2fff4fff8fff43ff2fff4fff8fff4 #     float(TODO: f128 unsupported)
-                "),
+                       58 20                               #   bytes(32)
+                          0000000000000000000000000000ff3f #     float(1.0)
+                          9a99999999999999999999999999fbbf #     float(-0.1000000000000000000000000000000000048148248609680896326399448564623182963452541205384704880998469889163970947265625)
+                "#),
             }
 
             u16_be_invalid_length(hex2value, value2hex) {
@@ -2261,3 +5372,540 @@ testcases! {
         }
     }
 }
+
+fn with_app_literals(diag: &str) -> String {
+    let value = utils::parse_diag(diag).unwrap();
+    let output = value.to_diag_with(&cbor_diag::DiagOptions {
+        app_literals: true,
+        ..cbor_diag::DiagOptions::default()
+    });
+    assert_eq!(
+        utils::parse_diag(&output).unwrap().to_bytes(),
+        value.to_bytes()
+    );
+    output
+}
+
+#[test]
+fn invalid_app_literals() {
+    for diag in [
+        "dt'2023-01-01'",
+        "DT'yesterday'",
+        "ip'192.0.2'",
+        "ip'192.0.2.0/33'",
+        "uuid'8c8a8d48'",
+        "unknown'text'",
+    ] {
+        assert!(utils::parse_diag(diag).is_err(), "{diag}");
+    }
+}
+
+#[test]
+fn app_literals_output() {
+    assert_eq!(
+        with_app_literals(r#"[1(1672531200), 1(-14159024), 1(1.5), 0("2023-01-01T00:00:00Z")]"#),
+        "[dt'2023-01-01T00:00:00Z',dt'1969-07-21T02:56:16Z',dt'1970-01-01T00:00:01.500Z',DT'2023-01-01T00:00:00Z']"
+    );
+    assert_eq!(
+        with_app_literals(
+            "[52(h'c0000201'), 52([24, h'c00002']), 52([h'c0000201', 24]), 54([32, h'20010db8'])]"
+        ),
+        "[ip'192.0.2.1',ip'192.0.2.0/24',ip'192.0.2.1/24',ip'2001:db8::/32']"
+    );
+    assert_eq!(
+        with_app_literals("37(h'8c8a8d48d5ac4e038f6754bd67da3a86')"),
+        "uuid'8c8a8d48-d5ac-4e03-8f67-54bd67da3a86'"
+    );
+}
+
+#[test]
+fn app_literals_disabled_by_default() {
+    assert_eq!(
+        utils::parse_diag("dt'2023-01-01T00:00:00Z'")
+            .unwrap()
+            .to_diag(),
+        "1(1672531200)"
+    );
+}
+
+#[test]
+fn app_literals_output_fallback() {
+    // Invalid contents, non-preferred encodings and prefixes with trailing
+    // zero bytes cannot be represented
+    for diag in [
+        r#"0("yesterday")"#,
+        "1(1_1)",
+        "1_0(0)",
+        r#"1("2023-01-01T00:00:00Z")"#,
+        "52(h'0102')",
+        "52([24,h'c0000200'])",
+        "37(h'01')",
+    ] {
+        assert_eq!(with_app_literals(diag), diag);
+    }
+}
+
+fn truncated(diag: &str, truncate: usize, pretty: bool) -> String {
+    utils::parse_diag(diag)
+        .unwrap()
+        .to_diag_with(&cbor_diag::DiagOptions {
+            pretty,
+            truncate: Some(truncate),
+            ..cbor_diag::DiagOptions::default()
+        })
+}
+
+#[test]
+fn elision_output_fallback() {
+    // Only nulls and strings of the same type with elisions between them
+    // parse back as elisions
+    for diag in [
+        r#"888(["abc"])"#,
+        r#"888(["abc",h'01',null])"#,
+        r#"888(["abc","def",null])"#,
+        "888([1,null])",
+        "888(1)",
+    ] {
+        assert_eq!(utils::parse_diag(diag).unwrap().to_diag(), diag);
+    }
+}
+
+#[test]
+fn truncate() {
+    assert_eq!(
+        truncated(
+            r#"[[1, 2, 3, 4, 5], {1: 2, 3: 4, 5: 6, 7: 8, 9: 10}, "abcdef", "ééé", h'0102']"#,
+            4,
+            false
+        ),
+        r#"[[1,2,3,4,...],{1:2,3:4,5:6,7:8,...},"abcd" ...,"éé" ...,...]"#
+    );
+}
+
+#[test]
+fn truncate_pretty() {
+    assert_eq!(
+        utils::DisplayDebug(truncated(
+            r#"{"a": [1, 2, 3, 4], "b": h'01020304', "c": "a longer text string"}"#,
+            3,
+            true
+        )),
+        utils::DisplayDebug(indoc!(
+            r#"
+            {
+                "a": [1, 2, 3, ...],
+                "b": h'010203' ...,
+                "c": "a l" ...,
+            }"#
+        ))
+    );
+}
+
+fn with_typed_arrays(diag: &str, pretty: bool, typed_arrays: TypedArrayStyle) -> String {
+    let value = utils::parse_diag(diag).unwrap();
+    let output = value.to_diag_with(&cbor_diag::DiagOptions {
+        pretty,
+        typed_arrays,
+        ..cbor_diag::DiagOptions::default()
+    });
+    assert_eq!(
+        utils::parse_diag(&output).unwrap().to_bytes(),
+        value.to_bytes()
+    );
+    output
+}
+
+#[test]
+fn typed_array_commented() {
+    let diag = "[64(h'0102ff'), 85(h'0000c03fcdcccc3d')]";
+    assert_eq!(
+        with_typed_arrays(diag, false, TypedArrayStyle::Commented),
+        "[64(h'0102ff')/[1,2,255]/,85(h'0000c03fcdcccc3d')/[1.5,0.1]/]"
+    );
+    assert_eq!(
+        with_typed_arrays(diag, true, TypedArrayStyle::Commented),
+        "[64(h'0102ff') / [1, 2, 255] /, 85(h'0000c03fcdcccc3d') / [1.5, 0.1] /]"
+    );
+}
+
+#[test]
+fn typed_array_literal() {
+    let diag = "[64(h'0102ff'), 79(h'feffffffffffffff'), 80(h'3c00fc00'), 85(h'0000c03fcdcccc3d')]";
+    assert_eq!(
+        with_typed_arrays(diag, false, TypedArrayStyle::Literal),
+        "[64(u8'1,2,255'),79(i64le'-2'),80(f16be'1.0,-Infinity'),85(f32le'1.5,0.1')]"
+    );
+    assert_eq!(
+        utils::DisplayDebug(with_typed_arrays(diag, true, TypedArrayStyle::Literal)),
+        utils::DisplayDebug(
+            indoc!(
+                "
+                [
+                    64(u8'1, 2, 255'),
+                    79(i64le'-2'),
+                    80(f16be'1.0, -Infinity'),
+                    85(f32le'1.5, 0.1'),
+                ]
+                "
+            )
+            .trim_end()
+        )
+    );
+}
+
+#[test]
+fn typed_array_literal_f128() {
+    assert_eq!(
+        with_typed_arrays(
+            "[83(h'3fff800000000000000000000000000080000000000000000000000000000000'), 87(h'0000000000000000000000000000ff7f')]",
+            false,
+            TypedArrayStyle::Literal
+        ),
+        "[83(f128be'1.5,-0.0'),87(f128le'Infinity')]"
+    );
+
+    // The exact decimal value of the smallest normal value round-trips
+    let output = with_typed_arrays(
+        "83(h'00010000000000000000000000000000')",
+        false,
+        TypedArrayStyle::Literal,
+    );
+    assert!(output.starts_with("83(f128be'3.3621031431120935062626778173217526"));
+    assert!(output.ends_with("e-4932')"));
+}
+
+#[test]
+fn typed_array_literal_fallback() {
+    // Invalid length and NaNs with a payload
+    for diag in [
+        "65(h'010203')",
+        "82(h'7ff8000000000001')",
+        "83(h'7fff800000000000000000000000002a')",
+    ] {
+        assert_eq!(
+            with_typed_arrays(diag, false, TypedArrayStyle::Literal),
+            diag
+        );
+    }
+}
+
+fn with_expand_arrays(diag: &str, pretty: bool) -> String {
+    utils::parse_diag(diag)
+        .unwrap()
+        .to_diag_with(&cbor_diag::DiagOptions {
+            pretty,
+            expand_arrays: true,
+            ..cbor_diag::DiagOptions::default()
+        })
+}
+
+#[test]
+fn expand_arrays_row_major() {
+    assert_eq!(
+        with_expand_arrays("40([[2, 3], 64(h'010203040506')])", false),
+        "40([[2,3],64(h'010203040506')])/[[1,2,3],[4,5,6]]/"
+    );
+}
+
+#[test]
+fn expand_arrays_column_major() {
+    assert_eq!(
+        with_expand_arrays("1040([[2, 3], [1, 2, 3, 4, 5, 6]])", false),
+        "1040([[2,3],[1,2,3,4,5,6]])/[[1,3,5],[2,4,6]]/"
+    );
+}
+
+#[test]
+fn expand_arrays_pretty() {
+    assert_eq!(
+        utils::DisplayDebug(with_expand_arrays(
+            "40([[2, 2, 2], 41([1, 2, 3, 4, 5, 6, 7, 8])])",
+            true
+        )),
+        utils::DisplayDebug(
+            indoc!(
+                "
+                40([
+                    [2, 2, 2],
+                    41([1, 2, 3, 4, 5, 6, 7, 8]),
+                ]) / [
+                    [[1, 2], [3, 4]],
+                    [[5, 6], [7, 8]],
+                ] /
+                "
+            )
+            .trim_end()
+        )
+    );
+}
+
+#[test]
+fn expand_arrays_slash_in_elements() {
+    let output = with_expand_arrays(r#"40([[2], ["a/b", "c"]])"#, false);
+    assert_eq!(output, r#"40([[2],["a/b","c"]])/["a\u002fb","c"]/"#);
+    assert_eq!(
+        utils::parse_diag(&output).unwrap(),
+        utils::parse_diag(r#"40([[2], ["a/b", "c"]])"#).unwrap()
+    );
+}
+
+#[test]
+fn expand_arrays_invalid() {
+    assert_eq!(
+        with_expand_arrays("40([[2, 4], [1, 2, 3]])", true),
+        "40([[2, 4], [1, 2, 3]])"
+    );
+    // The largest f128 cannot be shown exactly as a float
+    assert_eq!(
+        with_expand_arrays("40([[1], 83(h'7ffeffffffffffffffffffffffffffff')])", false),
+        "40([[1],83(h'7ffeffffffffffffffffffffffffffff')])"
+    );
+}
+
+#[test]
+fn expand_arrays_disabled_by_default() {
+    assert_eq!(
+        utils::parse_diag("40([[2, 3], 64(h'010203040506')])")
+            .unwrap()
+            .to_diag(),
+        "40([[2,3],64(h'010203040506')])"
+    );
+}
+
+fn resolved(diag: &str) -> Result<String, String> {
+    utils::parse_diag(diag)
+        .unwrap()
+        .resolve_references()
+        .map(|value| value.to_diag())
+        .map_err(|err| err.to_string())
+}
+
+#[test]
+fn resolve_shared_values() {
+    assert_eq!(
+        resolved(r#"[28([1, "abc"]), 29(0), 28(2), {29(1): 29(0)}]"#),
+        Ok(r#"[[1,"abc"],[1,"abc"],2,{2:[1,"abc"]}]"#.to_owned())
+    );
+}
+
+#[test]
+fn resolve_nested_shared_values() {
+    assert_eq!(
+        resolved("[28([28(1), 29(1)]), 29(0), 29(1)]"),
+        Ok("[[1,1],[1,1],1]".to_owned())
+    );
+}
+
+#[test]
+fn resolve_cyclic_shared_value() {
+    assert_eq!(
+        resolved("28([29(0)])"),
+        Err("TODO cbor-diag::Error: cyclic reference to shared value 0".to_owned())
+    );
+}
+
+#[test]
+fn resolve_unknown_shared_value() {
+    assert_eq!(
+        resolved("[29(0), 28(1)]"),
+        Err("TODO cbor-diag::Error: reference to unknown shared value 0".to_owned())
+    );
+}
+
+#[test]
+fn resolve_stringrefs() {
+    // Example from http://cbor.schmorp.de/stringref
+    assert_eq!(
+        resolved(
+            r#"256([
+                {"name": "Cocktail", "count": 417, "rank": 4},
+                {25(0): 25(1), 25(2): 1, 25(3): 25(3)}
+            ])"#
+        ),
+        Ok(
+            r#"[{"name":"Cocktail","count":417,"rank":4},{"name":"Cocktail","count":1,"rank":"rank"}]"#
+                .to_owned()
+        )
+    );
+}
+
+#[test]
+fn resolve_short_strings_are_not_referenceable() {
+    assert_eq!(
+        resolved(r#"256(["ab", h'0102', "abc", 25(0)])"#),
+        Ok(r#"["ab",h'0102',"abc","abc"]"#.to_owned())
+    );
+}
+
+#[test]
+fn resolve_nested_stringref_namespaces() {
+    assert_eq!(
+        resolved(r#"256(["aaa", 256(["bbb", 25(0)]), 25(0), 25(1)])"#),
+        Err("TODO cbor-diag::Error: reference to unknown string 1".to_owned())
+    );
+    assert_eq!(
+        resolved(r#"256(["aaa", 256(["bbb", 25(0)]), 25(0)])"#),
+        Ok(r#"["aaa",["bbb","bbb"],"aaa"]"#.to_owned())
+    );
+}
+
+#[test]
+fn resolve_stringref_outside_namespace() {
+    assert_eq!(
+        resolved("25(0)"),
+        Err("TODO cbor-diag::Error: stringref outside of a stringref namespace".to_owned())
+    );
+}
+
+#[test]
+fn resolve_other_tags_are_kept() {
+    assert_eq!(
+        resolved(r#"256([28(1("abc")), 29(0), 25(0)])"#),
+        Ok(r#"[1("abc"),1("abc"),"abc"]"#.to_owned())
+    );
+}
+
+#[test]
+fn resolve_exponential_shared_values() {
+    // Each value refers to the previous one twice, doubling its size
+    let diag = format!(
+        "[28(0), {}]",
+        (0..26)
+            .map(|i| format!("28([29({i}), 29({i})])"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    assert_eq!(
+        resolved(&diag),
+        Err(
+            "TODO cbor-diag::Error: shared values expand to too many items to be resolved"
+                .to_owned()
+        )
+    );
+}
+
+#[test]
+fn exponential_shared_values() {
+    // Each value refers to the previous one twice, doubling its size, so only
+    // the start of the last is described
+    let diag = format!(
+        "[28(0), {}]",
+        (0..26)
+            .map(|i| format!("28([29({i}), 29({i})])"))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    let hex = utils::parse_diag(diag).unwrap().to_hex();
+    let description = hex
+        .lines()
+        .find_map(|line| line.split_once("reference-to(25): "))
+        .unwrap()
+        .1;
+    assert!(description.starts_with("[[[[[[[[[[[[[[[[[[[[[[[[[0,0],[0,0]],"));
+    assert!(description.ends_with(",...]"));
+}
+
+fn strip_magic(bytes: &[u8]) -> String {
+    parse_bytes_with(bytes, &ParseOptions { strip_magic: true })
+        .unwrap()
+        .to_diag()
+}
+
+#[test]
+fn magic_detect_self_described() {
+    assert_eq!(
+        Magic::detect(&hex!("d9d9f7a0")),
+        Some((Magic::SelfDescribed, 3))
+    );
+}
+
+#[test]
+fn magic_detect_sequence() {
+    assert_eq!(
+        Magic::detect(&hex!("d9d9f843424f520102")),
+        Some((Magic::Sequence, 7))
+    );
+}
+
+#[test]
+fn magic_detect_labeled_non_cbor() {
+    assert_eq!(
+        Magic::detect(&hex!("d9d9f943424f5268656c6c6f")),
+        Some((Magic::LabeledNonCbor, 7))
+    );
+}
+
+#[test]
+fn magic_detect_content_format() {
+    assert_eq!(
+        Magic::detect(&hex!("da6374003c01")),
+        Some((Magic::ContentFormat(60), 5))
+    );
+}
+
+#[test]
+fn magic_detect_content_format_sequence() {
+    assert_eq!(
+        Magic::detect(&hex!("da6374003f43424f520102")),
+        Some((Magic::ContentFormatSequence(63), 9))
+    );
+}
+
+#[test]
+fn magic_detect_none() {
+    assert_eq!(Magic::detect(&hex!("")), None);
+    assert_eq!(Magic::detect(&hex!("d9d9f8")), None);
+    assert_eq!(Magic::detect(&hex!("d9d9f84201020304")), None);
+    assert_eq!(Magic::detect(&hex!("da6362003c01")), None);
+}
+
+#[test]
+fn strip_magic_self_described() {
+    assert_eq!(strip_magic(&hex!("d9d9f7a0")), "{}");
+}
+
+#[test]
+fn strip_magic_sequence() {
+    assert_eq!(strip_magic(&hex!("d9d9f843424f5201")), "1");
+}
+
+#[test]
+fn strip_magic_sequence_of_many() {
+    let options = ParseOptions { strip_magic: true };
+    assert!(parse_bytes_with(hex!("d9d9f843424f520102"), &options).is_err());
+    assert!(parse_bytes_with(hex!("d9d9f843424f52"), &options).is_err());
+    assert!(parse_bytes_with(hex!("da6374003f43424f520102"), &options).is_err());
+}
+
+#[test]
+fn strip_magic_nested() {
+    assert_eq!(strip_magic(&hex!("d9d9f7da6374003ca0")), "{}");
+    assert_eq!(strip_magic(&hex!("d9d9f7d9d9f843424f5201")), "1");
+}
+
+#[test]
+fn strip_magic_content_format() {
+    assert_eq!(strip_magic(&hex!("da6374003c01")), "1");
+}
+
+#[test]
+fn strip_magic_labeled_non_cbor() {
+    assert!(parse_bytes_with(hex!("d9d9f943424f52"), &ParseOptions { strip_magic: true }).is_err());
+}
+
+#[test]
+fn magic_kept_by_default() {
+    let bytes = hex!("d9d9f7a0");
+    assert_eq!(
+        parse_bytes_with(bytes, &ParseOptions::default()).unwrap(),
+        parse_bytes(bytes).unwrap()
+    );
+}
+
+#[test]
+fn magic_content_format_tag() {
+    let tag = utils::parse_diag("1668546620(1)").unwrap();
+    assert_eq!(tag.to_bytes(), hex!("da6374003c01"));
+    assert_eq!(Tag::from_content_format(60).content_format(), Some(60));
+    assert_eq!(Tag::SELF_DESCRIBE_CBOR.content_format(), None);
+}
@@ -66,6 +66,33 @@ testcases! {
                 r#""\\\"""#,
             }
         }
+
+        control(diag2value, value2diag) {
+            DataItem::TextString(TextString {
+                data: "a\nb\tc\r\u{8}\u{c}\u{0}\u{1f}\u{7f}\u{85}".into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            {
+                r#""a\nb\tc\r\b\f\u0000\u001f\u007f\u0085""#,
+                r#""a\nb\tc\r\b\f\u0000\u001f\u007f\u0085""#,
+            }
+        }
+
+        json_escapes(diag2value) {
+            DataItem::TextString(TextString {
+                data: "/\u{e9}\u{1f600}'".into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { r#""\/\u00E9\ud83d\ude00\'""# }
+        }
+
+        edn_escapes(diag2value) {
+            DataItem::TextString(TextString {
+                data: "\u{e9}\u{1f600}".into(),
+                bitwidth: IntegerWidth::Unknown,
+            }),
+            { r#""\u{e9}\u{1F600}""# }
+        }
     }
 
     mod tiny {
@@ -590,3 +617,24 @@ testcases! {
         }
     }
 }
+
+#[test]
+fn invalid_escapes() {
+    assert!(utils::parse_diag(r#""\x""#).is_err());
+    assert!(utils::parse_diag(r#""\u12""#).is_err());
+    assert!(utils::parse_diag(r#""\ud83d""#).is_err());
+    assert!(utils::parse_diag(r#""\ude00""#).is_err());
+    assert!(utils::parse_diag(r#""\u{110000}""#).is_err());
+    assert!(utils::parse_diag(r#""\u{d800}""#).is_err());
+}
+
+#[test]
+fn escape_non_ascii() {
+    let value = utils::parse_diag(r#"["caf\u00e9 \u{1f600}", "\n"]"#).unwrap();
+    let output = value.to_diag_with(&cbor_diag::DiagOptions {
+        escape_non_ascii: true,
+        ..cbor_diag::DiagOptions::default()
+    });
+    assert_eq!(output, r#"["caf\u00e9 \ud83d\ude00","\n"]"#);
+    assert_eq!(utils::parse_diag(&output).unwrap(), value);
+}
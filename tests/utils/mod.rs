@@ -1,7 +1,9 @@
+#[allow(unused_imports)]
 pub use cbor_diag::{parse_diag, parse_hex};
 
 #[derive(Eq)]
 #[doc(hidden)]
+#[allow(dead_code)]
 pub struct DisplayDebug<T>(pub T);
 
 impl<T, U> PartialEq<DisplayDebug<U>> for DisplayDebug<T>
@@ -27,6 +29,7 @@ pub fn remove_comments(hex: impl AsRef<str>) -> String {
         .collect()
 }
 
+#[allow(unused_macros)]
 macro_rules! testcases {
     (
         @testcase $name:ident(diag2value $(, $rest:ident)*) {